@@ -5,8 +5,11 @@ echo ============================================
 echo.
 
 echo [Step 1/3] Building product app (app.exe)...
-call npm run tauri:build:product
-if %errorlevel% neq 0 (
+set VITE_APP_MODE=product
+call npm run tauri:build
+set BUILD_RESULT=%errorlevel%
+set VITE_APP_MODE=
+if %BUILD_RESULT% neq 0 (
     echo ERROR: Failed to build product app
     exit /b 1
 )
@@ -1,3 +1,9 @@
 fn main() {
+  // 템플릿에 컴파일해 넣는 내장 데이터 암호화 키 (payload_crypto 참고)
+  println!("cargo:rerun-if-env-changed=TUTORIAL_PAYLOAD_KEY");
+  // 신뢰할 라이선스 발급자 공개 키 (license 참고)
+  println!("cargo:rerun-if-env-changed=TUTORIAL_LICENSE_ISSUER_KEY");
+  // 내장 데이터 서명 확인용 공개 키 (payload_signature 참고)
+  println!("cargo:rerun-if-env-changed=TUTORIAL_PAYLOAD_VERIFY_KEY");
   tauri_build::build()
 }
@@ -0,0 +1,58 @@
+// 페이로드 파서 퍼징
+//
+// 임의의 바이트를 exe 파일로 보고 트레일러/매니페스트 해석, 프로젝트 JSON,
+// 라이선스/서명 확인, 미디어 읽기(암호화된 엔트리는 고정 키로 복호화)와 무결성 검증까지 수행한다. 어떤 입력에서도 패닉이나 거대한
+// 할당 없이 에러를 반환해야 한다.
+//
+// 실행: cargo +nightly fuzz run payload_parser (src-tauri/fuzz 에서)
+
+#![no_main]
+#![allow(dead_code)]
+
+use std::io::Cursor;
+
+use libfuzzer_sys::fuzz_target;
+
+// tauri 의존성 없이 빌드하기 위해 앱 소스 모듈을 직접 포함
+#[path = "../../src/compression.rs"]
+mod compression;
+#[path = "../../src/payload.rs"]
+mod payload;
+#[path = "../../src/payload_crypto.rs"]
+mod payload_crypto;
+#[path = "../../src/license.rs"]
+mod license;
+#[path = "../../src/payload_signature.rs"]
+mod payload_signature;
+
+use payload::Payload;
+use payload_crypto::PayloadKey;
+
+const FUZZ_KEY: &str = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f";
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(mut payload) = Payload::from_source(Box::new(Cursor::new(data.to_vec())), data.len() as u64) else {
+        return;
+    };
+
+    if let Ok(key) = PayloadKey::from_hex(FUZZ_KEY) {
+        payload.set_key(key);
+    }
+
+    let _ = payload.project_json();
+
+    let Ok(manifest) = payload.manifest() else {
+        return;
+    };
+    let _ = license::check_license(manifest);
+    let _ = payload_signature::check_signature(manifest);
+    for entry in &manifest.media {
+        let _ = payload.read_media(entry);
+        let size = entry.content_size();
+        if size > 0 {
+            let _ = payload.read_media_range(entry, 0, size - 1);
+            let _ = payload.read_media_range(entry, size / 2, size - 1);
+        }
+    }
+    let _ = payload.verify(true);
+});
@@ -0,0 +1,167 @@
+// 빌드 결과를 원자적으로 기록
+//
+// 제품은 출력 경로 옆의 임시 경로(같은 디렉토리여야 rename이 원자적)에 만든 뒤 디스크에
+// 동기화하고, 모두 성공했을 때만 최종 경로로 rename한다. 빌드가 실패/취소되면 임시 경로만
+// 지워지고 이전 빌드 결과는 그대로 남는다.
+// .app 번들(디렉토리)은 rename으로 덮어쓸 수 없으므로 기존 번들을 옆으로 옮긴 뒤 교체한다.
+
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+// 출력 경로 옆의 숨김 임시 경로
+pub fn staging_path(output: &Path) -> PathBuf {
+    sibling_path(output, "partial")
+}
+
+fn sibling_path(output: &Path, suffix: &str) -> PathBuf {
+    let name = output.file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    output.with_file_name(format!(".{}.{}.{}", name, std::process::id(), suffix))
+}
+
+// 파일(번들이면 안의 모든 파일)의 내용을 디스크에 기록
+pub fn sync(path: &Path) -> Result<(), String> {
+    let metadata = fs::symlink_metadata(path)
+        .map_err(|e| format!("출력 파일 확인 실패: {}", e))?;
+
+    if metadata.is_dir() {
+        let entries = fs::read_dir(path)
+            .map_err(|e| format!("출력 폴더 읽기 실패: {}", e))?;
+        for entry in entries {
+            let entry = entry.map_err(|e| format!("출력 폴더 읽기 실패: {}", e))?;
+            sync(&entry.path())?;
+        }
+        return Ok(());
+    }
+    if metadata.file_type().is_symlink() {
+        return Ok(());
+    }
+
+    // Windows에서는 쓰기 권한으로 열어야 버퍼를 비울 수 있음
+    fs::OpenOptions::new()
+        .write(true)
+        .open(path)
+        .and_then(|file| file.sync_all())
+        .map_err(|e| format!("출력 파일 동기화 실패 ({}): {}", path.display(), e))
+}
+
+// 작은 파일(라이선스 등)을 임시 경로에 기록하고 동기화 (commit 전까지 최종 경로는 그대로)
+pub fn stage_file(output: &Path, data: &[u8]) -> Result<PathBuf, String> {
+    let staging = staging_path(output);
+    let mut file = fs::File::create(&staging)
+        .map_err(|e| format!("임시 파일 생성 실패: {}", e))?;
+    let written = file.write_all(data).and_then(|_| file.sync_all());
+    if let Err(e) = written {
+        let _ = fs::remove_file(&staging);
+        return Err(format!("임시 파일 쓰기 실패: {}", e));
+    }
+    Ok(staging)
+}
+
+// 임시 경로의 결과로 최종 경로를 교체
+pub fn commit(staging: &Path, output: &Path) -> Result<(), String> {
+    if staging.is_dir() && output.exists() {
+        // 기존 번들을 옆으로 옮겨 두고 교체, 실패하면 되돌림
+        let previous = sibling_path(output, "previous");
+        fs::rename(output, &previous)
+            .map_err(|e| format!("기존 출력 이동 실패: {}", e))?;
+        if let Err(e) = fs::rename(staging, output) {
+            let _ = fs::rename(&previous, output);
+            return Err(format!("출력 교체 실패: {}", e));
+        }
+        let _ = fs::remove_dir_all(&previous);
+    } else {
+        // 파일은 rename이 기존 파일을 원자적으로 덮어씀 (Windows 포함)
+        fs::rename(staging, output)
+            .map_err(|e| format!("출력 교체 실패 (실행 중인 제품이면 종료 후 다시 시도해주세요): {}", e))?;
+    }
+
+    sync_parent_dir(output);
+    Ok(())
+}
+
+// rename 결과(디렉토리 엔트리)를 디스크에 기록 (Windows는 디렉토리를 열 수 없어 생략)
+fn sync_parent_dir(path: &Path) {
+    #[cfg(unix)]
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        let _ = fs::File::open(parent).and_then(|dir| dir.sync_all());
+    }
+    #[cfg(not(unix))]
+    let _ = path;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> TempDir {
+            let dir = std::env::temp_dir().join(format!("atomic_output_{}_{}", name, std::process::id()));
+            let _ = fs::remove_dir_all(&dir);
+            fs::create_dir_all(&dir).unwrap();
+            TempDir(dir)
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn staging_path_is_hidden_sibling() {
+        let output = Path::new("out").join("MyTutorial.exe");
+        let staging = staging_path(&output);
+        assert_eq!(staging.parent(), output.parent());
+        let name = staging.file_name().unwrap().to_string_lossy();
+        assert!(name.starts_with(".MyTutorial.exe."));
+        assert!(name.ends_with(".partial"));
+    }
+
+    #[test]
+    fn commit_replaces_file() {
+        let dir = TempDir::new("file");
+        let output = dir.0.join("product.exe");
+        fs::write(&output, b"old").unwrap();
+
+        // commit 전까지 이전 결과는 그대로
+        let staging = stage_file(&output, b"new").unwrap();
+        sync(&staging).unwrap();
+        assert_eq!(fs::read(&output).unwrap(), b"old");
+
+        commit(&staging, &output).unwrap();
+        assert_eq!(fs::read(&output).unwrap(), b"new");
+        assert!(!staging.exists());
+    }
+
+    #[test]
+    fn commit_replaces_bundle() {
+        let dir = TempDir::new("bundle");
+        let output = dir.0.join("Product.app");
+        fs::create_dir_all(output.join("Contents")).unwrap();
+        fs::write(output.join("Contents").join("old"), b"old").unwrap();
+
+        let staging = staging_path(&output);
+        fs::create_dir_all(staging.join("Contents")).unwrap();
+        fs::write(staging.join("Contents").join("new"), b"new").unwrap();
+        sync(&staging).unwrap();
+
+        commit(&staging, &output).unwrap();
+        assert!(!staging.exists());
+        assert!(!output.join("Contents").join("old").exists());
+        assert_eq!(fs::read(output.join("Contents").join("new")).unwrap(), b"new");
+        // 옆으로 옮겨 둔 기존 번들은 지워짐
+        assert_eq!(fs::read_dir(&dir.0).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn sync_fails_for_missing_output() {
+        let dir = TempDir::new("missing");
+        assert!(sync(&dir.0.join("missing.exe")).is_err());
+    }
+}
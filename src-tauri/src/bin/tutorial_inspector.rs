@@ -0,0 +1,283 @@
+// 제품 exe 점검 도구 (지원 담당자용)
+//
+// `tutorial-inspector <product.exe> [--json] [--key-file <key>] [--dump <media-id> [--output <file>]]`
+// 내장 데이터 포맷(V1/V2), 매니페스트, 미디어 목록, 프로젝트 이름/페이지 수를 출력하고
+// --dump를 주면 미디어 하나를 파일로 꺼낸다.
+//   --json     매니페스트 JSON 전체 출력
+//   --key-file 암호화된 빌드의 키 파일 (없으면 컴파일된 키 또는 제품 옆 tutorial.license)
+// 종료 코드: 0 성공, 1 실패, 2 인자 오류
+
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+// 창/tauri 의존성 없이 빌드하기 위해 앱 소스 모듈을 직접 포함 (퍼징 타깃과 같은 방식)
+// 점검 도구가 쓰지 않는 앱 함수가 있으므로 포함한 모듈에만 dead_code를 허용
+#[allow(dead_code)]
+#[path = "../compression.rs"]
+mod compression;
+#[allow(dead_code)]
+#[path = "../license.rs"]
+mod license;
+#[allow(dead_code)]
+#[path = "../payload.rs"]
+mod payload;
+#[allow(dead_code)]
+#[path = "../payload_crypto.rs"]
+mod payload_crypto;
+#[allow(dead_code)]
+#[path = "../payload_signature.rs"]
+mod payload_signature;
+#[allow(dead_code)]
+#[path = "../pe_resources.rs"]
+mod pe_resources;
+#[allow(dead_code)]
+#[path = "../product_target.rs"]
+mod product_target;
+
+use payload::{BuildManifest, MediaManifestEntry, Payload, PayloadFormat};
+use payload_crypto::PayloadKey;
+
+// 미디어를 꺼낼 때 한 번에 읽는 크기
+const DUMP_CHUNK_SIZE: u64 = 1024 * 1024;
+
+const USAGE: &str = "사용법: tutorial-inspector <product.exe> [--json] [--key-file <key>] [--dump <media-id> [--output <file>]]";
+
+struct Options {
+    input: PathBuf,
+    json: bool,
+    key_file: Option<PathBuf>,
+    dump: Option<String>,
+    output: Option<PathBuf>,
+}
+
+fn parse_args() -> Result<Options, String> {
+    let mut options = Options {
+        input: PathBuf::new(),
+        json: false,
+        key_file: None,
+        dump: None,
+        output: None,
+    };
+    let mut input = None;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| args.next().ok_or_else(|| format!("{}에 값이 필요합니다.", name));
+        match arg.as_str() {
+            "--json" => options.json = true,
+            "--key-file" | "-k" => options.key_file = Some(PathBuf::from(value(&arg)?)),
+            "--dump" | "-d" => options.dump = Some(value(&arg)?),
+            "--output" | "-o" => options.output = Some(PathBuf::from(value(&arg)?)),
+            "--help" | "-h" => return Err(USAGE.to_string()),
+            other if other.starts_with('-') => return Err(format!("알 수 없는 옵션입니다: {}\n{}", other, USAGE)),
+            other if input.is_none() => input = Some(PathBuf::from(other)),
+            other => return Err(format!("입력 파일은 하나만 지정할 수 있습니다: {}\n{}", other, USAGE)),
+        }
+    }
+
+    options.input = input.ok_or_else(|| USAGE.to_string())?;
+    if options.output.is_some() && options.dump.is_none() {
+        return Err(format!("--output은 --dump와 함께 사용합니다.\n{}", USAGE));
+    }
+    Ok(options)
+}
+
+// 바이트 크기를 읽기 쉽게 표시
+fn format_size(bytes: u64) -> String {
+    if bytes >= 1_048_576 {
+        format!("{:.1} MB", bytes as f64 / 1_048_576.0)
+    } else if bytes >= 1024 {
+        format!("{:.1} KB", bytes as f64 / 1024.0)
+    } else {
+        format!("{} B", bytes)
+    }
+}
+
+fn print_manifest(manifest: &BuildManifest) {
+    println!("매니페스트:");
+    println!("  프로젝트 데이터  offset {}, size {}", manifest.project_json_offset, manifest.project_json_size);
+    println!("  프로젝트 SHA-256 {}", manifest.project_json_sha256.as_deref().unwrap_or("-"));
+    if let (Some(offset), Some(size)) = (manifest.app_icon_offset, manifest.app_icon_size) {
+        println!("  앱 아이콘        offset {}, size {}, SHA-256 {}", offset, size, manifest.app_icon_sha256.as_deref().unwrap_or("-"));
+    }
+    println!("  시작 시 검증     {}", if manifest.verify_on_startup { "전체 미디어" } else { "프로젝트 데이터만" });
+    match &manifest.encryption_key_id {
+        Some(key_id) if manifest.is_encrypted() => println!("  암호화           AES-256-GCM (키 ID {})", key_id),
+        _ => println!("  암호화           없음"),
+    }
+    match &manifest.license {
+        Some(license) => println!(
+            "  라이선스         발급자 {}, 만료 {}, 허용 기기 {}",
+            license.terms.issuer,
+            license.terms.expires_at.map(license::format_date).unwrap_or_else(|| "없음".to_string()),
+            if license.terms.allowed_machine_ids.is_empty() {
+                "제한 없음".to_string()
+            } else {
+                license.terms.allowed_machine_ids.join(", ")
+            }
+        ),
+        None => println!("  라이선스         없음"),
+    }
+    match &manifest.signature {
+        Some(signature) => println!("  서명             {}", signature.public_key),
+        None => println!("  서명             없음"),
+    }
+}
+
+fn media_flags(entry: &MediaManifestEntry) -> String {
+    let mut flags = Vec::new();
+    if let Some(compression) = entry.compression {
+        flags.push(format!("{:?} 압축", compression).to_lowercase());
+    }
+    if entry.encryption.is_some() {
+        flags.push("암호화".to_string());
+    }
+    if let Some(shared_from) = &entry.shared_from {
+        flags.push(format!("{}와 공유", shared_from));
+    }
+    if let Some(poster_for) = &entry.poster_for {
+        flags.push(format!("{} 포스터", poster_for));
+    }
+    flags.join(", ")
+}
+
+fn print_media(media: &[MediaManifestEntry]) {
+    println!("미디어 ({}개):", media.len());
+    for entry in media {
+        let stored = if entry.content_size() != entry.size {
+            format!("{} (저장 {})", format_size(entry.content_size()), format_size(entry.size))
+        } else {
+            format_size(entry.size)
+        };
+        let flags = media_flags(entry);
+        println!(
+            "  {}  {}  {}  {}{}",
+            entry.id,
+            entry.mime_type,
+            stored,
+            entry.name,
+            if flags.is_empty() { String::new() } else { format!("  [{}]", flags) }
+        );
+    }
+}
+
+// 프로젝트 JSON에서 이름/페이지 수 (V1 빌드는 embeddedMedia 목록도 출력)
+fn print_project(project_json: &str, format: PayloadFormat) -> Result<(), String> {
+    let project: serde_json::Value = serde_json::from_str(project_json)
+        .map_err(|e| format!("프로젝트 데이터 파싱 실패: {}", e))?;
+
+    println!("프로젝트:");
+    println!("  이름       {}", project["name"].as_str().unwrap_or("-"));
+    if let Some(id) = project["id"].as_str() {
+        println!("  ID         {}", id);
+    }
+    println!("  페이지 수  {}", project["pages"].as_array().map_or(0, Vec::len));
+    if let Some(updated_at) = project["updatedAt"].as_u64() {
+        println!("  수정일     {} (UTC)", license::format_date(updated_at / 1000));
+    }
+
+    if format == PayloadFormat::V1 {
+        let embedded = project["embeddedMedia"].as_array().map(Vec::as_slice).unwrap_or_default();
+        println!("미디어 ({}개, JSON 내 Base64):", embedded.len());
+        for media in embedded {
+            // Base64 길이로 원본 크기 추정
+            let encoded = media["base64"].as_str().unwrap_or_default();
+            let padding = encoded.bytes().rev().take_while(|&b| b == b'=').count();
+            let size = (encoded.len() / 4 * 3).saturating_sub(padding) as u64;
+            println!(
+                "  {}  {}  {}",
+                media["id"].as_str().unwrap_or("-"),
+                media["mimeType"].as_str().unwrap_or("-"),
+                format_size(size)
+            );
+        }
+    }
+
+    Ok(())
+}
+
+// 미디어 엔트리 하나를 파일로 저장 (압축되지 않은 엔트리는 나누어 읽음)
+fn dump_media(payload: &Payload, media_id: &str, output: Option<&Path>) -> Result<PathBuf, String> {
+    if payload.format() == PayloadFormat::V1 {
+        return Err("V1 빌드는 미디어 꺼내기를 지원하지 않습니다.".to_string());
+    }
+
+    let entry = payload.find_media(media_id)?;
+    let output = output.map(Path::to_path_buf).unwrap_or_else(|| PathBuf::from(&entry.name));
+    let mut file = fs::File::create(&output)
+        .map_err(|e| format!("출력 파일 생성 실패: {}", e))?;
+
+    if entry.compression.is_some() {
+        file.write_all(&payload.read_media(entry)?)
+            .map_err(|e| format!("출력 파일 쓰기 실패: {}", e))?;
+    } else {
+        let total = entry.content_size();
+        let mut start = 0;
+        while start < total {
+            let end = (start + DUMP_CHUNK_SIZE).min(total) - 1;
+            file.write_all(&payload.read_media_range(entry, start, end)?)
+                .map_err(|e| format!("출력 파일 쓰기 실패: {}", e))?;
+            start = end + 1;
+        }
+    }
+
+    Ok(output)
+}
+
+fn inspect(options: &Options) -> Result<(), String> {
+    let payload_file = product_target::payload_path(&options.input);
+    let mut payload = Payload::open(&payload_file)?;
+    if let Some(key_file) = &options.key_file {
+        payload.set_key(PayloadKey::load_file(key_file)?);
+    }
+
+    println!("파일: {}", options.input.display());
+    println!("포맷: {}", match payload.format() {
+        PayloadFormat::V1 => "V1 (프로젝트 JSON + Base64 미디어)",
+        PayloadFormat::V2 => "V2 (바이너리 미디어 + 매니페스트)",
+    });
+
+    if let Ok(manifest) = payload.manifest() {
+        if options.json {
+            let json = serde_json::to_string_pretty(manifest)
+                .map_err(|e| format!("매니페스트 직렬화 실패: {}", e))?;
+            println!("{}", json);
+        } else {
+            print_manifest(manifest);
+        }
+        print_media(&manifest.media);
+    }
+
+    // 암호화된 빌드에서 키가 없어도 매니페스트 정보는 출력
+    match payload.project_json() {
+        Ok(project_json) => print_project(&project_json, payload.format())?,
+        Err(e) => println!("프로젝트: 읽을 수 없음 ({})", e),
+    }
+
+    if let Some(media_id) = &options.dump {
+        let output = dump_media(&payload, media_id, options.output.as_deref())?;
+        println!("미디어 저장: {} → {}", media_id, output.display());
+    }
+
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let options = match parse_args() {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::from(2);
+        }
+    };
+
+    match inspect(&options) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("점검 실패: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,114 @@
+// 빌드 진행 상황 이벤트와 취소
+//
+// 모든 빌드 명령은 `build-progress` 이벤트로 BuildProgress를 보낸다.
+// 취소는 앱 state(BuildState)의 플래그로 전달되며, 빌드 코드는 긴 작업 사이사이에
+// check_cancelled로 확인하고 중단한다.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use serde::{Deserialize, Serialize};
+use tauri::{Emitter, Manager};
+
+pub const BUILD_PROGRESS_EVENT: &str = "build-progress";
+
+// 취소 시 빌드 명령이 반환하는 에러 메시지
+pub const BUILD_CANCELLED: &str = "빌드가 취소되었습니다.";
+
+// 빌드 단계
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BuildStage {
+    Preparing,
+    CopyingMedia,
+    ConvertingIcon,
+    BuildingTemplate,
+    CopyingTemplate,
+    ApplyingIcon,
+    StrippingPayload,
+    EmbeddingMedia,
+    EmbeddingProject,
+    // 임시 출력을 동기화하고 최종 경로로 교체
+    Finalizing,
+    Completed,
+    Cancelled,
+}
+
+// `build-progress` 이벤트 페이로드
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BuildProgress {
+    pub stage: BuildStage,
+    pub message: String,
+    // 데이터 쓰기 단계에서만 설정
+    pub bytes_written: Option<u64>,
+    pub bytes_total: Option<u64>,
+    // 현재 처리 중인 미디어 ID
+    pub media_id: Option<String>,
+}
+
+impl BuildProgress {
+    pub fn new(stage: BuildStage, message: impl Into<String>) -> Self {
+        BuildProgress {
+            stage,
+            message: message.into(),
+            bytes_written: None,
+            bytes_total: None,
+            media_id: None,
+        }
+    }
+
+    pub fn with_bytes(mut self, written: u64, total: u64) -> Self {
+        self.bytes_written = Some(written);
+        self.bytes_total = Some(total);
+        self
+    }
+
+    pub fn with_media(mut self, media_id: &str) -> Self {
+        self.media_id = Some(media_id.to_string());
+        self
+    }
+}
+
+// 진행 중인 빌드의 취소 상태 (한 번에 하나의 빌드만 실행)
+#[derive(Default)]
+pub struct BuildState {
+    cancelled: AtomicBool,
+}
+
+// 진행 상황 이벤트 전송
+pub fn report(app: &tauri::AppHandle, progress: BuildProgress) {
+    let _ = app.emit(BUILD_PROGRESS_EVENT, progress);
+}
+
+// 단계와 메시지만 있는 진행 상황 전송
+pub fn stage(app: &tauri::AppHandle, stage: BuildStage, message: impl Into<String>) {
+    report(app, BuildProgress::new(stage, message));
+}
+
+// 새 빌드 시작 시 이전 취소 요청 초기화
+pub fn begin(app: &tauri::AppHandle) {
+    if let Some(state) = app.try_state::<BuildState>() {
+        state.cancelled.store(false, Ordering::SeqCst);
+    }
+}
+
+// 현재 빌드 취소 요청
+pub fn cancel(app: &tauri::AppHandle) {
+    if let Some(state) = app.try_state::<BuildState>() {
+        state.cancelled.store(true, Ordering::SeqCst);
+    }
+}
+
+pub fn is_cancelled(app: &tauri::AppHandle) -> bool {
+    app.try_state::<BuildState>()
+        .map(|state| state.cancelled.load(Ordering::SeqCst))
+        .unwrap_or(false)
+}
+
+// 취소되었으면 BUILD_CANCELLED 에러 반환
+pub fn check_cancelled(app: &tauri::AppHandle) -> Result<(), String> {
+    if is_cancelled(app) {
+        return Err(BUILD_CANCELLED.to_string());
+    }
+    Ok(())
+}
@@ -0,0 +1,410 @@
+// 헤드리스 CLI 빌드
+//
+// `tutorial-maker build <input.tutorial> --output <product.exe> [--icon <icon.png>] [--target <target>]
+//      [--encrypt] [--key-file <key> | --generate-key]
+//      [--license-key <key> --issuer <name> [--expires <YYYY-MM-DD>] [--machine-id <id>...]]
+//      [--sign-key <key>] [--strict]`
+// 창을 띄우지 않고 .tutorial 파일에서 제품 exe를 만든다. (CI 야간 빌드용)
+// target: windows, linux, appimage, macos (생략 시 호스트 OS)
+// --generate-key: 키 파일/컴파일된 키가 없을 때 새 키를 만들어 제품 옆 tutorial.license(평문)로 저장
+// --strict: 프로젝트 검사(깨진 페이지 이동, 없는 미디어 등)에서 문제가 있거나 읽을 수 없는
+//           미디어 파일이 있으면 실패 (생략 시 경고만 출력)
+//
+// `tutorial-maker extract <product.exe> --output <restored.tutorial> [--key-file <key>] [--license-key <key>]`
+// 배포된 제품 exe에서 편집 가능한 .tutorial 파일을 복원한다.
+// 암호화된 제품은 복호화 키 파일, 라이선스가 들어간 제품은 발급자 서명 키 파일이 필요하다.
+//
+// 제품 exe에서는 빌더 전용 서브커맨드(build/extract/verify)를 처리하지 않는다.
+//
+// `tutorial-maker machine-id`
+// 기기 제한 라이선스에 넣을 이 컴퓨터의 기기 ID를 출력한다.
+//
+// `tutorial-maker verify <product.exe> [--public-key <hex>]`
+// 제품의 내장 데이터 서명과 모든 엔트리 해시를 확인한다. (공개 키 생략 시 컴파일된 키)
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use tauri::Listener;
+use tauri_plugin_cli::CliExt;
+
+use crate::build_progress::{BuildProgress, BUILD_PROGRESS_EVENT};
+use crate::license::{self, LicenseOptions};
+use crate::payload::Payload;
+use crate::payload_access::ExtractKeys;
+use crate::payload_signature;
+use crate::tutorial_extract::extract_exe_to_tutorial;
+use crate::product_target::{self, ProductTarget};
+use crate::{build_product_v2, MediaBuildInfo, ProductBuildOptions};
+
+// 확장자로 MIME 타입 추정 (프론트엔드 projectBuilder의 getExtension과 대응)
+fn mime_type_from_extension(path: &Path) -> String {
+    let ext = path.extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_ascii_lowercase())
+        .unwrap_or_default();
+
+    match ext.as_str() {
+        "mp4" => "video/mp4",
+        "webm" => "video/webm",
+        "avi" => "video/avi",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        _ => "application/octet-stream",
+    }
+    .to_string()
+}
+
+// .tutorial(zip) 파일에서 빌드에 필요한 데이터
+struct ExtractedTutorial {
+    project_json: String,
+    media_files: Vec<MediaBuildInfo>,
+    app_icon_path: Option<PathBuf>,
+}
+
+// .tutorial 파일을 임시 폴더에 풀고 빌드 정보 구성
+fn extract_tutorial(input: &Path, temp_dir: &Path) -> Result<ExtractedTutorial, String> {
+    let file = fs::File::open(input)
+        .map_err(|e| format!("튜토리얼 파일 열기 실패: {}", e))?;
+    let mut archive = zip::ZipArchive::new(file)
+        .map_err(|e| format!("튜토리얼 파일 압축 해제 실패: {}", e))?;
+
+    let mut project_json: Option<String> = None;
+    let mut media_files = Vec::new();
+    let mut app_icon_path: Option<PathBuf> = None;
+
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i)
+            .map_err(|e| format!("튜토리얼 파일 항목 읽기 실패: {}", e))?;
+        if entry.is_dir() {
+            continue;
+        }
+
+        // 압축 파일 밖으로 벗어나는 경로는 무시
+        let Some(relative_path) = entry.enclosed_name() else {
+            continue;
+        };
+
+        let dest = temp_dir.join(&relative_path);
+        if let Some(parent) = dest.parent() {
+            fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        let mut out = fs::File::create(&dest)
+            .map_err(|e| format!("임시 파일 생성 실패: {}", e))?;
+        io::copy(&mut entry, &mut out)
+            .map_err(|e| format!("튜토리얼 파일 압축 해제 실패: {}", e))?;
+
+        let folder = relative_path.parent()
+            .and_then(|p| p.to_str())
+            .unwrap_or_default();
+        let file_name = relative_path.file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let id = relative_path.file_stem()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        match folder {
+            "" if file_name == "project.json" => {
+                project_json = Some(fs::read_to_string(&dest)
+                    .map_err(|e| format!("project.json 읽기 실패: {}", e))?);
+            }
+            "media" | "buttons" => {
+                media_files.push(MediaBuildInfo {
+                    id,
+                    name: file_name,
+                    mime_type: mime_type_from_extension(&dest),
+                    file_path: dest.to_string_lossy().to_string(),
+                });
+            }
+            // 첫 번째 아이콘만 사용 (tutorialLoader와 동일)
+            "icons" if app_icon_path.is_none() => {
+                app_icon_path = Some(dest);
+            }
+            _ => {}
+        }
+    }
+
+    let project_json = project_json
+        .ok_or_else(|| "튜토리얼 파일에 project.json이 없습니다.".to_string())?;
+
+    // 프론트엔드 빌드와 동일하게 appIcon 필드는 제외하고 내장
+    let mut project: serde_json::Value = serde_json::from_str(&project_json)
+        .map_err(|e| format!("project.json 파싱 실패: {}", e))?;
+    if let Some(obj) = project.as_object_mut() {
+        obj.remove("appIcon");
+    }
+    let project_json = serde_json::to_string(&project)
+        .map_err(|e| format!("프로젝트 직렬화 실패: {}", e))?;
+
+    Ok(ExtractedTutorial { project_json, media_files, app_icon_path })
+}
+
+// .tutorial 파일로부터 제품 exe 빌드
+fn build_from_tutorial_file(
+    app: &tauri::AppHandle,
+    input: &Path,
+    output: &Path,
+    options: ProductBuildOptions,
+) -> Result<(), String> {
+    let temp_dir = std::env::temp_dir().join(format!("tutorial_cli_build_{}", std::process::id()));
+    fs::create_dir_all(&temp_dir).map_err(|e| e.to_string())?;
+
+    let result = extract_tutorial(input, &temp_dir).and_then(|tutorial| {
+        // --icon 인자가 있으면 .tutorial 안의 아이콘보다 우선
+        let app_icon_path = options.app_icon_path.map(Path::to_path_buf).or(tutorial.app_icon_path);
+        let options = ProductBuildOptions { app_icon_path: app_icon_path.as_deref(), ..options };
+        // 읽을 수 없는 미디어는 진행 상황(경고)으로 이미 출력됨
+        build_product_v2(app, &tutorial.project_json, &tutorial.media_files, output, &options).map(|_| ())
+    });
+
+    let _ = fs::remove_dir_all(&temp_dir);
+    result
+}
+
+// CLI 인자 값을 문자열로 가져오기
+fn arg_value(matches: &tauri_plugin_cli::Matches, name: &str) -> Option<String> {
+    matches.args.get(name)
+        .and_then(|arg| arg.value.as_str())
+        .map(|v| v.to_string())
+}
+
+// 여러 번 줄 수 있는 인자 값 목록
+fn arg_values(matches: &tauri_plugin_cli::Matches, name: &str) -> Vec<String> {
+    let Some(arg) = matches.args.get(name) else {
+        return Vec::new();
+    };
+    match &arg.value {
+        serde_json::Value::Array(values) => values.iter()
+            .filter_map(|v| v.as_str())
+            .map(str::to_string)
+            .collect(),
+        serde_json::Value::String(value) => vec![value.clone()],
+        _ => Vec::new(),
+    }
+}
+
+// 라이선스 인자 해석 (--license-key가 없으면 라이선스 없이 빌드)
+fn license_options(matches: &tauri_plugin_cli::Matches) -> Result<Option<LicenseOptions>, String> {
+    let Some(signing_key_file) = arg_value(matches, "license-key") else {
+        return Ok(None);
+    };
+    let issuer = arg_value(matches, "issuer")
+        .ok_or_else(|| "라이선스 발급자(--issuer)가 필요합니다.".to_string())?;
+    let expires_at = arg_value(matches, "expires")
+        .map(|value| license::parse_expiry(&value))
+        .transpose()?;
+
+    Ok(Some(LicenseOptions {
+        issuer,
+        expires_at,
+        allowed_machine_ids: arg_values(matches, "machine-id"),
+        signing_key_file,
+    }))
+}
+
+// 값 없는 플래그 인자가 주어졌는지
+fn flag_present(matches: &tauri_plugin_cli::Matches, name: &str) -> bool {
+    matches.args.get(name)
+        .and_then(|arg| arg.value.as_bool())
+        .unwrap_or(false)
+}
+
+// 제품(내장 데이터가 있는 실행 파일)에서도 처리하는 서브커맨드
+// build/extract/verify는 빌더 전용이라 제품에서는 무시하고 평소처럼 튜토리얼을 실행
+const PRODUCT_SUBCOMMANDS: &[&str] = &["machine-id"];
+
+// 이 실행 파일에서 처리하는 서브커맨드인지
+fn is_available(name: &str) -> bool {
+    PRODUCT_SUBCOMMANDS.contains(&name) || Payload::current().is_err()
+}
+
+// 주어진 서브커맨드로 실행되었으면 그 인자 (처리하지 않는 서브커맨드면 None)
+fn subcommand_matches(app: &tauri::App, name: &str) -> Option<tauri_plugin_cli::Matches> {
+    let matches = app.cli().matches().ok()?;
+    matches.subcommand
+        .filter(|s| s.name == name && is_available(name))
+        .map(|s| s.matches)
+}
+
+// 서브커맨드로 실행되었는지 (그러면 메인 창을 만들지 않음)
+pub fn has_subcommand(app: &tauri::App) -> bool {
+    app.cli().matches()
+        .is_ok_and(|matches| matches.subcommand.is_some_and(|s| is_available(&s.name)))
+}
+
+// `build` 서브커맨드가 주어졌으면 창 없이 빌드를 실행하고 종료
+pub fn handle_build_subcommand(app: &tauri::App) {
+    let Some(matches) = subcommand_matches(app, "build") else {
+        return;
+    };
+
+    let Some(input) = arg_value(&matches, "input") else {
+        eprintln!("입력 .tutorial 파일이 필요합니다.");
+        app.handle().exit(2);
+        return;
+    };
+    let Some(output) = arg_value(&matches, "output") else {
+        eprintln!("출력 경로(--output)가 필요합니다.");
+        app.handle().exit(2);
+        return;
+    };
+    let icon = arg_value(&matches, "icon");
+    let target = match arg_value(&matches, "target") {
+        Some(value) => match ProductTarget::parse(&value) {
+            Ok(target) => target,
+            Err(e) => {
+                eprintln!("{}", e);
+                app.handle().exit(2);
+                return;
+            }
+        },
+        None => ProductTarget::host(),
+    };
+    let key_file = arg_value(&matches, "key-file");
+    let encrypt = flag_present(&matches, "encrypt");
+    let generate_key = flag_present(&matches, "generate-key");
+    let sign_key = arg_value(&matches, "sign-key");
+    let strict = flag_present(&matches, "strict");
+    let license = match license_options(&matches) {
+        Ok(license) => license,
+        Err(e) => {
+            eprintln!("{}", e);
+            app.handle().exit(2);
+            return;
+        }
+    };
+
+    // 진행 상황은 표준 출력으로 전달 (바이트 단위 진행률 이벤트는 생략)
+    app.listen_any(BUILD_PROGRESS_EVENT, |event| {
+        match serde_json::from_str::<BuildProgress>(event.payload()) {
+            Ok(progress) if progress.bytes_written.is_some() => {}
+            Ok(progress) => println!("{}", progress.message),
+            Err(_) => println!("{}", event.payload()),
+        }
+    });
+
+    let handle = app.handle().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let options = ProductBuildOptions {
+            app_icon_path: icon.as_deref().map(Path::new),
+            verify_on_startup: false,
+            target,
+            encrypt: encrypt || key_file.is_some() || generate_key,
+            encryption_key_file: key_file.as_deref().map(Path::new),
+            generate_encryption_key: generate_key,
+            license: license.as_ref(),
+            signing_key_file: sign_key.as_deref().map(Path::new),
+            strict,
+        };
+        let result = build_from_tutorial_file(&handle, Path::new(&input), Path::new(&output), options);
+
+        match result {
+            Ok(()) => {
+                println!("빌드 완료: {}", output);
+                handle.exit(0);
+            }
+            Err(e) => {
+                eprintln!("빌드 실패: {}", e);
+                handle.exit(1);
+            }
+        }
+    });
+}
+
+// `extract` 서브커맨드가 주어졌으면 창 없이 .tutorial 파일을 복원하고 종료
+pub fn handle_extract_subcommand(app: &tauri::App) {
+    let Some(matches) = subcommand_matches(app, "extract") else {
+        return;
+    };
+
+    let Some(input) = arg_value(&matches, "input") else {
+        eprintln!("입력 exe 파일이 필요합니다.");
+        app.handle().exit(2);
+        return;
+    };
+    let Some(output) = arg_value(&matches, "output") else {
+        eprintln!("출력 경로(--output)가 필요합니다.");
+        app.handle().exit(2);
+        return;
+    };
+
+    let key_file = arg_value(&matches, "key-file");
+    let license_key = arg_value(&matches, "license-key");
+
+    let handle = app.handle().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let keys = ExtractKeys {
+            key_file: key_file.as_deref().map(Path::new),
+            license_key_file: license_key.as_deref().map(Path::new),
+        };
+        match extract_exe_to_tutorial(&product_target::payload_path(Path::new(&input)), Path::new(&output), keys) {
+            Ok(summary) => {
+                println!(
+                    "복원 완료: {} (미디어 {}개, 버튼 이미지 {}개)",
+                    output, summary.media_count, summary.button_count
+                );
+                handle.exit(0);
+            }
+            Err(e) => {
+                eprintln!("복원 실패: {}", e);
+                handle.exit(1);
+            }
+        }
+    });
+}
+
+// `machine-id` 서브커맨드가 주어졌으면 기기 ID를 출력하고 종료
+pub fn handle_machine_id_subcommand(app: &tauri::App) {
+    if subcommand_matches(app, "machine-id").is_none() {
+        return;
+    }
+
+    match license::machine_id() {
+        Ok(id) => {
+            println!("{}", id);
+            app.handle().exit(0);
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            app.handle().exit(1);
+        }
+    }
+}
+
+// `verify` 서브커맨드가 주어졌으면 제품 서명을 확인하고 종료 (확인 실패 시 종료 코드 1)
+pub fn handle_verify_subcommand(app: &tauri::App) {
+    let Some(matches) = subcommand_matches(app, "verify") else {
+        return;
+    };
+
+    let Some(input) = arg_value(&matches, "input") else {
+        eprintln!("확인할 제품 exe 파일이 필요합니다.");
+        app.handle().exit(2);
+        return;
+    };
+    let public_key = arg_value(&matches, "public-key");
+
+    let handle = app.handle().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let result = Payload::open(&product_target::payload_path(Path::new(&input)))
+            .and_then(|payload| payload_signature::verify_payload(&payload, public_key.as_deref()));
+
+        match result {
+            Ok(report) => {
+                println!(
+                    "서명 확인 완료: {} (엔트리 {}개, 서명 키 {})",
+                    input, report.integrity.verified_count, report.public_key
+                );
+                handle.exit(0);
+            }
+            Err(e) => {
+                eprintln!("서명 확인 실패: {}", e);
+                handle.exit(1);
+            }
+        }
+    });
+}
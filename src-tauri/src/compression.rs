@@ -0,0 +1,188 @@
+// 내장 미디어 압축
+//
+// 이미지처럼 압축 여지가 있는 미디어만 압축하고, 이미 압축된 비디오/오디오 등은
+// 원본 그대로 저장한다. 압축 결과가 원본보다 크면 압축하지 않는다.
+
+use std::io::{Read, Write};
+
+use serde::{Deserialize, Serialize};
+
+// zstd 압축 레벨 (빌드는 한 번, 재생은 여러 번이므로 높은 편으로 설정)
+const ZSTD_LEVEL: i32 = 9;
+
+// 압축 해제 후 최대 크기 (매니페스트의 크기를 믿지 않고 이보다 크면 풀지 않음)
+// 빌드도 이보다 큰 미디어는 압축하지 않으므로, 압축된 엔트리는 항상 압축 해제 캐시(payload.rs)에 들어감
+pub const MAX_DECOMPRESSED_SIZE: u64 = 32 * 1024 * 1024;
+
+// 미디어 엔트리 압축 방식
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MediaCompression {
+    Zstd,
+    Deflate,
+}
+
+// MIME 타입과 크기로 압축 방식 선택 (None이면 원본 저장)
+pub fn choose_compression(mime_type: &str, size: u64) -> Option<MediaCompression> {
+    if size > MAX_DECOMPRESSED_SIZE {
+        return None;
+    }
+    let mime_type = mime_type.to_ascii_lowercase();
+
+    // 이미 압축된 포맷은 다시 압축해도 효과가 없음
+    if mime_type.starts_with("video/") || mime_type.starts_with("audio/") {
+        return None;
+    }
+    if matches!(mime_type.as_str(), "image/jpeg" | "image/webp" | "image/gif" | "image/avif") {
+        return None;
+    }
+
+    // 텍스트 계열은 deflate로 충분
+    if mime_type.starts_with("text/") || mime_type == "image/svg+xml" || mime_type == "application/json" {
+        return Some(MediaCompression::Deflate);
+    }
+
+    // PNG 스크린샷, BMP 등 나머지 이미지는 zstd
+    Some(MediaCompression::Zstd)
+}
+
+// 미디어를 나누어 받으면서 압축 (대용량 파일을 메모리에 올리지 않음)
+pub enum StreamCompressor {
+    Zstd(zstd::stream::write::Encoder<'static, Vec<u8>>),
+    Deflate(flate2::write::DeflateEncoder<Vec<u8>>),
+}
+
+impl StreamCompressor {
+    pub fn new(compression: MediaCompression) -> Result<StreamCompressor, String> {
+        Ok(match compression {
+            MediaCompression::Zstd => StreamCompressor::Zstd(
+                zstd::stream::write::Encoder::new(Vec::new(), ZSTD_LEVEL)
+                    .map_err(|e| format!("zstd 압축 실패: {}", e))?,
+            ),
+            MediaCompression::Deflate => StreamCompressor::Deflate(
+                flate2::write::DeflateEncoder::new(Vec::new(), flate2::Compression::best()),
+            ),
+        })
+    }
+
+    // 데이터를 추가하고 지금까지 나온 압축 결과 반환
+    pub fn update(&mut self, data: &[u8]) -> Result<Vec<u8>, String> {
+        match self {
+            StreamCompressor::Zstd(encoder) => encoder.write_all(data)
+                .map(|_| std::mem::take(encoder.get_mut()))
+                .map_err(|e| format!("zstd 압축 실패: {}", e)),
+            StreamCompressor::Deflate(encoder) => encoder.write_all(data)
+                .map(|_| std::mem::take(encoder.get_mut()))
+                .map_err(|e| format!("deflate 압축 실패: {}", e)),
+        }
+    }
+
+    // 남은 압축 결과 반환
+    pub fn finish(self) -> Result<Vec<u8>, String> {
+        match self {
+            StreamCompressor::Zstd(encoder) => encoder.finish()
+                .map_err(|e| format!("zstd 압축 실패: {}", e)),
+            StreamCompressor::Deflate(encoder) => encoder.finish()
+                .map_err(|e| format!("deflate 압축 실패: {}", e)),
+        }
+    }
+}
+
+// original_size: 압축 해제 후 크기 (검증용)
+// 매니페스트 값은 신뢰할 수 없으므로 최대 크기를 넘으면 거부하고, 미리 할당하지 않으며,
+// 그보다 많이 풀리면 중단한다.
+pub fn decompress(data: &[u8], compression: MediaCompression, original_size: u64) -> Result<Vec<u8>, String> {
+    if original_size > MAX_DECOMPRESSED_SIZE {
+        return Err(format!(
+            "압축 해제 크기가 비정상적입니다 ({} 바이트, 최대 {} 바이트)",
+            original_size, MAX_DECOMPRESSED_SIZE
+        ));
+    }
+
+    let decoder: Box<dyn Read + '_> = match compression {
+        MediaCompression::Zstd => Box::new(
+            zstd::stream::read::Decoder::new(data)
+                .map_err(|e| format!("zstd 압축 해제 실패: {}", e))?,
+        ),
+        MediaCompression::Deflate => Box::new(flate2::read::DeflateDecoder::new(data)),
+    };
+
+    let mut decompressed = Vec::new();
+    decoder
+        .take(original_size.saturating_add(1))
+        .read_to_end(&mut decompressed)
+        .map_err(|e| match compression {
+            MediaCompression::Zstd => format!("zstd 압축 해제 실패: {}", e),
+            MediaCompression::Deflate => format!("deflate 압축 해제 실패: {}", e),
+        })?;
+
+    if decompressed.len() as u64 != original_size {
+        return Err(format!(
+            "압축 해제 크기가 다릅니다 (예상 {}, 실제 {})",
+            original_size,
+            decompressed.len()
+        ));
+    }
+
+    Ok(decompressed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn compress(data: &[u8], method: MediaCompression) -> Vec<u8> {
+        let mut compressor = StreamCompressor::new(method).unwrap();
+        let mut output = Vec::new();
+        for piece in data.chunks(4096) {
+            output.extend_from_slice(&compressor.update(piece).unwrap());
+        }
+        output.extend_from_slice(&compressor.finish().unwrap());
+        output
+    }
+
+    #[test]
+    fn round_trip() {
+        let data: Vec<u8> = b"tutorial maker screenshot ".repeat(1000);
+        for method in [MediaCompression::Zstd, MediaCompression::Deflate] {
+            let compressed = compress(&data, method);
+            assert!(compressed.len() < data.len());
+            assert_eq!(decompress(&compressed, method, data.len() as u64).unwrap(), data);
+        }
+    }
+
+    #[test]
+    fn rejects_wrong_size_or_corrupt_data() {
+        let data = vec![7u8; 10_000];
+        for method in [MediaCompression::Zstd, MediaCompression::Deflate] {
+            let compressed = compress(&data, method);
+            assert!(decompress(&compressed, method, 9_999).is_err());
+            assert!(decompress(&compressed, method, 10_001).is_err());
+            assert!(decompress(&compressed[..compressed.len() / 2], method, 10_000).is_err());
+        }
+    }
+
+    #[test]
+    fn rejects_oversized_original_size() {
+        let data = vec![0u8; 1000];
+        for method in [MediaCompression::Zstd, MediaCompression::Deflate] {
+            let compressed = compress(&data, method);
+            let error = decompress(&compressed, method, MAX_DECOMPRESSED_SIZE + 1).unwrap_err();
+            assert!(error.contains("비정상"), "{}", error);
+            assert!(decompress(&compressed, method, u64::MAX).is_err());
+        }
+    }
+
+    #[test]
+    fn chooses_compression_by_mime_type() {
+        assert_eq!(choose_compression("video/mp4", 100), None);
+        assert_eq!(choose_compression("audio/mpeg", 100), None);
+        assert_eq!(choose_compression("image/JPEG", 100), None);
+        assert_eq!(choose_compression("image/svg+xml", 100), Some(MediaCompression::Deflate));
+        assert_eq!(choose_compression("text/plain", 100), Some(MediaCompression::Deflate));
+        assert_eq!(choose_compression("image/png", MAX_DECOMPRESSED_SIZE), Some(MediaCompression::Zstd));
+
+        // 압축 해제 최대 크기보다 큰 미디어는 원본 저장
+        assert_eq!(choose_compression("image/png", MAX_DECOMPRESSED_SIZE + 1), None);
+    }
+}
@@ -0,0 +1,345 @@
+// 키오스크 종료 보호 (종료 키 + 관리자 PIN)
+//
+// 제품은 시작 시 내장된 프로젝트 설정의 종료 키, 관리자 PIN 해시, 창 닫기 차단 여부를
+// 앱 state(ExitGuard)에 등록한다. 메인 창에는 눌린 키를 `exit_key_pressed`로 보내는
+// 초기화 스크립트를 넣고, 등록된 종료 키인지는 Rust가 판단한다. 종료 키이거나
+// 창 닫기 차단 중 Alt+F4/닫기 버튼이 눌리면 `exit-requested` 이벤트로 프론트엔드에
+// 종료 확인(PIN 입력)을 요청하고, 프론트엔드가 `request_exit`로 보낸 PIN이 맞을 때만 종료한다.
+// 프로젝트 설정을 읽지 못하면 작성자가 보호를 켰는지 알 수 없으므로 일반 창처럼 닫을 수 있다.
+// PIN은 무작위 salt와 함께 반복 해시한 값만 프로젝트에 저장한다.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use aes_gcm::aead::rand_core::RngCore;
+use aes_gcm::aead::OsRng;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use tauri::{Emitter, Manager};
+
+use crate::payload_crypto::{from_hex, to_hex};
+use crate::project_model::ProjectSettings;
+
+// 종료 키가 눌렸거나 닫기 요청을 막았을 때 프론트엔드로 보내는 이벤트
+pub const EXIT_REQUEST_EVENT: &str = "exit-requested";
+
+// 제품 메인 창에 넣는 키 입력 전달 스크립트 (종료 키 판단은 exit_key_pressed에서)
+pub const KEY_LISTENER_SCRIPT: &str = r#"
+window.addEventListener('keydown', (e) => {
+  if (e.repeat || !window.__TAURI_INTERNALS__) return;
+  window.__TAURI_INTERNALS__.invoke('exit_key_pressed', { key: e.key }).catch(() => {});
+}, true);
+"#;
+
+const PIN_SALT_SIZE: usize = 16;
+// 추출한 프로젝트에서 해시로 PIN을 찾는 데 드는 시간을 늘리기 위한 반복 횟수
+const PIN_HASH_ROUNDS: u32 = 100_000;
+const PIN_MIN_LENGTH: usize = 4;
+const PIN_MAX_LENGTH: usize = 12;
+
+// 연속으로 틀리면 잠시 입력을 막음
+const MAX_PIN_ATTEMPTS: u32 = 5;
+const PIN_LOCKOUT: Duration = Duration::from_secs(30);
+
+// 프론트엔드에 전달하는 종료 설정
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExitSettings {
+    // KeyboardEvent.key 값 ("Escape", "F11" 등)
+    pub exit_key: Option<String>,
+    pub pin_required: bool,
+    pub block_close: bool,
+}
+
+// PIN 입력 실패 기록
+#[derive(Default)]
+struct PinAttempts {
+    failures: u32,
+    locked_until: Option<Instant>,
+}
+
+// 제품 종료 보호 state (빌더로 실행되면 아무것도 막지 않음)
+#[derive(Default)]
+pub struct ExitGuard {
+    settings: Option<ExitSettings>,
+    pin_hash: Option<String>,
+    // PIN 확인 후 종료 중이면 닫기 요청을 막지 않음
+    exiting: AtomicBool,
+    attempts: Mutex<PinAttempts>,
+}
+
+impl ExitGuard {
+    // 내장된 프로젝트 설정으로 생성
+    // project: None이면 빌더(아무것도 막지 않음), Err면 설정을 읽지 못한 제품
+    // (읽은 설정이 요청한 경우에만 막으므로, 읽지 못하면 종료 키/PIN 없이 닫을 수 있음)
+    pub fn new(project: Option<Result<&ProjectSettings, &String>>) -> ExitGuard {
+        let settings = match project {
+            None => return ExitGuard::default(),
+            Some(Ok(settings)) => settings,
+            Some(Err(e)) => {
+                log::warn!("종료 설정을 읽지 못해 기본 종료 동작을 사용합니다: {}", e);
+                return ExitGuard {
+                    settings: Some(ExitSettings { exit_key: None, pin_required: false, block_close: false }),
+                    ..ExitGuard::default()
+                };
+            }
+        };
+
+        let pin_hash = settings.exit_pin_hash.clone().filter(|hash| !hash.is_empty());
+        ExitGuard {
+            settings: Some(ExitSettings {
+                exit_key: settings.exit_key.as_deref().and_then(normalize_key),
+                pin_required: pin_hash.is_some(),
+                block_close: settings.block_close,
+            }),
+            pin_hash,
+            ..ExitGuard::default()
+        }
+    }
+
+    // 창 닫기 요청(Alt+F4, 닫기 버튼)을 막아야 하는지
+    fn blocks_close(&self) -> bool {
+        self.settings.as_ref().is_some_and(|s| s.block_close) && !self.exiting.load(Ordering::SeqCst)
+    }
+
+    // PIN 확인 (PIN이 설정되지 않았으면 항상 통과)
+    fn authorize(&self, pin: Option<&str>) -> Result<(), String> {
+        let Some(stored) = &self.pin_hash else {
+            return Ok(());
+        };
+
+        let mut attempts = self.attempts.lock()
+            .map_err(|_| "PIN 확인 상태 잠금 실패".to_string())?;
+        if let Some(until) = attempts.locked_until {
+            let now = Instant::now();
+            if now < until {
+                return Err(format!(
+                    "PIN을 {}번 잘못 입력했습니다. {}초 후 다시 시도해주세요.",
+                    MAX_PIN_ATTEMPTS,
+                    (until - now).as_secs() + 1
+                ));
+            }
+            *attempts = PinAttempts::default();
+        }
+
+        if pin.is_some_and(|pin| verify_pin(stored, pin)) {
+            *attempts = PinAttempts::default();
+            return Ok(());
+        }
+
+        attempts.failures += 1;
+        if attempts.failures >= MAX_PIN_ATTEMPTS {
+            attempts.locked_until = Some(Instant::now() + PIN_LOCKOUT);
+        }
+        Err("PIN이 올바르지 않습니다.".to_string())
+    }
+}
+
+// 이전 버전 프로젝트의 키 이름("ESC" 등)을 KeyboardEvent.key 값으로 변환
+fn normalize_key(key: &str) -> Option<String> {
+    let key = key.trim();
+    match key.to_ascii_lowercase().as_str() {
+        "" => None,
+        "esc" | "escape" => Some("Escape".to_string()),
+        _ => Some(key.to_string()),
+    }
+}
+
+// 빌더에서 프로젝트에 저장할 PIN 해시 생성 ("<salt hex>$<hash hex>")
+pub fn hash_pin(pin: &str) -> Result<String, String> {
+    let length = pin.chars().count();
+    if !(PIN_MIN_LENGTH..=PIN_MAX_LENGTH).contains(&length) || !pin.chars().all(|c| c.is_ascii_digit()) {
+        return Err(format!("관리자 PIN은 {}~{}자리 숫자여야 합니다.", PIN_MIN_LENGTH, PIN_MAX_LENGTH));
+    }
+
+    let mut salt = [0u8; PIN_SALT_SIZE];
+    OsRng.fill_bytes(&mut salt);
+    Ok(format!("{}${}", to_hex(&salt), to_hex(&derive(&salt, pin))))
+}
+
+fn verify_pin(stored: &str, pin: &str) -> bool {
+    let Some((salt, hash)) = stored.split_once('$') else {
+        return false;
+    };
+    let (Some(salt), Some(expected)) = (from_hex(salt), from_hex(hash)) else {
+        return false;
+    };
+
+    // 비교 시간으로 일치 길이가 드러나지 않도록 모든 바이트 비교
+    let actual = derive(&salt, pin);
+    actual.len() == expected.len()
+        && actual.iter().zip(&expected).fold(0u8, |diff, (a, b)| diff | (a ^ b)) == 0
+}
+
+fn derive(salt: &[u8], pin: &str) -> [u8; 32] {
+    let mut digest: [u8; 32] = Sha256::new()
+        .chain_update(salt)
+        .chain_update(pin.as_bytes())
+        .finalize()
+        .into();
+    for _ in 1..PIN_HASH_ROUNDS {
+        digest = Sha256::new()
+            .chain_update(salt)
+            .chain_update(digest)
+            .finalize()
+            .into();
+    }
+    digest
+}
+
+// 제품의 종료 설정 (빌더면 None)
+pub fn settings(app: &tauri::AppHandle) -> Option<ExitSettings> {
+    app.try_state::<ExitGuard>()
+        .and_then(|guard| guard.settings.clone())
+}
+
+// 메인 창에 키 입력 전달 스크립트가 필요한지 (종료 키가 등록된 제품)
+pub fn listens_for_keys(app: &tauri::App) -> bool {
+    app.try_state::<ExitGuard>()
+        .is_some_and(|guard| guard.settings.as_ref().is_some_and(|s| s.exit_key.is_some()))
+}
+
+// 눌린 키가 등록된 종료 키면 프론트엔드에 종료 확인 요청
+pub fn key_pressed(app: &tauri::AppHandle, key: &str) -> Result<(), String> {
+    let is_exit_key = app.try_state::<ExitGuard>()
+        .and_then(|guard| guard.settings.as_ref().and_then(|s| s.exit_key.clone()))
+        .is_some_and(|exit_key| exit_key == key);
+    if is_exit_key {
+        app.emit(EXIT_REQUEST_EVENT, ())
+            .map_err(|e| format!("종료 요청 전달 실패: {}", e))?;
+    }
+    Ok(())
+}
+
+// PIN을 확인하고 앱 종료
+pub fn request_exit(app: &tauri::AppHandle, pin: Option<&str>) -> Result<(), String> {
+    if let Some(guard) = app.try_state::<ExitGuard>() {
+        guard.authorize(pin)?;
+        guard.exiting.store(true, Ordering::SeqCst);
+    }
+    app.exit(0);
+    Ok(())
+}
+
+// 창 닫기 요청 처리: 차단 중이면 막고 프론트엔드에 PIN 입력 요청
+pub fn handle_window_event(window: &tauri::Window, event: &tauri::WindowEvent) {
+    let tauri::WindowEvent::CloseRequested { api, .. } = event else {
+        return;
+    };
+
+    let blocked = window.try_state::<ExitGuard>()
+        .is_some_and(|guard| guard.blocks_close());
+    if blocked {
+        api.prevent_close();
+        let _ = window.emit(EXIT_REQUEST_EVENT, ());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn product_settings(pin_hash: Option<String>, block_close: bool) -> ProjectSettings {
+        ProjectSettings {
+            exit_key: Some("ESC".to_string()),
+            exit_pin_hash: pin_hash,
+            block_close,
+            ..ProjectSettings::default()
+        }
+    }
+
+    #[test]
+    fn hashes_and_verifies_pin() {
+        let hash = hash_pin("1234").unwrap();
+        assert!(verify_pin(&hash, "1234"));
+        assert!(!verify_pin(&hash, "1235"));
+        assert!(!verify_pin(&hash, ""));
+
+        // 같은 PIN이어도 salt가 달라 해시가 다름
+        assert_ne!(hash, hash_pin("1234").unwrap());
+
+        assert!(!verify_pin("no-separator", "1234"));
+        assert!(!verify_pin("zz$zz", "1234"));
+        assert!(!verify_pin(&hash.replace('$', "$00"), "1234"));
+    }
+
+    #[test]
+    fn rejects_invalid_pins() {
+        for pin in ["", "123", "1234567890123", "12a4", "١٢٣٤"] {
+            assert!(hash_pin(pin).is_err(), "{}", pin);
+        }
+        assert!(hash_pin("123456789012").is_ok());
+    }
+
+    #[test]
+    fn builder_blocks_nothing() {
+        let guard = ExitGuard::new(None);
+        assert!(guard.settings.is_none());
+        assert!(!guard.blocks_close());
+        assert!(guard.authorize(None).is_ok());
+    }
+
+    #[test]
+    fn registers_project_settings() {
+        let settings = product_settings(Some(hash_pin("2468").unwrap()), true);
+        let guard = ExitGuard::new(Some(Ok(&settings)));
+        let registered = guard.settings.as_ref().unwrap();
+        assert_eq!(registered.exit_key.as_deref(), Some("Escape"));
+        assert!(registered.pin_required);
+        assert!(guard.blocks_close());
+
+        assert!(guard.authorize(None).is_err());
+        assert!(guard.authorize(Some("0000")).is_err());
+        assert!(guard.authorize(Some("2468")).is_ok());
+
+        // PIN 확인 후 종료 중이면 닫기를 막지 않음
+        guard.exiting.store(true, Ordering::SeqCst);
+        assert!(!guard.blocks_close());
+    }
+
+    #[test]
+    fn settings_without_pin_allow_exit() {
+        let settings = product_settings(Some(String::new()), false);
+        let guard = ExitGuard::new(Some(Ok(&settings)));
+        assert!(!guard.settings.as_ref().unwrap().pin_required);
+        assert!(!guard.blocks_close());
+        assert!(guard.authorize(None).is_ok());
+    }
+
+    #[test]
+    fn unreadable_settings_fall_back_to_normal_close() {
+        let error = "라이선스 파일이 없습니다.".to_string();
+        let guard = ExitGuard::new(Some(Err(&error)));
+        let registered = guard.settings.as_ref().unwrap();
+        assert_eq!(registered.exit_key, None);
+        assert!(!registered.pin_required);
+        assert!(!guard.blocks_close());
+        assert!(guard.authorize(None).is_ok());
+    }
+
+    #[test]
+    fn locks_out_after_repeated_failures() {
+        let settings = product_settings(Some(hash_pin("1357").unwrap()), true);
+        let guard = ExitGuard::new(Some(Ok(&settings)));
+
+        for _ in 0..MAX_PIN_ATTEMPTS {
+            assert_eq!(guard.authorize(Some("0000")).unwrap_err(), "PIN이 올바르지 않습니다.");
+        }
+        // 잠긴 동안에는 맞는 PIN도 거부
+        assert!(guard.authorize(Some("1357")).unwrap_err().contains("후 다시 시도"));
+
+        // 잠금 시간이 지나면 다시 입력 가능
+        guard.attempts.lock().unwrap().locked_until = Some(Instant::now());
+        assert!(guard.authorize(Some("1357")).is_ok());
+        assert_eq!(guard.attempts.lock().unwrap().failures, 0);
+    }
+
+    #[test]
+    fn normalizes_legacy_key_names() {
+        assert_eq!(normalize_key(" esc ").as_deref(), Some("Escape"));
+        assert_eq!(normalize_key("Escape").as_deref(), Some("Escape"));
+        assert_eq!(normalize_key("F11").as_deref(), Some("F11"));
+        assert_eq!(normalize_key("  "), None);
+    }
+}
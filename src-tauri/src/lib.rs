@@ -1,38 +1,59 @@
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::fs;
 use std::env;
-use std::io::{BufWriter, Read, Write, Seek, SeekFrom};
-use tauri::{Emitter, Manager};
+use std::io::{self, Read, Write, Seek, SeekFrom};
+use tauri::Manager;
 use ico::{IconDir, IconDirEntry, IconImage, ResourceType};
 
-#[allow(unused_imports)]
-use image::GenericImageView;
-
-// 매직 바이트: exe 끝에 데이터가 있는지 확인하는 마커
-const MAGIC_BYTES: &[u8] = b"TUTORIALMAKER_DATA_V1";
-// 새 버전 매직 바이트 (바이너리 미디어 포함)
-const MAGIC_BYTES_V2: &[u8] = b"TUTORIALMAKER_DATA_V2";
+mod atomic_output;
+mod build_progress;
+mod cli_build;
+mod compression;
+mod exit_guard;
+mod license;
+mod media_check;
+mod payload;
+mod payload_access;
+mod payload_crypto;
+mod payload_signature;
+mod pe_resources;
+mod poster_frame;
+mod product_startup;
+mod product_target;
+mod project_model;
+mod source_build;
+mod tutorial_extract;
+
+use payload::{
+    format_corrupt_entries, sha256_hex, BuildManifest, IntegrityReport, MediaManifestEntry, Payload,
+    PayloadFormat, MAGIC_BYTES, MAGIC_BYTES_V2, PROJECT_JSON_ENTRY_ID,
+};
+
+use build_progress::{BuildProgress, BuildStage};
+use license::{LicenseIssuer, LicenseOptions};
+use media_check::MissingMedia;
+use compression::{MediaCompression, StreamCompressor};
+use payload_crypto::{PayloadEncryption, PayloadKey, StreamEncryptor};
+use payload_signature::{PayloadSigner, SignatureReport};
+use product_target::ProductTarget;
+use project_model::Project;
 
 use serde::{Deserialize, Serialize};
-
-// 미디어 매니페스트 엔트리
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct MediaManifestEntry {
-    id: String,
-    name: String,
-    mime_type: String,
-    offset: u64,
-    size: u64,
-}
-
-// 빌드 매니페스트
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct BuildManifest {
-    project_json_offset: u64,
-    project_json_size: u64,
-    media: Vec<MediaManifestEntry>,
-    app_icon_offset: Option<u64>,
-    app_icon_size: Option<u64>,
+use sha2::{Digest, Sha256};
+
+// 페이로드 빌드 결과 (압축/암호화/중복 제거 통계)
+#[derive(Debug, Clone, Default)]
+struct PayloadBuildReport {
+    original_bytes: u64,
+    stored_bytes: u64,
+    compressed_count: usize,
+    encrypted_count: usize,
+    // 내용이 같은 미디어를 다시 기록하지 않은 엔트리 수와 그만큼 줄어든 저장 크기
+    deduplicated_count: usize,
+    deduplicated_bytes: u64,
+    // 내장한 비디오 포스터 프레임 수
+    poster_count: usize,
 }
 
 // 미디어 빌드 정보 (프론트엔드에서 전달받음)
@@ -46,6 +67,86 @@ struct MediaBuildInfo {
     file_path: String,
 }
 
+// 빌드 명령 결과 (프론트엔드 projectBuilder의 BuildResult와 대응)
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct BuildResult {
+    output_file: String,
+    // 엄격 모드가 아닐 때 빼고 빌드한 미디어 (엄격 모드에서는 빌드 실패)
+    missing_media: Vec<MissingMedia>,
+}
+
+// 읽을 수 없는 미디어 알림 (엄격 모드면 목록과 함께 실패, 아니면 경고)
+fn report_missing_media(app: &tauri::AppHandle, missing: &[MissingMedia], strict: bool) -> Result<(), String> {
+    if missing.is_empty() {
+        return Ok(());
+    }
+    if strict {
+        return Err(media_check::format_missing_media(missing));
+    }
+    for media in missing {
+        build_progress::stage(app, BuildStage::Preparing, format!("경고: 미디어 누락 - {}", media_check::describe(media)));
+    }
+    Ok(())
+}
+
+// V1: 미디어 경로를 확인하고 읽을 수 있는 파일만 media_dir로 복사
+// 파일 이름(확장자 제외)을 미디어 ID로 간주
+fn copy_media_paths(
+    app: &tauri::AppHandle,
+    project_json: &str,
+    media_paths: &[String],
+    media_dir: &Path,
+    strict: bool,
+) -> Result<Vec<MissingMedia>, String> {
+    let project = Project::parse(project_json).ok();
+    let mut sources = Vec::new();
+    let mut missing = Vec::new();
+
+    for media_path in media_paths.iter().filter(|p| !p.is_empty()) {
+        let source = Path::new(media_path);
+        let id = source.file_stem().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+        let name = source.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+        match media_check::check_media(project.as_ref(), &id, &name, media_path) {
+            Some(media) => missing.push(media),
+            None => sources.push(source),
+        }
+    }
+
+    report_missing_media(app, &missing, strict)?;
+
+    for source in sources {
+        if let Some(filename) = source.file_name() {
+            let dest = media_dir.join(filename);
+            fs::copy(source, &dest).map_err(|e| e.to_string())?;
+        }
+    }
+
+    Ok(missing)
+}
+
+// V2: 내장할 미디어 확인 (읽을 수 없는 미디어는 엄격 모드면 실패, 아니면 빼고 빌드)
+// 반환값: (내장할 미디어, 읽을 수 없는 미디어)
+fn check_media_files(
+    app: &tauri::AppHandle,
+    project: Option<&Project>,
+    media_files: &[MediaBuildInfo],
+    strict: bool,
+) -> Result<(Vec<MediaBuildInfo>, Vec<MissingMedia>), String> {
+    let mut present = Vec::new();
+    let mut missing = Vec::new();
+
+    for media_info in media_files {
+        match media_check::check_media(project, &media_info.id, &media_info.name, &media_info.file_path) {
+            Some(media) => missing.push(media),
+            None => present.push(media_info.clone()),
+        }
+    }
+
+    report_missing_media(app, &missing, strict)?;
+    Ok((present, missing))
+}
+
 // exe 파일 끝에 프로젝트 데이터 추가
 fn append_data_to_exe(exe_path: &Path, data: &[u8]) -> Result<(), String> {
     let mut file = fs::OpenOptions::new()
@@ -71,52 +172,11 @@ fn append_data_to_exe(exe_path: &Path, data: &[u8]) -> Result<(), String> {
     Ok(())
 }
 
-// exe 파일 끝에서 프로젝트 데이터 읽기
-fn read_data_from_exe(exe_path: &Path) -> Result<Vec<u8>, String> {
-    let mut file = fs::File::open(exe_path)
-        .map_err(|e| format!("exe 파일 열기 실패: {}", e))?;
-
-    let file_size = file.metadata()
-        .map_err(|e| format!("파일 메타데이터 읽기 실패: {}", e))?
-        .len();
-
-    // 매직 바이트 확인
-    let magic_len = MAGIC_BYTES.len() as u64;
-    file.seek(SeekFrom::End(-(magic_len as i64)))
-        .map_err(|e| format!("파일 탐색 실패: {}", e))?;
-
-    let mut magic_buf = vec![0u8; MAGIC_BYTES.len()];
-    file.read_exact(&mut magic_buf)
-        .map_err(|e| format!("매직 바이트 읽기 실패: {}", e))?;
+// 내장 앱 아이콘 PNG의 최대 크기
+const APP_ICON_MAX_SIZE: u32 = 256;
 
-    if magic_buf != MAGIC_BYTES {
-        return Err("내장된 프로젝트 데이터를 찾을 수 없습니다.".to_string());
-    }
-
-    // 데이터 길이 읽기 (매직 바이트 앞 8바이트)
-    file.seek(SeekFrom::End(-(magic_len as i64) - 8))
-        .map_err(|e| format!("파일 탐색 실패: {}", e))?;
-
-    let mut len_buf = [0u8; 8];
-    file.read_exact(&mut len_buf)
-        .map_err(|e| format!("데이터 길이 읽기 실패: {}", e))?;
-
-    let data_len = u64::from_le_bytes(len_buf);
-
-    // 데이터 읽기
-    let data_start = file_size - magic_len - 8 - data_len;
-    file.seek(SeekFrom::Start(data_start))
-        .map_err(|e| format!("파일 탐색 실패: {}", e))?;
-
-    let mut data = vec![0u8; data_len as usize];
-    file.read_exact(&mut data)
-        .map_err(|e| format!("데이터 읽기 실패: {}", e))?;
-
-    Ok(data)
-}
-
-// 이미지를 ICO 파일로 변환
-fn create_ico_file(source_image_path: &Path, output_ico_path: &Path) -> Result<(), String> {
+// 이미지를 여러 크기의 아이콘 이미지(IconDir)로 변환
+fn create_icon_dir(source_image_path: &Path) -> Result<IconDir, String> {
     let img = image::open(source_image_path)
         .map_err(|e| format!("이미지 로드 실패: {}", e))?;
 
@@ -133,73 +193,28 @@ fn create_ico_file(source_image_path: &Path, output_ico_path: &Path) -> Result<(
             .map_err(|e| format!("ICO 엔트리 인코딩 실패: {}", e))?);
     }
 
-    let ico_file = fs::File::create(output_ico_path)
-        .map_err(|e| format!("ICO 파일 생성 실패: {}", e))?;
-    icon_dir.write(BufWriter::new(ico_file))
-        .map_err(|e| format!("ICO 파일 쓰기 실패: {}", e))?;
-
-    Ok(())
+    Ok(icon_dir)
 }
 
-// rcedit 다운로드
-fn download_rcedit(target_path: &Path) -> Result<(), String> {
-    use std::process::Command;
-
-    let url = "https://github.com/electron/rcedit/releases/download/v2.0.0/rcedit-x64.exe";
-
-    // curl 사용하여 다운로드
-    let output = Command::new("curl")
-        .args(&[
-            "-L",
-            "-o",
-            &target_path.to_string_lossy(),
-            url,
-        ])
-        .output()
-        .map_err(|e| format!("curl 실행 실패: {}", e))?;
+// 제품 창/작업 표시줄 아이콘으로 내장할 PNG (큰 이미지는 256px로 축소)
+fn create_app_icon_png(source_image_path: &Path) -> Result<Vec<u8>, String> {
+    let mut img = image::open(source_image_path)
+        .map_err(|e| format!("이미지 로드 실패: {}", e))?;
 
-    if !output.status.success() {
-        let error = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("rcedit 다운로드 실패: {}", error));
+    if img.width() > APP_ICON_MAX_SIZE || img.height() > APP_ICON_MAX_SIZE {
+        img = img.resize(APP_ICON_MAX_SIZE, APP_ICON_MAX_SIZE, image::imageops::FilterType::Lanczos3);
     }
 
-    Ok(())
+    let mut png = Vec::new();
+    img.write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+        .map_err(|e| format!("PNG 인코딩 실패: {}", e))?;
+    Ok(png)
 }
 
-// rcedit를 사용하여 exe 파일의 아이콘 변경
-fn change_exe_icon(exe_path: &Path, ico_path: &Path) -> Result<(), String> {
-    use std::process::Command;
-
-    // rcedit 경로 (LOCALAPPDATA/tauri에 저장)
-    let local_app_data = env::var("LOCALAPPDATA")
-        .map_err(|_| "LOCALAPPDATA 환경변수를 찾을 수 없습니다.")?;
-    let tauri_dir = PathBuf::from(&local_app_data).join("tauri");
-    let rcedit_path = tauri_dir.join("rcedit-x64.exe");
-
-    // rcedit가 없으면 다운로드
-    if !rcedit_path.exists() {
-        // tauri 디렉토리 생성
-        fs::create_dir_all(&tauri_dir)
-            .map_err(|e| format!("tauri 디렉토리 생성 실패: {}", e))?;
-
-        download_rcedit(&rcedit_path)?;
-    }
-
-    let output = Command::new(&rcedit_path)
-        .args(&[
-            exe_path.to_string_lossy().to_string(),
-            "--set-icon".to_string(),
-            ico_path.to_string_lossy().to_string(),
-        ])
-        .output()
-        .map_err(|e| format!("rcedit 실행 실패: {}", e))?;
-
-    if !output.status.success() {
-        let error = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("아이콘 변경 실패: {}", error));
-    }
-
-    Ok(())
+// exe 파일의 아이콘 리소스 교체 (외부 도구 없이 직접 PE 리소스 편집)
+fn change_exe_icon(exe_path: &Path, icon_dir: &IconDir) -> Result<(), String> {
+    pe_resources::replace_exe_icon(exe_path, icon_dir)
+        .map_err(|e| format!("아이콘 변경 실패: {}", e))
 }
 
 fn copy_dir_recursive(src: &Path, dst: &Path) -> std::io::Result<()> {
@@ -228,14 +243,15 @@ async fn build_project(
     project_json: String,
     output_dir: String,
     media_paths: Vec<String>,
-) -> Result<String, String> {
+    strict: Option<bool>,
+) -> Result<BuildResult, String> {
     let output_path = PathBuf::from(&output_dir);
 
     // 출력 디렉토리 생성
     fs::create_dir_all(&output_path).map_err(|e| e.to_string())?;
 
     // 빌드 진행 상황 전송
-    let _ = app.emit("build-progress", "프로젝트 파일 준비 중...");
+    build_progress::stage(&app, BuildStage::Preparing, "프로젝트 파일 준비 중...");
 
     // 1. 프로젝트 데이터를 JSON 파일로 저장
     let project_file = output_path.join("project.json");
@@ -245,23 +261,13 @@ async fn build_project(
     let media_dir = output_path.join("media");
     fs::create_dir_all(&media_dir).map_err(|e| e.to_string())?;
 
-    let _ = app.emit("build-progress", "미디어 파일 복사 중...");
+    build_progress::stage(&app, BuildStage::CopyingMedia, "미디어 파일 복사 중...");
 
     // 각 미디어 파일 복사
-    for media_path in media_paths.iter() {
-        if !media_path.is_empty() {
-            let source = PathBuf::from(media_path);
-            if source.exists() {
-                if let Some(filename) = source.file_name() {
-                    let dest = media_dir.join(filename);
-                    fs::copy(&source, &dest).map_err(|e| e.to_string())?;
-                }
-            }
-        }
-    }
+    let missing_media = copy_media_paths(&app, &project_json, &media_paths, &media_dir, strict.unwrap_or(false))?;
 
     // 3. 현재 실행 파일 복사
-    let _ = app.emit("build-progress", "실행 파일 생성 중...");
+    build_progress::stage(&app, BuildStage::CopyingTemplate, "실행 파일 생성 중...");
 
     // 현재 실행중인 exe 파일 경로 가져오기
     let current_exe = std::env::current_exe().map_err(|e| e.to_string())?;
@@ -282,9 +288,9 @@ async fn build_project(
         copy_dir_recursive(&resources_src, &resources_dst).map_err(|e| e.to_string())?;
     }
 
-    let _ = app.emit("build-progress", "빌드 완료!");
+    build_progress::stage(&app, BuildStage::Completed, "빌드 완료!");
 
-    Ok(output_dir)
+    Ok(BuildResult { output_file: output_dir, missing_media })
 }
 
 #[tauri::command]
@@ -296,18 +302,13 @@ fn get_temp_path(relative_path: String) -> Result<String, String> {
 }
 
 #[tauri::command]
-fn read_project_file() -> Result<String, String> {
-    // 실행 파일에서 내장된 프로젝트 데이터 읽기
-    let current_exe = std::env::current_exe().map_err(|e| e.to_string())?;
-
-    // exe 끝에서 데이터 읽기 시도
-    match read_data_from_exe(&current_exe) {
-        Ok(data) => {
-            String::from_utf8(data)
-                .map_err(|e| format!("프로젝트 데이터 디코딩 실패: {}", e))
-        }
+fn read_project_file(app: tauri::AppHandle) -> Result<String, String> {
+    // 실행 파일에서 내장된 프로젝트 데이터 읽기 (시작 시 사용 허가를 확인한 경우만)
+    match Payload::current() {
+        Ok(_) => payload_access::payload(&app)?.project_json(),
         Err(_) => {
             // 내장 데이터가 없으면 project.json 파일에서 읽기 (개발 모드 호환)
+            let current_exe = std::env::current_exe().map_err(|e| e.to_string())?;
             let exe_dir = current_exe.parent()
                 .ok_or_else(|| "실행 파일 디렉토리를 찾을 수 없습니다.".to_string())?;
 
@@ -381,7 +382,8 @@ async fn build_standalone_executable(
     output_file: String,
     media_paths: Vec<String>,
     app_icon_path: Option<String>,
-) -> Result<String, String> {
+    strict: Option<bool>,
+) -> Result<BuildResult, String> {
     let output_path = PathBuf::from(&output_file);
     let output_dir = output_path.parent()
         .ok_or_else(|| "출력 디렉토리를 찾을 수 없습니다.".to_string())?;
@@ -389,7 +391,8 @@ async fn build_standalone_executable(
     // 출력 디렉토리 생성
     fs::create_dir_all(output_dir).map_err(|e| e.to_string())?;
 
-    let _ = app.emit("build-progress", "프로젝트 빌드 준비 중...");
+    build_progress::begin(&app);
+    build_progress::stage(&app, BuildStage::Preparing, "프로젝트 빌드 준비 중...");
 
     // 임시 빌드 디렉토리 생성
     let temp_build_dir = env::temp_dir().join(format!("tutorial_build_{}", std::time::SystemTime::now()
@@ -398,142 +401,126 @@ async fn build_standalone_executable(
         .as_secs()));
     fs::create_dir_all(&temp_build_dir).map_err(|e| e.to_string())?;
 
+    let result = build_standalone_v1(
+        &app,
+        &project_json,
+        &media_paths,
+        app_icon_path.as_deref().map(Path::new),
+        strict.unwrap_or(false),
+        &temp_build_dir,
+        &output_path,
+    );
+
+    // 성공/실패/취소와 관계없이 임시 빌드 디렉토리 삭제
+    let _ = fs::remove_dir_all(&temp_build_dir);
+
+    let missing_media = match result {
+        Ok(missing_media) => missing_media,
+        Err(e) => {
+            if build_progress::is_cancelled(&app) {
+                build_progress::stage(&app, BuildStage::Cancelled, build_progress::BUILD_CANCELLED);
+            }
+            return Err(e);
+        }
+    };
+
+    build_progress::stage(&app, BuildStage::Completed, "빌드 완료!");
+
+    Ok(BuildResult { output_file, missing_media })
+}
+
+// V1 빌드: 템플릿 빌드 + 아이콘 적용 + 프로젝트 JSON append
+fn build_standalone_v1(
+    app: &tauri::AppHandle,
+    project_json: &str,
+    media_paths: &[String],
+    app_icon_path: Option<&Path>,
+    strict: bool,
+    temp_build_dir: &Path,
+    output_path: &Path,
+) -> Result<Vec<MissingMedia>, String> {
     // 1. project.json 저장
     let project_file = temp_build_dir.join("project.json");
-    fs::write(&project_file, &project_json).map_err(|e| e.to_string())?;
+    fs::write(&project_file, project_json).map_err(|e| e.to_string())?;
 
     // 2. 미디어 디렉토리 생성 및 파일 복사
     let media_dir = temp_build_dir.join("media");
     fs::create_dir_all(&media_dir).map_err(|e| e.to_string())?;
 
-    let _ = app.emit("build-progress", "미디어 파일 복사 중...");
-
-    for media_path in media_paths.iter() {
-        if !media_path.is_empty() {
-            let source = PathBuf::from(media_path);
-            if source.exists() {
-                if let Some(filename) = source.file_name() {
-                    let dest = media_dir.join(filename);
-                    fs::copy(&source, &dest).map_err(|e| e.to_string())?;
-                }
-            }
-        }
-    }
+    build_progress::stage(app, BuildStage::CopyingMedia, "미디어 파일 복사 중...");
 
-    // 3. 현재 프로젝트의 소스 코드를 임시 빌드 디렉토리에 복사
-    let current_dir = env::current_dir().map_err(|e| e.to_string())?;
+    let missing_media = copy_media_paths(app, project_json, media_paths, &media_dir, strict)?;
 
-    // Cargo.toml이 있는 프로젝트 루트 찾기
-    let project_root = if current_dir.join("src-tauri").exists() {
-        current_dir.clone()
-    } else {
-        current_dir.parent()
-            .ok_or_else(|| "프로젝트 루트를 찾을 수 없습니다.".to_string())?
-            .to_path_buf()
-    };
+    // 3. 앱 아이콘 이미지 생성
+    let mut custom_icon: Option<IconDir> = None;
 
-    // 4. 앱 아이콘 ICO 파일 생성 (임시 폴더에)
-    let mut custom_icon_path: Option<PathBuf> = None;
+    if let Some(source_icon) = app_icon_path.filter(|p| p.exists()) {
+        build_progress::stage(app, BuildStage::ConvertingIcon, "앱 아이콘 변환 중...");
 
-    if let Some(icon_path) = &app_icon_path {
-        let source_icon = PathBuf::from(icon_path);
-        if source_icon.exists() {
-            let _ = app.emit("build-progress", "앱 아이콘 변환 중...");
-
-            // 임시 폴더에 ICO 파일 생성
-            let ico_path = temp_build_dir.join("custom_icon.ico");
-            match create_ico_file(&source_icon, &ico_path) {
-                Ok(_) => {
-                    custom_icon_path = Some(ico_path);
-                }
-                Err(e) => {
-                    let _ = app.emit("build-progress", &format!("아이콘 변환 경고: {} (기본 아이콘 사용)", e));
-                }
+        match create_icon_dir(source_icon) {
+            Ok(icon_dir) => {
+                custom_icon = Some(icon_dir);
+            }
+            Err(e) => {
+                build_progress::stage(app, BuildStage::ConvertingIcon, format!("아이콘 변환 경고: {} (기본 아이콘 사용)", e));
             }
         }
     }
 
-    // 5. Tauri 빌드 실행
-    let _ = app.emit("build-progress", "프로젝트 빌드 중... (몇 분 소요될 수 있습니다)");
+    // 4. 템플릿 실행 파일 빌드
+    let built_exe = source_build::build_from_source(app)?;
+    build_progress::check_cancelled(app)?;
 
-    // npm run tauri:build:product 실행
-    use std::process::Command;
-    let build_output = Command::new("cmd")
-        .args(&["/C", "npm", "run", "tauri:build:product"])
-        .current_dir(&project_root)
-        .output()
-        .map_err(|e| format!("빌드 명령 실행 실패: {}", e))?;
+    build_progress::stage(app, BuildStage::CopyingTemplate, "빌드된 실행 파일 복사 중...");
 
-    if !build_output.status.success() {
-        let stderr = String::from_utf8_lossy(&build_output.stderr);
-        let stdout = String::from_utf8_lossy(&build_output.stdout);
-        return Err(format!("빌드 실패:\n{}\n{}", stdout, stderr));
-    }
+    // 5. 빌드된 실행 파일과 리소스를 최종 출력 위치로 복사
+    fs::copy(&built_exe, output_path).map_err(|e| e.to_string())?;
 
-    // 빌드된 실행 파일 찾기
-    let target_dir = project_root.join("src-tauri").join("target").join("release");
-    let built_exe = if cfg!(target_os = "windows") {
-        target_dir.join("app.exe")
-    } else if cfg!(target_os = "macos") {
-        target_dir.join("app")
-    } else {
-        target_dir.join("app")
-    };
-
-    if !built_exe.exists() {
-        return Err(format!(
-            "빌드된 실행 파일을 찾을 수 없습니다: {}",
-            built_exe.display()
-        ));
+    // 여기서부터 실패하면 불완전한 출력 파일을 남기지 않음
+    let result = finish_standalone_v1(app, &project_file, output_path, custom_icon.as_ref());
+    if result.is_err() {
+        let _ = fs::remove_file(output_path);
     }
+    result.map(|_| missing_media)
+}
 
-    let _ = app.emit("build-progress", "빌드된 실행 파일 복사 중...");
-
-    // 6. 빌드된 실행 파일과 리소스를 최종 출력 위치로 복사
-    fs::copy(&built_exe, &output_path).map_err(|e| e.to_string())?;
-
-    // 7. 커스텀 아이콘이 있으면 exe 파일의 아이콘 변경
-    if let Some(ico_path) = &custom_icon_path {
-        let _ = app.emit("build-progress", "앱 아이콘 적용 중...");
-        match change_exe_icon(&output_path, ico_path) {
+// V1 빌드의 아이콘 적용과 프로젝트 데이터 append
+fn finish_standalone_v1(
+    app: &tauri::AppHandle,
+    project_file: &Path,
+    output_path: &Path,
+    custom_icon: Option<&IconDir>,
+) -> Result<(), String> {
+    // 6. 커스텀 아이콘이 있으면 exe 파일의 아이콘 변경
+    if let Some(icon_dir) = custom_icon {
+        build_progress::stage(app, BuildStage::ApplyingIcon, "앱 아이콘 적용 중...");
+        match change_exe_icon(output_path, icon_dir) {
             Ok(_) => {
-                let _ = app.emit("build-progress", "앱 아이콘 적용 완료!");
+                build_progress::stage(app, BuildStage::ApplyingIcon, "앱 아이콘 적용 완료!");
             }
             Err(e) => {
                 // 아이콘 변경 실패해도 빌드는 계속 진행
-                let _ = app.emit("build-progress", &format!("아이콘 적용 실패 (기본 아이콘 사용): {}", e));
+                build_progress::stage(app, BuildStage::ApplyingIcon, format!("아이콘 적용 실패 (기본 아이콘 사용): {}", e));
             }
         }
     }
+    build_progress::check_cancelled(app)?;
 
-    // 8. 프로젝트 데이터를 exe 파일 끝에 추가 (단일 파일 배포)
-    let _ = app.emit("build-progress", "프로젝트 데이터 내장 중...");
-    let project_data = fs::read(&project_file).map_err(|e| e.to_string())?;
-    append_data_to_exe(&output_path, &project_data)?;
-
-    // 9. 임시 빌드 디렉토리 삭제
-    let _ = fs::remove_dir_all(&temp_build_dir);
-
-    let _ = app.emit("build-progress", "빌드 완료!");
-
-    Ok(output_file)
+    // 7. 프로젝트 데이터를 exe 파일 끝에 추가 (단일 파일 배포)
+    build_progress::stage(app, BuildStage::EmbeddingProject, "프로젝트 데이터 내장 중...");
+    let project_data = fs::read(project_file).map_err(|e| e.to_string())?;
+    append_data_to_exe(output_path, &project_data)
 }
 
-// 번들된 product-template.exe 찾기
-fn find_bundled_template(app: &tauri::AppHandle) -> Option<PathBuf> {
-    // 유효한 exe인지 확인하는 함수 (최소 1MB 이상)
-    let is_valid_exe = |path: &PathBuf| -> bool {
-        if let Ok(metadata) = fs::metadata(path) {
-            metadata.len() > 1_000_000  // 1MB 이상이면 유효한 exe로 간주
-        } else {
-            false
-        }
-    };
+// 대상 플랫폼의 번들된 템플릿 찾기 (product-template.exe, product-template.app 등)
+fn find_bundled_template(app: &tauri::AppHandle, target: ProductTarget) -> Option<PathBuf> {
+    let template_name = target.template_name();
+    let is_valid_template = |path: &PathBuf| target.is_valid_template(path);
 
     // 1. Tauri 리소스 경로에서 찾기 (번들된 앱)
     if let Ok(resource_path) = app.path().resource_dir() {
-        let template_path = resource_path.join("product-template.exe");
-        if template_path.exists() && is_valid_exe(&template_path) {
+        let template_path = resource_path.join(template_name);
+        if is_valid_template(&template_path) {
             return Some(template_path);
         }
     }
@@ -541,14 +528,14 @@ fn find_bundled_template(app: &tauri::AppHandle) -> Option<PathBuf> {
     // 2. 실행 파일 옆에서 찾기
     if let Ok(exe_path) = std::env::current_exe() {
         if let Some(exe_dir) = exe_path.parent() {
-            let template_path = exe_dir.join("product-template.exe");
-            if template_path.exists() && is_valid_exe(&template_path) {
+            let template_path = exe_dir.join(template_name);
+            if is_valid_template(&template_path) {
                 return Some(template_path);
             }
 
             // resources 하위 폴더에서 찾기
-            let template_path = exe_dir.join("resources").join("product-template.exe");
-            if template_path.exists() && is_valid_exe(&template_path) {
+            let template_path = exe_dir.join("resources").join(template_name);
+            if is_valid_template(&template_path) {
                 return Some(template_path);
             }
         }
@@ -557,14 +544,14 @@ fn find_bundled_template(app: &tauri::AppHandle) -> Option<PathBuf> {
     // 3. 개발 모드: 프로젝트 src-tauri/resources에서 찾기
     if let Ok(current_dir) = env::current_dir() {
         // 프로젝트 루트에서 실행된 경우
-        let template_path = current_dir.join("src-tauri").join("resources").join("product-template.exe");
-        if template_path.exists() && is_valid_exe(&template_path) {
+        let template_path = current_dir.join("src-tauri").join("resources").join(template_name);
+        if is_valid_template(&template_path) {
             return Some(template_path);
         }
 
         // src-tauri에서 실행된 경우
-        let template_path = current_dir.join("resources").join("product-template.exe");
-        if template_path.exists() && is_valid_exe(&template_path) {
+        let template_path = current_dir.join("resources").join(template_name);
+        if is_valid_template(&template_path) {
             return Some(template_path);
         }
     }
@@ -574,8 +561,8 @@ fn find_bundled_template(app: &tauri::AppHandle) -> Option<PathBuf> {
         let mut current = exe_path.as_path();
         for _ in 0..5 {
             if let Some(parent) = current.parent() {
-                let template_path = parent.join("src-tauri").join("resources").join("product-template.exe");
-                if template_path.exists() && is_valid_exe(&template_path) {
+                let template_path = parent.join("src-tauri").join("resources").join(template_name);
+                if is_valid_template(&template_path) {
                     return Some(template_path);
                 }
                 current = parent;
@@ -588,162 +575,901 @@ fn find_bundled_template(app: &tauri::AppHandle) -> Option<PathBuf> {
     None
 }
 
-// 개발 환경에서 빌드
-fn build_from_source(app: &tauri::AppHandle) -> Result<PathBuf, String> {
-    let current_dir = env::current_dir().map_err(|e| e.to_string())?;
-
-    // 프로젝트 루트 찾기 - 여러 경로 시도
-    let mut candidates: Vec<PathBuf> = vec![
-        current_dir.clone(),
-        current_dir.join("..").canonicalize().unwrap_or_default(),
-    ];
+// V2 빌드 명령의 추가 옵션 (모두 생략 가능)
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct BuildRequestOptions {
+    // 시작 시 모든 미디어 해시 검증
+    verify_on_startup: bool,
+    // 생략 시 빌더가 실행 중인 OS
+    target: Option<ProductTarget>,
+    // 미디어/프로젝트 데이터 암호화 (키 파일을 지정하면 자동으로 켜짐)
+    encrypt: bool,
+    encryption_key_file: Option<String>,
+    // 키 파일/컴파일된 키가 없을 때 새 키를 만들어 제품 옆에 평문 라이선스 파일로 저장
+    generate_encryption_key: bool,
+    // 기간/기기 제한 라이선스 발급
+    license: Option<LicenseOptions>,
+    // 내장 데이터 서명 키 파일 (Ed25519, 64자리 hex 시드)
+    signing_key_file: Option<String>,
+    // 프로젝트 검사에서 문제가 있거나 읽을 수 없는 미디어가 있으면 빌드 중단 (false면 경고만)
+    strict: bool,
+}
 
-    // 실행 파일 위치 기준
-    if let Ok(exe_path) = std::env::current_exe() {
-        if let Some(exe_dir) = exe_path.parent() {
-            candidates.push(exe_dir.to_path_buf());
-            if let Some(parent) = exe_dir.parent() {
-                candidates.push(parent.to_path_buf());
-                // target/debug 에서 실행될 경우: target/debug -> target -> src-tauri -> project_root
-                if let Some(grandparent) = parent.parent() {
-                    candidates.push(grandparent.to_path_buf());
-                    if let Some(greatgrandparent) = grandparent.parent() {
-                        candidates.push(greatgrandparent.to_path_buf());
-                    }
-                }
-            }
-        }
-    }
+// V2: 바이너리 미디어를 exe에 직접 append하는 새 빌드 함수
+#[tauri::command]
+async fn build_standalone_executable_v2(
+    app: tauri::AppHandle,
+    project_json: String,
+    media_info_json: String,
+    output_file: String,
+    app_icon_path: Option<String>,
+    temp_dir: String,
+    options: Option<BuildRequestOptions>,
+) -> Result<BuildResult, String> {
+    let output_path = PathBuf::from(&output_file);
+    let temp_build_dir = PathBuf::from(&temp_dir);
 
-    // src-tauri와 package.json이 있는 프로젝트 루트 찾기
-    let project_root = candidates.iter()
-        .find(|p| p.join("src-tauri").exists() && p.join("package.json").exists())
-        .cloned()
-        .ok_or_else(|| {
-            let tried = candidates.iter()
-                .map(|p| format!("  - {}", p.display()))
-                .collect::<Vec<_>>()
-                .join("\n");
-            format!("개발 환경을 찾을 수 없습니다.\n\n시도한 경로:\n{}", tried)
-        })?;
+    build_progress::begin(&app);
 
-    let _ = app.emit("build-progress", "프로젝트 빌드 중... (몇 분 소요될 수 있습니다)");
+    // 미디어 정보 파싱
+    let media_files: Vec<MediaBuildInfo> = serde_json::from_str(&media_info_json)
+        .map_err(|e| format!("미디어 정보 파싱 실패: {}", e))?;
 
-    use std::process::Command;
-    let build_output = Command::new("cmd")
-        .args(&["/C", "npm", "run", "tauri:build:product"])
-        .current_dir(&project_root)
-        .output()
-        .map_err(|e| format!("빌드 명령 실행 실패: {}", e))?;
+    let request = options.unwrap_or_default();
+    let options = ProductBuildOptions {
+        app_icon_path: app_icon_path.as_deref().map(Path::new),
+        verify_on_startup: request.verify_on_startup,
+        target: request.target.unwrap_or_else(ProductTarget::host),
+        encrypt: request.encrypt || request.encryption_key_file.is_some(),
+        encryption_key_file: request.encryption_key_file.as_deref().map(Path::new),
+        generate_encryption_key: request.generate_encryption_key,
+        license: request.license.as_ref(),
+        signing_key_file: request.signing_key_file.as_deref().map(Path::new),
+        strict: request.strict,
+    };
+    let result = build_product_v2(&app, &project_json, &media_files, &output_path, &options);
 
-    if !build_output.status.success() {
-        let stderr = String::from_utf8_lossy(&build_output.stderr);
-        let stdout = String::from_utf8_lossy(&build_output.stdout);
-        return Err(format!("빌드 실패:\n{}\n{}", stdout, stderr));
-    }
+    // 임시 빌드 디렉토리 삭제
+    let _ = fs::remove_dir_all(&temp_build_dir);
 
-    let target_dir = project_root.join("src-tauri").join("target").join("release");
-    let built_exe = target_dir.join("app.exe");
+    let missing_media = match result {
+        Ok(missing_media) => missing_media,
+        Err(e) => {
+            if build_progress::is_cancelled(&app) {
+                build_progress::stage(&app, BuildStage::Cancelled, build_progress::BUILD_CANCELLED);
+            }
+            return Err(e);
+        }
+    };
 
-    if !built_exe.exists() {
-        return Err(format!("빌드된 실행 파일을 찾을 수 없습니다: {}", built_exe.display()));
-    }
+    build_progress::stage(&app, BuildStage::Completed, "빌드 완료!");
 
-    Ok(built_exe)
+    Ok(BuildResult { output_file, missing_media })
 }
 
-// V2: 바이너리 미디어를 exe에 직접 append하는 새 빌드 함수
+// 기존 제품 exe의 내장 데이터만 교체 (템플릿 복사/아이콘 적용 없이 빠르게 재빌드)
+// 이미 적용된 아이콘과 템플릿은 그대로 유지된다.
 #[tauri::command]
-async fn build_standalone_executable_v2(
+async fn update_product_payload(
     app: tauri::AppHandle,
+    exe_path: String,
     project_json: String,
     media_info_json: String,
-    output_file: String,
-    app_icon_path: Option<String>,
     temp_dir: String,
-) -> Result<String, String> {
-    let output_path = PathBuf::from(&output_file);
+    verify_on_startup: Option<bool>,
+    encryption_key_file: Option<String>,
+    strict: Option<bool>,
+) -> Result<BuildResult, String> {
+    let exe = PathBuf::from(&exe_path);
+    let temp_build_dir = PathBuf::from(&temp_dir);
+
+    build_progress::begin(&app);
+
+    let media_files: Vec<MediaBuildInfo> = serde_json::from_str(&media_info_json)
+        .map_err(|e| format!("미디어 정보 파싱 실패: {}", e))?;
+
+    let result = update_product_v2(
+        &app,
+        &exe,
+        &project_json,
+        &media_files,
+        verify_on_startup,
+        encryption_key_file.as_deref().map(Path::new),
+        strict.unwrap_or(false),
+    );
+
+    // 임시 빌드 디렉토리 삭제
+    let _ = fs::remove_dir_all(&temp_build_dir);
+
+    let missing_media = match result {
+        Ok(missing_media) => missing_media,
+        Err(e) => {
+            if build_progress::is_cancelled(&app) {
+                build_progress::stage(&app, BuildStage::Cancelled, build_progress::BUILD_CANCELLED);
+            }
+            return Err(e);
+        }
+    };
+
+    build_progress::stage(&app, BuildStage::Completed, "데이터 업데이트 완료!");
+
+    Ok(BuildResult { output_file: exe_path, missing_media })
+}
+
+// 기존 데이터를 뺀 사본에 새 데이터를 append해 교체 (verify_on_startup이 없으면 기존 설정 유지)
+// 기존 데이터가 암호화되어 있으면 같은 키(키 파일, 라이선스 파일 또는 컴파일된 키)로 다시 암호화
+fn update_product_v2(
+    app: &tauri::AppHandle,
+    exe_path: &Path,
+    project_json: &str,
+    media_files: &[MediaBuildInfo],
+    verify_on_startup: Option<bool>,
+    encryption_key_file: Option<&Path>,
+    strict: bool,
+) -> Result<Vec<MissingMedia>, String> {
+    // .app 번들이면 번들 안의 페이로드 리소스 파일을 교체
+    let payload_file = product_target::payload_path(exe_path);
+
+    // 기존 데이터를 건드리기 전에 미디어 확인
+    let project = Project::parse(project_json).ok();
+    let (media_files, missing_media) = check_media_files(app, project.as_ref(), media_files, strict)?;
+
+    build_progress::stage(app, BuildStage::StrippingPayload, "기존 데이터 제거 중...");
+
+    // 데이터를 잘라내기 전에 키를 확인 (키가 없으면 기존 제품을 그대로 둠)
+    let previous = read_payload_settings(&payload_file)?;
+    if previous.licensed {
+        return Err("라이선스가 포함된 제품은 데이터만 교체할 수 없습니다. 다시 빌드해주세요.".to_string());
+    }
+    if previous.signed {
+        return Err("서명된 제품은 데이터만 교체할 수 없습니다. 다시 빌드해 서명해주세요.".to_string());
+    }
+    let encryption_key = match encryption_key_file {
+        Some(path) => Some(PayloadKey::load_file(path)?),
+        None if previous.encrypted => Some(payload_crypto::find_runtime_key(&payload_file)
+            .map_err(|e| format!("기존 데이터가 암호화되어 있어 같은 키가 필요합니다: {}", e))?),
+        None => None,
+    };
+
+    let contents = PayloadContents {
+        project_json,
+        media_files: &media_files,
+        verify_on_startup: verify_on_startup.unwrap_or(previous.verify_on_startup),
+        encryption_key: encryption_key.as_ref(),
+        app_icon: previous.app_icon.as_deref(),
+        license: None,
+        signer: None,
+    };
+
+    // 기존 데이터를 뺀 사본을 옆의 임시 경로에 만들어 새 데이터를 기록하고, 모두 성공했을 때만 교체
+    // (실패/취소되어도 기존 제품은 그대로 남음)
+    let staging = atomic_output::staging_path(&payload_file);
+    let _ = fs::remove_file(&staging);
+    let result = copy_without_payload(&payload_file, &staging)
+        .and_then(|_| embed_payload_v2(app, &staging, &contents))
+        .and_then(|_| {
+            build_progress::stage(app, BuildStage::Finalizing, "출력 파일 저장 중...");
+            atomic_output::sync(&staging)?;
+            build_progress::check_cancelled(app)?;
+            atomic_output::commit(&staging, &payload_file)
+        });
+    if result.is_err() {
+        let _ = fs::remove_file(&staging);
+    }
+    result.map(|_| missing_media)
+}
+
+// V2 빌드 옵션
+#[derive(Debug, Clone, Copy)]
+struct ProductBuildOptions<'a> {
+    app_icon_path: Option<&'a Path>,
+    verify_on_startup: bool,
+    target: ProductTarget,
+    encrypt: bool,
+    // 없으면 컴파일된 키를 사용
+    encryption_key_file: Option<&'a Path>,
+    // 키 파일/컴파일된 키가 모두 없을 때 새 키 생성 (없으면 암호화 빌드 실패)
+    generate_encryption_key: bool,
+    license: Option<&'a LicenseOptions>,
+    signing_key_file: Option<&'a Path>,
+    // 프로젝트 검사에서 문제가 있으면 빌드 중단 (false면 경고만)
+    strict: bool,
+}
+
+// 내장 전 프로젝트 검사 (깨진 goto, 없는 미디어, 화면 밖 버튼/터치 영역, 빈 페이지)
+// 엄격 모드면 발견한 문제를 모두 담아 실패하고, 아니면 경고로 알린 뒤 계속 빌드
+// 반환값: 읽은 프로젝트 (형식이 맞지 않아 검사를 생략했으면 None)
+fn check_project(
+    app: &tauri::AppHandle,
+    project_json: &str,
+    media_files: &[MediaBuildInfo],
+    strict: bool,
+) -> Result<Option<Project>, String> {
+    build_progress::stage(app, BuildStage::Preparing, "프로젝트 검사 중...");
+
+    let project = match Project::parse(project_json) {
+        Ok(project) => project,
+        Err(e) if strict => return Err(e),
+        Err(e) => {
+            build_progress::stage(app, BuildStage::Preparing, format!("경고: {} (검사 생략)", e));
+            return Ok(None);
+        }
+    };
+
+    let media_ids: HashSet<&str> = media_files.iter().map(|m| m.id.as_str()).collect();
+    let issues = project_model::validate_project(&project, &media_ids);
+    if strict && !issues.is_empty() {
+        return Err(project_model::format_issues(&issues));
+    }
+    for issue in &issues {
+        build_progress::stage(app, BuildStage::Preparing, format!("경고: {}", issue.message));
+    }
+
+    Ok(Some(project))
+}
+
+// V2 빌드 파이프라인: 템플릿 복사 + 아이콘 적용 + 데이터 append
+// (GUI 빌드 명령과 CLI 빌드가 함께 사용)
+fn build_product_v2(
+    app: &tauri::AppHandle,
+    project_json: &str,
+    media_files: &[MediaBuildInfo],
+    output_path: &Path,
+    options: &ProductBuildOptions,
+) -> Result<Vec<MissingMedia>, String> {
     let output_dir = output_path.parent()
         .ok_or_else(|| "출력 디렉토리를 찾을 수 없습니다.".to_string())?;
-    let temp_build_dir = PathBuf::from(&temp_dir);
 
     // 출력 디렉토리 생성
     fs::create_dir_all(output_dir).map_err(|e| e.to_string())?;
 
-    let _ = app.emit("build-progress", "프로젝트 빌드 준비 중...");
+    build_progress::stage(app, BuildStage::Preparing, "프로젝트 빌드 준비 중...");
 
-    // 미디어 정보 파싱
-    let media_files: Vec<MediaBuildInfo> = serde_json::from_str(&media_info_json)
-        .map_err(|e| format!("미디어 정보 파싱 실패: {}", e))?;
+    let project = check_project(app, project_json, media_files, options.strict)?;
+    let (media_files, missing_media) = check_media_files(app, project.as_ref(), media_files, options.strict)?;
 
-    // 앱 아이콘 ICO 파일 생성 (임시 폴더에)
-    let mut custom_icon_path: Option<PathBuf> = None;
+    // 앱 아이콘 이미지 생성
+    // 창/작업 표시줄 아이콘 PNG는 모든 대상에 내장하고, 실행 파일 아이콘 리소스는 Windows 대상만 교체 가능
+    let mut custom_icon: Option<IconDir> = None;
+    let mut app_icon_png: Option<Vec<u8>> = None;
 
-    if let Some(icon_path) = &app_icon_path {
-        let source_icon = PathBuf::from(icon_path);
-        if source_icon.exists() {
-            let _ = app.emit("build-progress", "앱 아이콘 변환 중...");
+    if let Some(source_icon) = options.app_icon_path.filter(|p| p.exists()) {
+        build_progress::stage(app, BuildStage::ConvertingIcon, "앱 아이콘 변환 중...");
 
-            let ico_path = temp_build_dir.join("custom_icon.ico");
-            match create_ico_file(&source_icon, &ico_path) {
-                Ok(_) => {
-                    custom_icon_path = Some(ico_path);
+        match create_app_icon_png(source_icon) {
+            Ok(png) => {
+                app_icon_png = Some(png);
+            }
+            Err(e) => {
+                build_progress::stage(app, BuildStage::ConvertingIcon, format!("아이콘 변환 경고: {} (기본 아이콘 사용)", e));
+            }
+        }
+
+        if options.target.supports_icon_resource() {
+            match create_icon_dir(source_icon) {
+                Ok(icon_dir) => {
+                    custom_icon = Some(icon_dir);
                 }
                 Err(e) => {
-                    let _ = app.emit("build-progress", &format!("아이콘 변환 경고: {} (기본 아이콘 사용)", e));
+                    build_progress::stage(app, BuildStage::ConvertingIcon, format!("아이콘 변환 경고: {} (기본 아이콘 사용)", e));
                 }
             }
+        } else if app_icon_png.is_some() {
+            build_progress::stage(app, BuildStage::ConvertingIcon, "실행 파일 아이콘 변경은 Windows 대상만 지원합니다. (창 아이콘만 적용)");
         }
     }
 
-    // 1. 먼저 번들된 템플릿 exe 찾기
-    // 2. 없으면 개발 환경에서 빌드
-    let source_exe = if let Some(template_path) = find_bundled_template(&app) {
+    // 1. 먼저 번들된 템플릿 찾기
+    // 2. 없으면 개발 환경에서 빌드 (호스트와 같은 단일 바이너리 대상만 가능)
+    let source_exe = if let Some(template_path) = find_bundled_template(app, options.target) {
         let msg = format!("템플릿 실행 파일 발견: {}", template_path.display());
-        let _ = app.emit("build-progress", &msg);
+        build_progress::stage(app, BuildStage::Preparing, msg);
         template_path
+    } else if options.target == ProductTarget::host() && !options.target.is_bundle() {
+        build_progress::stage(app, BuildStage::BuildingTemplate, "템플릿을 찾을 수 없어 개발 환경에서 빌드합니다...");
+        source_build::build_from_source(app)?
+    } else {
+        return Err(format!(
+            "{:?} 대상 템플릿({})을 찾을 수 없습니다. resources 폴더에 템플릿을 넣어주세요.",
+            options.target,
+            options.target.template_name()
+        ));
+    };
+
+    build_progress::check_cancelled(app)?;
+
+    // 암호화 키 결정 (새로 만든 키는 제품 옆 라이선스 파일로 저장)
+    let encryption = if options.encrypt {
+        Some(payload_crypto::resolve_build_key(options.encryption_key_file, options.generate_encryption_key)?)
     } else {
-        let _ = app.emit("build-progress", "템플릿을 찾을 수 없어 개발 환경에서 빌드합니다...");
-        build_from_source(&app)?
+        None
+    };
+    if encryption.as_ref().is_some_and(|(_, new_key)| new_key.is_some()) {
+        build_progress::stage(app, BuildStage::Preparing, format!(
+            "경고: 새 암호화 키를 만들어 제품 옆에 평문으로 저장합니다 ({}). 이 파일이 있으면 누구나 내용을 복호화할 수 있습니다.",
+            payload_crypto::LICENSE_FILE_NAME
+        ));
+    }
+
+    // 라이선스 발급 키 확인 (제품 템플릿에 이 공개 키가 컴파일되어 있어야 제품이 라이선스를 확인할 수 있음)
+    let license_issuer = options.license.map(LicenseIssuer::new).transpose()?;
+    if let Some(issuer) = &license_issuer {
+        let public_key = issuer.public_key_hex();
+        build_progress::stage(app, BuildStage::Preparing, format!("라이선스 발급자 공개 키: {}", public_key));
+        if !license::template_trusts_issuer(&source_exe, &public_key)? {
+            return Err(format!(
+                "제품 템플릿에 이 발급자 공개 키가 컴파일되어 있지 않아 라이선스를 넣을 수 없습니다. \
+                 TUTORIAL_LICENSE_ISSUER_KEY={}로 템플릿을 다시 빌드해주세요.",
+                public_key
+            ));
+        }
+    }
+
+    // 서명 키 확인 (제품 템플릿에 이 공개 키를 컴파일해 넣으면 시작 시 서명을 확인함)
+    let signer = options.signing_key_file.map(PayloadSigner::load).transpose()?;
+    if let Some(signer) = &signer {
+        build_progress::stage(app, BuildStage::Preparing, format!(
+            "서명 공개 키: {}",
+            signer.public_key_hex()
+        ));
+    }
+
+    let contents = PayloadContents {
+        project_json,
+        media_files: &media_files,
+        verify_on_startup: options.verify_on_startup,
+        encryption_key: encryption.as_ref().map(|(key, _)| key),
+        app_icon: app_icon_png.as_deref(),
+        license: license_issuer.as_ref(),
+        signer: signer.as_ref(),
+    };
+
+    // 출력 경로 옆의 임시 경로에 제품을 만들고 모두 성공했을 때만 출력 경로로 교체
+    // (실패/취소되면 임시 파일만 삭제되고 이전 빌드 결과는 그대로 남음)
+    let staging = atomic_output::staging_path(output_path);
+    remove_product(&staging);
+
+    let new_key = encryption.as_ref().and_then(|(_, key_hex)| key_hex.as_deref());
+    let result = write_product_v2(app, &source_exe, &staging, custom_icon.as_ref(), options.target, &contents)
+        .and_then(|_| finalize_product(app, &staging, output_path, new_key));
+    if result.is_err() {
+        remove_product(&staging);
+    }
+    result.map(|_| missing_media)
+}
+
+// 임시 경로의 제품을 디스크에 동기화한 뒤 출력 경로로 교체
+// new_key: 새로 만든 암호화 키 (제품 옆 라이선스 파일도 함께 교체)
+fn finalize_product(
+    app: &tauri::AppHandle,
+    staging: &Path,
+    output_path: &Path,
+    new_key: Option<&str>,
+) -> Result<(), String> {
+    build_progress::stage(app, BuildStage::Finalizing, "출력 파일 저장 중...");
+    atomic_output::sync(staging)?;
+
+    let license = payload_crypto::license_path(output_path);
+    let license_staging = new_key
+        .map(|key_hex| atomic_output::stage_file(&license, key_hex.as_bytes()))
+        .transpose()
+        .map_err(|e| format!("라이선스 파일 저장 실패: {}", e))?;
+
+    // 라이선스 파일을 먼저 교체하고 제품은 마지막에 교체
+    // (제품 교체에 실패하면 이전 라이선스 파일로 되돌려 이전 제품과 키가 계속 맞도록 함)
+    let previous_license = license_staging.as_ref().and_then(|_| fs::read(&license).ok());
+    let mut license_committed = false;
+    let committed = build_progress::check_cancelled(app)
+        .and_then(|_| match &license_staging {
+            Some(staged) => atomic_output::commit(staged, &license)
+                .map(|_| license_committed = true)
+                .map_err(|e| format!("라이선스 파일 저장 실패: {}", e)),
+            None => Ok(()),
+        })
+        .and_then(|_| atomic_output::commit(staging, output_path));
+    if committed.is_err() {
+        if let Some(staged) = &license_staging {
+            let _ = fs::remove_file(staged);
+        }
+        if license_committed {
+            restore_license(&license, previous_license.as_deref());
+        }
+    }
+    committed?;
+
+    if new_key.is_some() {
+        build_progress::stage(app, BuildStage::Finalizing, format!(
+            "새 암호화 키를 만들었습니다. 제품과 함께 라이선스 파일을 배포하세요: {}",
+            license.display()
+        ));
+    }
+    Ok(())
+}
+
+// 제품 교체에 실패했을 때 이전 라이선스 파일로 되돌림 (없었으면 새 파일 삭제)
+fn restore_license(license: &Path, previous: Option<&[u8]>) {
+    let restored = match previous {
+        Some(data) => atomic_output::stage_file(license, data)
+            .and_then(|staged| atomic_output::commit(&staged, license)),
+        None => fs::remove_file(license).map_err(|e| e.to_string()),
     };
+    if let Err(e) = restored {
+        log::warn!("라이선스 파일 복원 실패: {}", e);
+    }
+}
 
-    // 템플릿 exe 복사
-    fs::copy(&source_exe, &output_path).map_err(|e| e.to_string())?;
+// 제품에 내장할 데이터
+#[derive(Clone, Copy)]
+struct PayloadContents<'a> {
+    project_json: &'a str,
+    media_files: &'a [MediaBuildInfo],
+    verify_on_startup: bool,
+    // 있으면 미디어와 프로젝트 JSON을 암호화
+    encryption_key: Option<&'a PayloadKey>,
+    // 제품 창/작업 표시줄 아이콘 PNG
+    app_icon: Option<&'a [u8]>,
+    // 있으면 프로젝트 JSON 해시에 묶인 라이선스 블록을 서명해 넣음
+    license: Option<&'a LicenseIssuer>,
+    // 있으면 완성된 매니페스트를 서명
+    signer: Option<&'a PayloadSigner>,
+}
+
+// 출력 제품(또는 임시 출력) 삭제 (.app 번들은 폴더째)
+fn remove_product(path: &Path) {
+    if path.is_dir() {
+        let _ = fs::remove_dir_all(path);
+    } else {
+        let _ = fs::remove_file(path);
+    }
+}
+
+// 템플릿 복사 + 아이콘 적용 + 데이터 append
+fn write_product_v2(
+    app: &tauri::AppHandle,
+    source_exe: &Path,
+    output_path: &Path,
+    custom_icon: Option<&IconDir>,
+    target: ProductTarget,
+    contents: &PayloadContents,
+) -> Result<(), String> {
+    // 템플릿 복사 (.app 번들은 폴더 전체를 복사하고 페이로드는 번들 리소스 파일에 기록)
+    if target.is_bundle() {
+        build_progress::stage(app, BuildStage::CopyingTemplate, "템플릿 번들 복사 중...");
+        copy_dir_recursive(source_exe, output_path)
+            .map_err(|e| format!("템플릿 번들 복사 실패: {}", e))?;
+    } else {
+        copy_with_progress(app, source_exe, output_path)?;
+    }
 
     // 커스텀 아이콘 적용
-    if let Some(ico_path) = &custom_icon_path {
-        let _ = app.emit("build-progress", "앱 아이콘 적용 중...");
-        match change_exe_icon(&output_path, ico_path) {
+    if let Some(icon_dir) = custom_icon {
+        build_progress::stage(app, BuildStage::ApplyingIcon, "앱 아이콘 적용 중...");
+        match change_exe_icon(output_path, icon_dir) {
             Ok(_) => {
-                let _ = app.emit("build-progress", "앱 아이콘 적용 완료!");
+                build_progress::stage(app, BuildStage::ApplyingIcon, "앱 아이콘 적용 완료!");
             }
             Err(e) => {
-                let _ = app.emit("build-progress", &format!("아이콘 적용 실패 (기본 아이콘 사용): {}", e));
+                build_progress::stage(app, BuildStage::ApplyingIcon, format!("아이콘 적용 실패 (기본 아이콘 사용): {}", e));
             }
         }
     }
 
+    build_progress::check_cancelled(app)?;
+
+    let payload_file = product_target::payload_path(output_path);
+    if target.is_bundle() {
+        fs::File::create(&payload_file)
+            .map_err(|e| format!("번들 데이터 파일 생성 실패: {}", e))?;
+    }
+
+    embed_payload_v2(app, &payload_file, contents)
+}
+
+// 진행 상황 보고/취소 확인 단위
+const COPY_CHUNK_SIZE: usize = 1024 * 1024;
+
+// 템플릿 exe를 청크 단위로 복사 (중간에 취소 가능)
+fn copy_with_progress(app: &tauri::AppHandle, source: &Path, dest: &Path) -> Result<(), String> {
+    let mut reader = fs::File::open(source)
+        .map_err(|e| format!("템플릿 파일 열기 실패: {}", e))?;
+    let total = reader.metadata()
+        .map_err(|e| format!("템플릿 파일 메타데이터 읽기 실패: {}", e))?
+        .len();
+    let mut writer = fs::File::create(dest)
+        .map_err(|e| format!("출력 파일 생성 실패: {}", e))?;
+
+    let mut buf = vec![0u8; COPY_CHUNK_SIZE];
+    let mut copied = 0u64;
+    loop {
+        build_progress::check_cancelled(app)?;
+
+        let read = reader.read(&mut buf)
+            .map_err(|e| format!("템플릿 파일 읽기 실패: {}", e))?;
+        if read == 0 {
+            break;
+        }
+        writer.write_all(&buf[..read])
+            .map_err(|e| format!("템플릿 파일 복사 실패: {}", e))?;
+
+        copied += read as u64;
+        build_progress::report(app, BuildProgress::new(BuildStage::CopyingTemplate, "템플릿 복사 중...")
+            .with_bytes(copied, total));
+    }
+
+    // Linux/AppImage 템플릿의 실행 권한 유지
+    let permissions = reader.metadata()
+        .map_err(|e| format!("템플릿 파일 메타데이터 읽기 실패: {}", e))?
+        .permissions();
+    fs::set_permissions(dest, permissions)
+        .map_err(|e| format!("출력 파일 권한 설정 실패: {}", e))?;
+
+    Ok(())
+}
+
+// V2 데이터 append + 압축 결과 알림 (새 빌드와 데이터 업데이트가 함께 사용)
+fn embed_payload_v2(app: &tauri::AppHandle, exe_path: &Path, contents: &PayloadContents) -> Result<(), String> {
     // V2 데이터 append: 미디어 바이너리 + project.json + manifest
-    let _ = app.emit("build-progress", "프로젝트 데이터 내장 중...");
+    build_progress::stage(app, BuildStage::EmbeddingMedia, "프로젝트 데이터 내장 중...");
+
+    let report = append_binary_data_v2(app, exe_path, contents)?;
+
+    if report.compressed_count > 0 {
+        let saved = report.original_bytes.saturating_sub(report.stored_bytes);
+        build_progress::stage(app, BuildStage::EmbeddingMedia, format!(
+            "미디어 압축: {}개 파일, {:.1} MB → {:.1} MB ({:.1} MB 절약)",
+            report.compressed_count,
+            report.original_bytes as f64 / 1_048_576.0,
+            report.stored_bytes as f64 / 1_048_576.0,
+            saved as f64 / 1_048_576.0,
+        ));
+    }
 
-    append_binary_data_v2(&output_path, &project_json, &media_files)?;
+    if report.deduplicated_count > 0 {
+        build_progress::stage(app, BuildStage::EmbeddingMedia, format!(
+            "중복 미디어: {}개 엔트리가 같은 데이터를 공유 ({:.1} MB 절약)",
+            report.deduplicated_count,
+            report.deduplicated_bytes as f64 / 1_048_576.0,
+        ));
+    }
 
-    // 임시 빌드 디렉토리 삭제
-    let _ = fs::remove_dir_all(&temp_build_dir);
+    if report.poster_count > 0 {
+        build_progress::stage(app, BuildStage::EmbeddingMedia, format!(
+            "비디오 포스터: {}개 내장",
+            report.poster_count
+        ));
+    }
+
+    if report.encrypted_count > 0 {
+        build_progress::stage(app, BuildStage::EmbeddingMedia, format!(
+            "데이터 암호화: 미디어 {}개 + 프로젝트 데이터",
+            report.encrypted_count
+        ));
+    }
+
+    Ok(())
+}
+
+// 기존 내장 데이터의 빌드 설정
+struct PayloadSettings {
+    verify_on_startup: bool,
+    encrypted: bool,
+    licensed: bool,
+    signed: bool,
+    // 데이터만 교체할 때 그대로 다시 내장할 앱 아이콘
+    app_icon: Option<Vec<u8>>,
+}
+
+fn read_payload_settings(exe_path: &Path) -> Result<PayloadSettings, String> {
+    let payload = Payload::open(exe_path)?;
+    Ok(match payload.manifest() {
+        Ok(manifest) => PayloadSettings {
+            verify_on_startup: manifest.verify_on_startup,
+            encrypted: manifest.is_encrypted(),
+            licensed: manifest.license.is_some(),
+            signed: manifest.signature.is_some(),
+            app_icon: payload.app_icon()?,
+        },
+        Err(_) => PayloadSettings { verify_on_startup: false, encrypted: false, licensed: false, signed: false, app_icon: None },
+    })
+}
+
+// PE/ELF 헤더와 섹션 테이블을 읽기에 충분한 크기
+const EXE_HEADER_READ_SIZE: u64 = 64 * 1024;
+
+// 기존 제품 exe에서 내장 데이터(V1/V2)를 뺀 앞부분(템플릿 상태)을 output에 복사
+fn copy_without_payload(exe_path: &Path, output: &Path) -> Result<(), String> {
+    let payload_start = {
+        let payload = Payload::open(exe_path)?;
+
+        // 오프셋을 믿고 잘라내기 전에 프로젝트 데이터가 온전한지 확인
+        if payload.format() == PayloadFormat::V2 {
+            let report = payload.verify(false)?;
+            if !report.corrupt.is_empty() {
+                return Err(format_corrupt_entries(&report.corrupt));
+            }
+        }
+
+        payload.payload_start()?
+    };
 
-    let _ = app.emit("build-progress", "빌드 완료!");
+    // 잘못된 오프셋으로 exe 본체까지 잘라내지 않도록 실행 파일 이미지 끝과 비교
+    let mut headers = Vec::new();
+    fs::File::open(exe_path)
+        .and_then(|f| f.take(EXE_HEADER_READ_SIZE).read_to_end(&mut headers))
+        .map_err(|e| format!("exe 헤더 읽기 실패: {}", e))?;
+    let image_end = product_target::image_end(&headers)?;
+    if payload_start < image_end {
+        return Err(format!(
+            "내장 데이터 위치({})가 exe 본체({} 바이트)와 겹칩니다.",
+            payload_start, image_end
+        ));
+    }
+
+    // 원본은 건드리지 않고 데이터 앞부분만 복사 (실행 권한 등도 원본과 같게)
+    let source = fs::File::open(exe_path)
+        .map_err(|e| format!("exe 파일 열기 실패: {}", e))?;
+    let mut copy = fs::File::create(output)
+        .map_err(|e| format!("임시 파일 생성 실패: {}", e))?;
+    let copied = io::copy(&mut source.take(payload_start), &mut copy)
+        .map_err(|e| format!("기존 데이터 제거 실패: {}", e))?;
+    if copied != payload_start {
+        return Err(format!("기존 데이터 제거 실패: {} / {} 바이트만 복사됨", copied, payload_start));
+    }
+
+    let permissions = fs::metadata(exe_path)
+        .map_err(|e| format!("파일 메타데이터 읽기 실패: {}", e))?
+        .permissions();
+    fs::set_permissions(output, permissions)
+        .map_err(|e| format!("임시 파일 권한 설정 실패: {}", e))
+}
+
+// 미디어 엔트리 하나를 기록한 결과
+struct StoredMedia {
+    // 실제로 기록한 바이트 수 (압축/암호화 후)
+    size: u64,
+    // 원본 파일에서 읽은 바이트 수
+    original_size: u64,
+    // 압축 후, 암호화 전 크기
+    plain_size: u64,
+    sha256: String,
+    compression: Option<MediaCompression>,
+    nonce: Option<String>,
+}
+
+// 기록한 바이트 수와 SHA-256을 함께 계산하는 출력
+struct HashingWriter<'a> {
+    file: &'a mut fs::File,
+    hasher: Sha256,
+    written: u64,
+}
+
+impl HashingWriter<'_> {
+    fn write(&mut self, data: &[u8]) -> Result<(), String> {
+        self.file.write_all(data)
+            .map_err(|e| format!("미디어 데이터 쓰기 실패: {}", e))?;
+        self.hasher.update(data);
+        self.written += data.len() as u64;
+        Ok(())
+    }
+
+    // 암호화가 켜져 있으면 암호화해서 기록
+    fn write_plain(&mut self, encryptor: Option<&mut StreamEncryptor>, data: &[u8]) -> Result<(), String> {
+        match encryptor {
+            Some(encryptor) => self.write(&encryptor.update(data)?),
+            None => self.write(data),
+        }
+    }
+}
+
+// 미디어 파일을 고정 크기 버퍼로 읽어 (압축 → 암호화 →) 현재 위치에 기록
+// progress: (처리한 바이트, 전체 바이트), 원본 기준으로 읽은 만큼 증가
+fn write_media_entry(
+    app: &tauri::AppHandle,
+    file: &mut fs::File,
+    media_info: &MediaBuildInfo,
+    compression: Option<MediaCompression>,
+    encryption_key: Option<&PayloadKey>,
+    progress: &mut (u64, u64),
+) -> Result<StoredMedia, String> {
+    let mut reader = fs::File::open(&media_info.file_path)
+        .map_err(|e| format!("미디어 파일 읽기 실패 ({}): {}", media_info.id, e))?;
+
+    let mut compressor = compression.map(StreamCompressor::new).transpose()?;
+    let nonce = encryption_key.map(|_| payload_crypto::new_nonce());
+    let mut encryptor = match (encryption_key, &nonce) {
+        (Some(key), Some(nonce)) => Some(StreamEncryptor::new(key, nonce, &media_info.id)?),
+        _ => None,
+    };
+    let mut sink = HashingWriter { file, hasher: Sha256::new(), written: 0 };
+
+    let mut buf = vec![0u8; COPY_CHUNK_SIZE];
+    let mut original_size = 0u64;
+    let mut plain_size = 0u64;
+    loop {
+        build_progress::check_cancelled(app)?;
+
+        let read = reader.read(&mut buf)
+            .map_err(|e| format!("미디어 파일 읽기 실패 ({}): {}", media_info.id, e))?;
+        if read == 0 {
+            break;
+        }
+        original_size += read as u64;
+
+        match compressor.as_mut() {
+            Some(compressor) => {
+                let compressed = compressor.update(&buf[..read])?;
+                plain_size += compressed.len() as u64;
+                sink.write_plain(encryptor.as_mut(), &compressed)?;
+            }
+            None => {
+                plain_size += read as u64;
+                sink.write_plain(encryptor.as_mut(), &buf[..read])?;
+            }
+        }
+
+        progress.0 += read as u64;
+        build_progress::report(app, BuildProgress::new(BuildStage::EmbeddingMedia, "미디어 데이터 쓰는 중...")
+            .with_bytes(progress.0, progress.1)
+            .with_media(&media_info.id));
+    }
 
-    Ok(output_file)
+    if let Some(compressor) = compressor {
+        let rest = compressor.finish()?;
+        plain_size += rest.len() as u64;
+        sink.write_plain(encryptor.as_mut(), &rest)?;
+    }
+    if let Some(encryptor) = encryptor {
+        sink.write(&encryptor.finish()?)?;
+    }
+
+    Ok(StoredMedia {
+        size: sink.written,
+        original_size,
+        plain_size,
+        sha256: format!("{:x}", sink.hasher.finalize()),
+        compression,
+        nonce,
+    })
+}
+
+// 파일 내용의 SHA-256 (고정 크기 버퍼로 읽음)
+fn sha256_file(path: &Path) -> Result<String, String> {
+    let mut reader = fs::File::open(path)
+        .map_err(|e| format!("미디어 파일 읽기 실패: {}", e))?;
+    let mut hasher = Sha256::new();
+    let mut buf = vec![0u8; COPY_CHUNK_SIZE];
+    loop {
+        let read = reader.read(&mut buf)
+            .map_err(|e| format!("미디어 파일 읽기 실패: {}", e))?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buf[..read]);
+    }
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+// 중복 확인용 미디어 내용 해시 (media_files와 같은 순서)
+// 크기가 같은 다른 파일이 있을 때만 해시를 계산하고, 나머지는 None
+fn media_content_hashes(app: &tauri::AppHandle, media_files: &[MediaBuildInfo]) -> Result<Vec<Option<String>>, String> {
+    let sizes: Vec<Option<u64>> = media_files.iter()
+        .map(|m| fs::metadata(&m.file_path).ok().filter(|m| m.is_file()).map(|m| m.len()))
+        .collect();
+    let mut size_counts: HashMap<u64, usize> = HashMap::new();
+    for size in sizes.iter().flatten() {
+        *size_counts.entry(*size).or_default() += 1;
+    }
+
+    media_files.iter().zip(&sizes)
+        .map(|(media_info, size)| {
+            build_progress::check_cancelled(app)?;
+            match size {
+                Some(size) if size_counts[size] > 1 => {
+                    build_progress::stage(app, BuildStage::EmbeddingMedia, format!("중복 미디어 확인 중: {}", media_info.name));
+                    sha256_file(Path::new(&media_info.file_path))
+                        .map(Some)
+                        .map_err(|e| format!("{} ({})", e, media_info.id))
+                }
+                _ => Ok(None),
+            }
+        })
+        .collect()
+}
+
+// 비디오 하나의 포스터 프레임 (임시 JPEG 파일)
+struct PosterFrame {
+    info: MediaBuildInfo,
+    video_id: String,
+}
+
+// 빌드 중 추출한 포스터 프레임 (임시 폴더는 빌드가 끝나거나 실패하면 삭제)
+struct PosterFrames {
+    dir: PathBuf,
+    posters: Vec<PosterFrame>,
+}
+
+impl Drop for PosterFrames {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.dir);
+    }
+}
+
+// 비디오마다 포스터 프레임 추출
+// ffmpeg가 없으면 포스터 없이, 추출에 실패한 비디오는 그 비디오만 포스터 없이 빌드를 계속함
+fn extract_poster_frames(
+    app: &tauri::AppHandle,
+    media_files: &[MediaBuildInfo],
+    content_hashes: &[Option<String>],
+) -> Result<PosterFrames, String> {
+    let mut frames = PosterFrames {
+        dir: env::temp_dir().join(format!("tutorial_posters_{}", std::process::id())),
+        posters: Vec::new(),
+    };
+
+    let videos: Vec<_> = media_files.iter().zip(content_hashes)
+        .filter(|(m, _)| poster_frame::is_video(&m.mime_type) && Path::new(&m.file_path).is_file())
+        .collect();
+    if videos.is_empty() {
+        return Ok(frames);
+    }
+
+    let Some(ffmpeg) = poster_frame::find_ffmpeg(app) else {
+        build_progress::stage(app, BuildStage::EmbeddingMedia, "ffmpeg를 찾을 수 없어 비디오 포스터 없이 빌드합니다.");
+        return Ok(frames);
+    };
+    fs::create_dir_all(&frames.dir)
+        .map_err(|e| format!("포스터 임시 폴더 생성 실패: {}", e))?;
+
+    // 내용이 같은 비디오는 한 번만 추출
+    let mut extracted: HashMap<&str, String> = HashMap::new();
+    for (media_info, content_hash) in videos {
+        build_progress::check_cancelled(app)?;
+
+        let existing = content_hash.as_deref().and_then(|hash| extracted.get(hash)).cloned();
+        let file_path = match existing {
+            Some(file_path) => file_path,
+            None => {
+                build_progress::stage(app, BuildStage::EmbeddingMedia, format!("포스터 프레임 추출 중: {}", media_info.name));
+
+                let poster_path = frames.dir.join(format!("{}.jpg", frames.posters.len()));
+                if let Err(e) = poster_frame::extract_poster(&ffmpeg, Path::new(&media_info.file_path), &poster_path) {
+                    build_progress::stage(app, BuildStage::EmbeddingMedia, format!("{} ({}, 포스터 없이 계속)", e, media_info.name));
+                    continue;
+                }
+                let file_path = poster_path.to_string_lossy().into_owned();
+                if let Some(hash) = content_hash {
+                    extracted.insert(hash, file_path.clone());
+                }
+                file_path
+            }
+        };
+
+        frames.posters.push(PosterFrame {
+            info: MediaBuildInfo {
+                id: poster_frame::poster_id(&media_info.id),
+                name: format!("{}.poster.jpg", media_info.name),
+                mime_type: poster_frame::POSTER_MIME_TYPE.to_string(),
+                file_path,
+            },
+            video_id: media_info.id.clone(),
+        });
+    }
+
+    Ok(frames)
 }
 
 // V2: 바이너리 데이터를 exe에 append
 fn append_binary_data_v2(
+    app: &tauri::AppHandle,
     exe_path: &Path,
-    project_json: &str,
-    media_files: &[MediaBuildInfo],
-) -> Result<(), String> {
+    contents: &PayloadContents,
+) -> Result<PayloadBuildReport, String> {
+    let PayloadContents { project_json, media_files, verify_on_startup, encryption_key, app_icon, license, signer } = *contents;
+
+    // 압축이 효과가 없을 때 기록한 엔트리를 잘라낼 수 있도록 append 대신 쓰기 모드로 열고 끝으로 이동
     let mut file = fs::OpenOptions::new()
-        .append(true)
+        .write(true)
         .open(exe_path)
         .map_err(|e| format!("exe 파일 열기 실패: {}", e))?;
 
@@ -757,38 +1483,159 @@ fn append_binary_data_v2(
         media: Vec::new(),
         app_icon_offset: None,
         app_icon_size: None,
+        app_icon_sha256: None,
+        project_json_sha256: None,
+        verify_on_startup,
+        project_json_encryption: None,
+        project_json_nonce: None,
+        encryption_key_id: encryption_key.map(|key| key.key_id().to_string()),
+        license: None,
+        signature: None,
     };
 
     let mut current_offset = data_start;
+    let mut report = PayloadBuildReport::default();
+
+    // 내장할 미디어: (빌드 정보, 내용 해시, 포스터라면 그 비디오 ID)
+    // 포스터 프레임은 프로젝트 미디어 뒤에 같은 방식(압축/암호화/중복 제거)으로 기록
+    let content_hashes = media_content_hashes(app, media_files)?;
+    let poster_frames = extract_poster_frames(app, media_files, &content_hashes)?;
+    let mut sources: Vec<(&MediaBuildInfo, Option<String>, Option<&str>)> = media_files.iter()
+        .zip(content_hashes)
+        .map(|(media_info, content_hash)| (media_info, content_hash, None))
+        .collect();
+    for poster in &poster_frames.posters {
+        let content_hash = sha256_file(Path::new(&poster.info.file_path))?;
+        sources.push((&poster.info, Some(content_hash), Some(poster.video_id.as_str())));
+    }
 
-    // 1. 미디어 파일들을 바이너리로 append
-    for media_info in media_files {
-        let media_path = PathBuf::from(&media_info.file_path);
-        if media_path.exists() {
-            let media_data = fs::read(&media_path)
-                .map_err(|e| format!("미디어 파일 읽기 실패 ({}): {}", media_info.id, e))?;
-
-            let media_size = media_data.len() as u64;
+    // 진행률 계산용 전체 크기 (원본 미디어 + 프로젝트 JSON)
+    let bytes_total = sources.iter()
+        .filter_map(|(m, _, _)| fs::metadata(&m.file_path).ok())
+        .map(|m| m.len())
+        .sum::<u64>()
+        + project_json.len() as u64;
+    let mut bytes_processed = 0u64;
+
+    // 내용 해시 → 그 내용을 처음 기록한 매니페스트 엔트리 위치
+    let mut stored_by_content: HashMap<&str, usize> = HashMap::new();
+
+    // 1. 미디어 파일들을 바이너리로 append (압축 가능한 타입은 압축)
+    //    파일은 고정 크기 버퍼로 읽으며 기록하고, 오프셋은 실제로 기록한 바이트 수로 계산
+    //    내용이 같은 파일은 한 번만 기록하고 나머지 엔트리는 같은 위치를 가리킴
+    //    (읽을 수 없는 미디어는 check_media_files에서 걸러지므로 여기서 열지 못하면 빌드 실패)
+    for (media_info, content_hash, poster_for) in &sources {
+        build_progress::check_cancelled(app)?;
+
+        let stored_index = content_hash.as_deref().and_then(|hash| stored_by_content.get(hash));
+        if let Some(&index) = stored_index {
+            let stored = &manifest.media[index];
+            let shared_from = stored.stored_id().to_string();
+            let entry = MediaManifestEntry {
+                id: media_info.id.clone(),
+                name: media_info.name.clone(),
+                mime_type: media_info.mime_type.clone(),
+                shared_from: Some(shared_from),
+                poster_for: poster_for.map(str::to_string),
+                ..stored.clone()
+            };
+
+            report.deduplicated_count += 1;
+            report.deduplicated_bytes += entry.size;
+            bytes_processed += entry.content_size();
+            manifest.media.push(entry);
+        } else {
+            build_progress::report(app, BuildProgress::new(
+                BuildStage::EmbeddingMedia,
+                format!("미디어 내장 중: {}", media_info.name),
+            )
+            .with_bytes(bytes_processed, bytes_total)
+            .with_media(&media_info.id));
+
+            let mut progress = (bytes_processed, bytes_total);
+            let size = fs::metadata(&media_info.file_path).map(|m| m.len()).unwrap_or(u64::MAX);
+            let method = compression::choose_compression(&media_info.mime_type, size);
+            let mut stored = write_media_entry(app, &mut file, media_info, method, encryption_key, &mut progress)?;
+
+            // 압축 결과가 원본보다 작지 않거나 (빌드 중 파일이 커져) 제품에서 풀 수 없는 크기면
+            // 기록한 부분을 잘라내고 원본 그대로 다시 기록
+            let too_large = stored.original_size > compression::MAX_DECOMPRESSED_SIZE;
+            if method.is_some() && (stored.plain_size >= stored.original_size || too_large) {
+                file.set_len(current_offset)
+                    .and_then(|_| file.seek(SeekFrom::Start(current_offset)))
+                    .map_err(|e| format!("미디어 데이터 되돌리기 실패: {}", e))?;
+                progress = (bytes_processed, bytes_total);
+                stored = write_media_entry(app, &mut file, media_info, None, encryption_key, &mut progress)?;
+            }
+            bytes_processed = progress.0;
 
-            file.write_all(&media_data)
-                .map_err(|e| format!("미디어 데이터 쓰기 실패: {}", e))?;
+            report.original_bytes += stored.original_size;
+            report.stored_bytes += stored.size;
+            if stored.compression.is_some() {
+                report.compressed_count += 1;
+            }
+            if stored.nonce.is_some() {
+                report.encrypted_count += 1;
+            }
 
+            let encryption = stored.nonce.as_ref().map(|_| PayloadEncryption::Aes256GcmChunked);
             manifest.media.push(MediaManifestEntry {
                 id: media_info.id.clone(),
                 name: media_info.name.clone(),
                 mime_type: media_info.mime_type.clone(),
                 offset: current_offset,
-                size: media_size,
+                size: stored.size,
+                sha256: Some(stored.sha256),
+                compression: stored.compression,
+                original_size: (stored.compression.is_some() || encryption.is_some()).then_some(stored.original_size),
+                encryption,
+                nonce: stored.nonce,
+                shared_from: None,
+                poster_for: poster_for.map(str::to_string),
             });
+            if let Some(hash) = content_hash {
+                stored_by_content.insert(hash, manifest.media.len() - 1);
+            }
 
-            current_offset += media_size;
+            current_offset += stored.size;
         }
     }
 
+    report.poster_count = manifest.media.iter().filter(|m| m.poster_for.is_some()).count();
+
+    // 앱 아이콘 PNG append (창이 뜨기 전에 읽으므로 압축/암호화하지 않음)
+    if let Some(icon) = app_icon {
+        file.write_all(icon)
+            .map_err(|e| format!("앱 아이콘 쓰기 실패: {}", e))?;
+        manifest.app_icon_offset = Some(current_offset);
+        manifest.app_icon_size = Some(icon.len() as u64);
+        manifest.app_icon_sha256 = Some(sha256_hex(icon));
+        current_offset += icon.len() as u64;
+    }
+
     // 2. project.json append
-    let project_bytes = project_json.as_bytes();
+    build_progress::check_cancelled(app)?;
+    build_progress::stage(app, BuildStage::EmbeddingProject, "프로젝트 데이터 쓰는 중...");
+
+    let project_bytes = match encryption_key {
+        Some(key) => {
+            let nonce = payload_crypto::new_nonce();
+            let sealed = payload_crypto::encrypt(key, &nonce, PROJECT_JSON_ENTRY_ID, project_json.as_bytes())?;
+            manifest.project_json_encryption = Some(PayloadEncryption::Aes256GcmChunked);
+            manifest.project_json_nonce = Some(nonce);
+            sealed
+        }
+        None => project_json.as_bytes().to_vec(),
+    };
+    let project_bytes = project_bytes.as_slice();
     manifest.project_json_offset = current_offset;
     manifest.project_json_size = project_bytes.len() as u64;
+    manifest.project_json_sha256 = Some(sha256_hex(project_bytes));
+    manifest.license = license
+        .map(|issuer| issuer.issue(manifest.project_json_sha256.as_deref()))
+        .transpose()?;
+    // 해시와 라이선스까지 채운 뒤 서명
+    manifest.signature = signer.map(|signer| signer.sign(&manifest)).transpose()?;
 
     file.write_all(project_bytes)
         .map_err(|e| format!("프로젝트 데이터 쓰기 실패: {}", e))?;
@@ -810,113 +1657,267 @@ fn append_binary_data_v2(
     file.write_all(MAGIC_BYTES_V2)
         .map_err(|e| format!("매직 바이트 쓰기 실패: {}", e))?;
 
-    Ok(())
-}
-
-// V2: exe 파일에서 매니페스트 읽기
-fn read_manifest_from_exe(exe_path: &Path) -> Result<BuildManifest, String> {
-    let mut file = fs::File::open(exe_path)
-        .map_err(|e| format!("exe 파일 열기 실패: {}", e))?;
-
-    // 매직 바이트 V2 확인
-    let magic_len = MAGIC_BYTES_V2.len() as i64;
-    file.seek(SeekFrom::End(-magic_len))
-        .map_err(|e| format!("파일 탐색 실패: {}", e))?;
+    build_progress::report(app, BuildProgress::new(BuildStage::EmbeddingProject, "프로젝트 데이터 내장 완료")
+        .with_bytes(bytes_total, bytes_total));
 
-    let mut magic_buf = vec![0u8; MAGIC_BYTES_V2.len()];
-    file.read_exact(&mut magic_buf)
-        .map_err(|e| format!("매직 바이트 읽기 실패: {}", e))?;
+    Ok(report)
+}
 
-    if magic_buf != MAGIC_BYTES_V2 {
-        return Err("V2 데이터 포맷이 아닙니다.".to_string());
+// V2: 내장 데이터 무결성 검증 (exe_path가 없으면 현재 실행 파일 검증)
+#[tauri::command]
+fn verify_embedded_payload(exe_path: Option<String>) -> Result<IntegrityReport, String> {
+    match exe_path {
+        Some(path) => Payload::open(&product_target::payload_path(Path::new(&path)))?.verify(true),
+        None => Payload::current()?.verify(true),
     }
+}
 
-    // 매니페스트 크기 읽기
-    file.seek(SeekFrom::End(-magic_len - 8))
-        .map_err(|e| format!("파일 탐색 실패: {}", e))?;
+// 제품 exe의 서명과 모든 엔트리 해시 확인 (public_key가 없으면 컴파일된 공개 키 사용)
+#[tauri::command]
+fn verify_payload_signature(exe_path: String, public_key: Option<String>) -> Result<SignatureReport, String> {
+    let payload = Payload::open(&product_target::payload_path(Path::new(&exe_path)))?;
+    payload_signature::verify_payload(&payload, public_key.as_deref())
+}
 
-    let mut len_buf = [0u8; 8];
-    file.read_exact(&mut len_buf)
-        .map_err(|e| format!("매니페스트 크기 읽기 실패: {}", e))?;
+// 이 컴퓨터의 기기 ID (기기 제한 라이선스 발급용)
+#[tauri::command]
+fn get_machine_id() -> Result<String, String> {
+    license::machine_id()
+}
 
-    let manifest_size = u64::from_le_bytes(len_buf);
+// 진행 중인 빌드 취소 (실행 중인 npm 빌드는 종료되고 불완전한 출력 파일은 삭제됨)
+#[tauri::command]
+fn cancel_build(app: tauri::AppHandle) {
+    build_progress::cancel(&app);
+}
 
-    // 매니페스트 읽기
-    file.seek(SeekFrom::End(-magic_len - 8 - manifest_size as i64))
-        .map_err(|e| format!("파일 탐색 실패: {}", e))?;
+// 빌더: 관리자 PIN을 프로젝트에 저장할 해시로 변환
+#[tauri::command]
+fn hash_exit_pin(pin: String) -> Result<String, String> {
+    exit_guard::hash_pin(&pin)
+}
 
-    let mut manifest_buf = vec![0u8; manifest_size as usize];
-    file.read_exact(&mut manifest_buf)
-        .map_err(|e| format!("매니페스트 읽기 실패: {}", e))?;
+// 제품: 시작 시 등록한 종료 키/PIN 설정 (빌더면 null)
+#[tauri::command]
+fn get_exit_settings(app: tauri::AppHandle) -> Option<exit_guard::ExitSettings> {
+    exit_guard::settings(&app)
+}
 
-    let manifest: BuildManifest = serde_json::from_slice(&manifest_buf)
-        .map_err(|e| format!("매니페스트 파싱 실패: {}", e))?;
+// 제품: 메인 창의 키 입력 (등록된 종료 키면 종료 확인 요청)
+#[tauri::command]
+fn exit_key_pressed(app: tauri::AppHandle, key: String) -> Result<(), String> {
+    exit_guard::key_pressed(&app, &key)
+}
+
+// 제품: 관리자 PIN을 확인하고 종료 (PIN이 없는 제품은 바로 종료)
+#[tauri::command]
+fn request_exit(app: tauri::AppHandle, pin: Option<String>) -> Result<(), String> {
+    exit_guard::request_exit(&app, pin.as_deref())
+}
 
-    Ok(manifest)
+// 제품 exe에서 편집 가능한 .tutorial 프로젝트 복원
+// 암호화/라이선스 빌드는 작성자의 키 파일(복호화 키, 발급자 서명 키)이 있어야 복원
+#[tauri::command]
+fn extract_tutorial_from_exe(
+    exe_path: String,
+    output_path: String,
+    key_file: Option<String>,
+    license_key_file: Option<String>,
+) -> Result<tutorial_extract::ExtractSummary, String> {
+    // 복원은 빌더 기능 (제품으로 다른 제품의 내용을 꺼내지 않도록)
+    if Payload::current().is_ok() {
+        return Err("제품에서는 튜토리얼을 복원할 수 없습니다. Tutorial Maker에서 실행해주세요.".to_string());
+    }
+    let payload_file = product_target::payload_path(Path::new(&exe_path));
+    let keys = payload_access::ExtractKeys {
+        key_file: key_file.as_deref().map(Path::new),
+        license_key_file: license_key_file.as_deref().map(Path::new),
+    };
+    tutorial_extract::extract_exe_to_tutorial(&payload_file, Path::new(&output_path), keys)
 }
 
 // V2: exe 파일에서 프로젝트 JSON 읽기
+// 서명/무결성/라이선스는 시작 시 한 번 확인하고(payload_access) 실패했으면 그 사유를 반환
 #[tauri::command]
-fn read_project_file_v2() -> Result<String, String> {
-    let current_exe = std::env::current_exe().map_err(|e| e.to_string())?;
+fn read_project_file_v2(app: tauri::AppHandle) -> Result<String, String> {
+    read_project_file(app)
+}
 
-    // V2 매니페스트 읽기 시도
-    match read_manifest_from_exe(&current_exe) {
-        Ok(manifest) => {
-            let mut file = fs::File::open(&current_exe)
-                .map_err(|e| format!("exe 파일 열기 실패: {}", e))?;
+// V2: exe 파일에서 미디어 데이터 읽기
+#[tauri::command]
+fn read_embedded_media(app: tauri::AppHandle, media_id: String) -> Result<Vec<u8>, String> {
+    let payload = payload_access::payload(&app)?;
+    let media_entry = payload.find_media(&media_id)?;
 
-            file.seek(SeekFrom::Start(manifest.project_json_offset))
-                .map_err(|e| format!("파일 탐색 실패: {}", e))?;
+    payload.read_media(media_entry)
+}
 
-            let mut project_buf = vec![0u8; manifest.project_json_size as usize];
-            file.read_exact(&mut project_buf)
-                .map_err(|e| format!("프로젝트 데이터 읽기 실패: {}", e))?;
+// V2: 미디어 매니페스트 가져오기
+#[tauri::command]
+fn get_media_manifest(app: tauri::AppHandle) -> Result<String, String> {
+    let manifest = payload_access::payload(&app)?.manifest()?;
 
-            String::from_utf8(project_buf)
-                .map_err(|e| format!("프로젝트 데이터 디코딩 실패: {}", e))
-        }
-        Err(_) => {
-            // V2 실패시 V1 또는 파일 시도
-            read_project_file()
+    serde_json::to_string(&manifest.media)
+        .map_err(|e| format!("매니페스트 직렬화 실패: {}", e))
+}
+
+// 미디어 스트리밍 URI 스킴 (tutorial-media://localhost/<media_id>)
+const MEDIA_PROTOCOL: &str = "tutorial-media";
+// Range 끝이 지정되지 않은 요청에 한 번에 응답할 최대 크기
+const MEDIA_CHUNK_SIZE: u64 = 1024 * 1024;
+// Range 없는 요청에 한 번에 응답하는 최대 크기 (이미지 등, 넘으면 첫 청크만 응답)
+const MEDIA_FULL_RESPONSE_LIMIT: u64 = 32 * 1024 * 1024;
+
+// Range 헤더 파싱 ("bytes=start-end", "bytes=start-", "bytes=-suffix")
+// 반환값: (시작, 끝) - 끝은 포함(inclusive)
+fn parse_range_header(range: &str, total_size: u64) -> Option<(u64, u64)> {
+    let spec = range.trim().strip_prefix("bytes=")?;
+    // 다중 범위는 지원하지 않으므로 첫 번째 범위만 사용
+    let spec = spec.split(',').next()?.trim();
+    let (start_str, end_str) = spec.split_once('-')?;
+
+    if total_size == 0 {
+        return None;
+    }
+
+    let (start, end) = if start_str.is_empty() {
+        // suffix 범위: 마지막 N 바이트
+        let suffix: u64 = end_str.parse().ok()?;
+        if suffix == 0 {
+            return None;
         }
+        (total_size.saturating_sub(suffix), total_size - 1)
+    } else {
+        let start: u64 = start_str.parse().ok()?;
+        let end = if end_str.is_empty() {
+            // 끝이 없으면 청크 크기만큼만 응답 (브라우저가 이어서 요청함)
+            start.saturating_add(MEDIA_CHUNK_SIZE - 1)
+        } else {
+            end_str.parse().ok()?
+        };
+        (start, end.min(total_size - 1))
+    };
+
+    if start > end {
+        return None;
     }
-}
 
-// V2: exe 파일에서 미디어 데이터 읽기
-#[tauri::command]
-fn read_embedded_media(media_id: String) -> Result<Vec<u8>, String> {
-    let current_exe = std::env::current_exe().map_err(|e| e.to_string())?;
+    Some((start, end))
+}
 
-    let manifest = read_manifest_from_exe(&current_exe)?;
+// Range 없는 요청에 전체 데이터로 응답할 수 있는지
+// 비디오/오디오는 플레이어가 Range로 이어서 요청하므로 첫 청크만 응답
+fn fits_full_response(mime_type: &str, size: u64) -> bool {
+    let streamed = mime_type.starts_with("video/") || mime_type.starts_with("audio/");
+    size <= if streamed { MEDIA_CHUNK_SIZE } else { MEDIA_FULL_RESPONSE_LIMIT }
+}
 
-    // 해당 미디어 찾기
-    let media_entry = manifest.media.iter()
-        .find(|m| m.id == media_id)
-        .ok_or_else(|| format!("미디어를 찾을 수 없습니다: {}", media_id))?;
+// URI 경로의 퍼센트 인코딩 해제 (잘못된 인코딩이나 UTF-8이 아니면 None)
+fn percent_decode(value: &str) -> Option<String> {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = std::str::from_utf8(bytes.get(i + 1..i + 3)?).ok()?;
+            decoded.push(u8::from_str_radix(hex, 16).ok()?);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(decoded).ok()
+}
 
-    let mut file = fs::File::open(&current_exe)
-        .map_err(|e| format!("exe 파일 열기 실패: {}", e))?;
+// 미디어 스트리밍 요청 처리
+fn handle_media_request(
+    app: &tauri::AppHandle,
+    request: &tauri::http::Request<Vec<u8>>,
+) -> tauri::http::Response<Vec<u8>> {
+    use tauri::http::{header, Response, StatusCode};
+
+    let error_response = |status: StatusCode, message: String| {
+        Response::builder()
+            .status(status)
+            .header(header::CONTENT_TYPE, "text/plain; charset=utf-8")
+            .header(header::ACCESS_CONTROL_ALLOW_ORIGIN, "*")
+            .body(message.into_bytes())
+            .unwrap()
+    };
 
-    file.seek(SeekFrom::Start(media_entry.offset))
-        .map_err(|e| format!("파일 탐색 실패: {}", e))?;
+    // convertFileSrc가 미디어 ID를 퍼센트 인코딩하므로 디코딩해서 찾음
+    let Some(media_id) = percent_decode(request.uri().path().trim_start_matches('/')) else {
+        return error_response(StatusCode::BAD_REQUEST, "미디어 ID 형식이 잘못되었습니다.".to_string());
+    };
+    if media_id.is_empty() {
+        return error_response(StatusCode::BAD_REQUEST, "미디어 ID가 없습니다.".to_string());
+    }
 
-    let mut media_buf = vec![0u8; media_entry.size as usize];
-    file.read_exact(&mut media_buf)
-        .map_err(|e| format!("미디어 데이터 읽기 실패: {}", e))?;
+    let payload = match Payload::current() {
+        Ok(_) => match payload_access::payload(app) {
+            Ok(payload) => payload,
+            Err(e) => return error_response(StatusCode::FORBIDDEN, e),
+        },
+        Err(e) => return error_response(StatusCode::NOT_FOUND, e),
+    };
 
-    Ok(media_buf)
-}
+    let entry = match payload.find_media(&media_id) {
+        Ok(entry) => entry,
+        Err(e) => return error_response(StatusCode::NOT_FOUND, e),
+    };
 
-// V2: 미디어 매니페스트 가져오기
-#[tauri::command]
-fn get_media_manifest() -> Result<String, String> {
-    let current_exe = std::env::current_exe().map_err(|e| e.to_string())?;
+    let range_header = request
+        .headers()
+        .get(header::RANGE)
+        .and_then(|value| value.to_str().ok());
+
+    // Range 요청이 없으면 이미지와 작은 파일은 전체 데이터로 응답 (<img>는 이어서 요청하지 않음)
+    // 그 외에는 첫 청크만 206으로 응답하고 나머지는 Range로 요청하게 함
+    let range_header = match range_header {
+        Some(range_header) => range_header,
+        None if !fits_full_response(&entry.mime_type, entry.content_size()) => "bytes=0-",
+        None => {
+            let data = if entry.content_size() == 0 {
+                Ok(Vec::new())
+            } else {
+                payload.read_media_range(entry, 0, entry.content_size() - 1)
+            };
+            return match data {
+                Ok(data) => Response::builder()
+                    .status(StatusCode::OK)
+                    .header(header::CONTENT_TYPE, &entry.mime_type)
+                    .header(header::CONTENT_LENGTH, data.len())
+                    .header(header::ACCEPT_RANGES, "bytes")
+                    .header(header::ACCESS_CONTROL_ALLOW_ORIGIN, "*")
+                    .body(data)
+                    .unwrap(),
+                Err(e) => error_response(StatusCode::INTERNAL_SERVER_ERROR, e),
+            };
+        }
+    };
 
-    let manifest = read_manifest_from_exe(&current_exe)?;
+    let Some((start, end)) = parse_range_header(range_header, entry.content_size()) else {
+        return Response::builder()
+            .status(StatusCode::RANGE_NOT_SATISFIABLE)
+            .header(header::CONTENT_RANGE, format!("bytes */{}", entry.content_size()))
+            .header(header::ACCESS_CONTROL_ALLOW_ORIGIN, "*")
+            .body(Vec::new())
+            .unwrap();
+    };
 
-    serde_json::to_string(&manifest.media)
-        .map_err(|e| format!("매니페스트 직렬화 실패: {}", e))
+    match payload.read_media_range(entry, start, end) {
+        Ok(data) => Response::builder()
+            .status(StatusCode::PARTIAL_CONTENT)
+            .header(header::CONTENT_TYPE, &entry.mime_type)
+            .header(header::CONTENT_LENGTH, data.len())
+            .header(header::CONTENT_RANGE, format!("bytes {}-{}/{}", start, end, entry.content_size()))
+            .header(header::ACCEPT_RANGES, "bytes")
+            .header(header::ACCESS_CONTROL_ALLOW_ORIGIN, "*")
+            .body(data)
+            .unwrap(),
+        Err(e) => error_response(StatusCode::INTERNAL_SERVER_ERROR, e),
+    }
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
@@ -926,6 +1927,14 @@ pub fn run() {
     .plugin(tauri_plugin_shell::init())
     .plugin(tauri_plugin_fs::init())
     .plugin(tauri_plugin_cli::init())
+    .manage(build_progress::BuildState::default())
+    // 내장 미디어를 IPC 대신 Range 요청으로 스트리밍 (대용량 비디오 탐색 지원)
+    .register_asynchronous_uri_scheme_protocol(MEDIA_PROTOCOL, |ctx, request, responder| {
+      let app = ctx.app_handle().clone();
+      std::thread::spawn(move || {
+        responder.respond(handle_media_request(&app, &request));
+      });
+    })
     .setup(|app| {
       if cfg!(debug_assertions) {
         app.handle().plugin(
@@ -934,9 +1943,31 @@ pub fn run() {
             .build(),
         )?;
       }
+
+      // `build`/`extract`/`machine-id`/`verify` 서브커맨드로 실행되면 창 없이 해당 작업만 수행
+      // (제품은 `machine-id`만 처리하고 빌더 전용 서브커맨드는 무시)
+      if cli_build::has_subcommand(app) {
+        cli_build::handle_build_subcommand(app);
+        cli_build::handle_extract_subcommand(app);
+        cli_build::handle_machine_id_subcommand(app);
+        cli_build::handle_verify_subcommand(app);
+        return Ok(());
+      }
+
+      // 제품이면 내장 데이터의 서명/무결성/라이선스를 한 번 확인해 두고 모든 데이터 요청에 적용
+      app.manage(payload_access::PayloadAccess::check());
+
+      // 제품이면 내장된 프로젝트의 종료 키/PIN을 등록하고, 창 설정과 앱 아이콘을 적용해 메인 창 생성
+      // (제품인데 사용 허가가 없거나 프로젝트를 읽지 못하면 종료 보호 없이 일반 창처럼 닫을 수 있음)
+      let project = product_startup::embedded_project(app.handle());
+      app.manage(exit_guard::ExitGuard::new(project.as_ref().map(|p| p.as_ref().map(|p| &p.settings))));
+      product_startup::create_main_window(app, project.as_ref().and_then(|p| p.as_ref().ok()))?;
+
       Ok(())
     })
-    .invoke_handler(tauri::generate_handler![build_project, get_temp_path, build_standalone_executable, build_standalone_executable_v2, read_project_file, read_project_file_v2, get_media_path, read_media_file, read_embedded_media, get_media_manifest]);
+    // 종료 보호가 켜진 제품은 Alt+F4/닫기 버튼 대신 PIN 입력 요청
+    .on_window_event(exit_guard::handle_window_event)
+    .invoke_handler(tauri::generate_handler![build_project, get_temp_path, build_standalone_executable, build_standalone_executable_v2, read_project_file, read_project_file_v2, get_media_path, read_media_file, read_embedded_media, get_media_manifest, verify_embedded_payload, extract_tutorial_from_exe, update_product_payload, cancel_build, get_machine_id, verify_payload_signature, hash_exit_pin, get_exit_settings, exit_key_pressed, request_exit]);
 
   #[cfg(debug_assertions)]
   {
@@ -947,3 +1978,49 @@ pub fn run() {
     .run(tauri::generate_context!())
     .expect("error while running tauri application");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_range_header() {
+        assert_eq!(parse_range_header("bytes=0-99", 1000), Some((0, 99)));
+        // 끝이 전체 크기를 넘으면 마지막 바이트까지
+        assert_eq!(parse_range_header("bytes=900-2000", 1000), Some((900, 999)));
+        // 끝이 없으면 청크 크기만큼
+        assert_eq!(
+            parse_range_header("bytes=10-", 10 * MEDIA_CHUNK_SIZE),
+            Some((10, 10 + MEDIA_CHUNK_SIZE - 1))
+        );
+        assert_eq!(parse_range_header("bytes=10-", 100), Some((10, 99)));
+        // suffix 범위
+        assert_eq!(parse_range_header("bytes=-100", 1000), Some((900, 999)));
+        assert_eq!(parse_range_header("bytes=-5000", 1000), Some((0, 999)));
+        // 다중 범위는 첫 번째만
+        assert_eq!(parse_range_header(" bytes=0-9, 20-29", 1000), Some((0, 9)));
+    }
+
+    #[test]
+    fn rejects_invalid_range_header() {
+        assert_eq!(parse_range_header("items=0-9", 1000), None);
+        assert_eq!(parse_range_header("bytes=abc", 1000), None);
+        assert_eq!(parse_range_header("bytes=a-9", 1000), None);
+        assert_eq!(parse_range_header("bytes=-0", 1000), None);
+        assert_eq!(parse_range_header("bytes=50-10", 1000), None);
+        // 시작이 전체 크기를 넘음
+        assert_eq!(parse_range_header("bytes=1000-", 1000), None);
+        assert_eq!(parse_range_header("bytes=0-9", 0), None);
+    }
+
+    #[test]
+    fn decodes_percent_encoding() {
+        assert_eq!(percent_decode("media-1").as_deref(), Some("media-1"));
+        assert_eq!(percent_decode("a%20b").as_deref(), Some("a b"));
+        assert_eq!(percent_decode("%ED%95%9C").as_deref(), Some("한"));
+        // 잘린 인코딩, 16진수가 아닌 값, UTF-8이 아닌 바이트
+        assert_eq!(percent_decode("a%2"), None);
+        assert_eq!(percent_decode("%zz"), None);
+        assert_eq!(percent_decode("%FF"), None);
+    }
+}
@@ -0,0 +1,472 @@
+// 기간 제한/기기 제한 빌드 (라이선스 블록)
+//
+// 발급자 Ed25519 키로 사용 조건(LicenseTerms)을 서명해 매니페스트에 넣고, 제품은
+// 프로젝트를 열기 전에 서명, 만료 시각, 기기 ID를 확인한다.
+// 조건에는 프로젝트 JSON 해시가 포함되어 다른 빌드로 옮겨 붙일 수 없다.
+// 템플릿 빌드 시 TUTORIAL_LICENSE_ISSUER_KEY(발급자 공개 키 hex)를 컴파일해 넣으면
+// 그 키로 서명된 라이선스만 인정하고, 라이선스가 없는 데이터도 거부한다.
+// 발급자 키가 컴파일되지 않은 실행 파일은 라이선스 빌드를 열지 않으며(매니페스트의 공개 키는
+// 신뢰하지 않음), 빌드도 템플릿에 발급자 키가 없으면 라이선스를 넣지 않는다.
+
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use ed25519_dalek::{Signer, SigningKey, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::payload::BuildManifest;
+use crate::payload_crypto::to_hex;
+use crate::payload_signature;
+
+// 템플릿에 컴파일해 넣는 신뢰할 발급자 공개 키 (hex)
+const TRUSTED_ISSUER_KEY: Option<&str> = option_env!("TUTORIAL_LICENSE_ISSUER_KEY");
+
+// 서명 대상 사용 조건
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LicenseTerms {
+    pub issuer: String,
+    // 발급/만료 시각 (Unix 초)
+    pub issued_at: u64,
+    #[serde(default)]
+    pub expires_at: Option<u64>,
+    // 비어 있으면 모든 기기에서 실행 가능
+    #[serde(default)]
+    pub allowed_machine_ids: Vec<String>,
+    // 이 라이선스가 발급된 빌드의 프로젝트 JSON 해시
+    pub project_json_sha256: Option<String>,
+}
+
+// 매니페스트에 들어가는 서명된 라이선스 블록
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LicenseBlock {
+    #[serde(flatten)]
+    pub terms: LicenseTerms,
+    // 발급자 공개 키와 조건에 대한 서명 (hex)
+    pub public_key: String,
+    pub signature: String,
+}
+
+// 빌드 요청의 라이선스 옵션 (프론트엔드/CLI에서 전달)
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LicenseOptions {
+    pub issuer: String,
+    #[serde(default)]
+    pub expires_at: Option<u64>,
+    #[serde(default)]
+    pub allowed_machine_ids: Vec<String>,
+    // 발급자 Ed25519 서명 키 파일 (64자리 hex 시드)
+    pub signing_key_file: String,
+}
+
+// 서명 키를 읽어 둔 라이선스 발급자
+pub struct LicenseIssuer {
+    options: LicenseOptions,
+    key: SigningKey,
+}
+
+impl LicenseIssuer {
+    pub fn new(options: &LicenseOptions) -> Result<LicenseIssuer, String> {
+        if options.issuer.trim().is_empty() {
+            return Err("라이선스 발급자 이름이 필요합니다.".to_string());
+        }
+        let key = payload_signature::load_signing_key(Path::new(&options.signing_key_file))?;
+        Ok(LicenseIssuer { options: options.clone(), key })
+    }
+
+    // 제품 템플릿에 컴파일해 넣을 공개 키 (hex)
+    pub fn public_key_hex(&self) -> String {
+        to_hex(self.key.verifying_key().as_bytes())
+    }
+
+    // 프로젝트 JSON 해시에 묶인 라이선스 블록 발급
+    pub fn issue(&self, project_json_sha256: Option<&str>) -> Result<LicenseBlock, String> {
+        let terms = LicenseTerms {
+            issuer: self.options.issuer.trim().to_string(),
+            issued_at: now_unix(),
+            expires_at: self.options.expires_at,
+            allowed_machine_ids: self.options.allowed_machine_ids.iter()
+                .map(|id| normalize_machine_id(id))
+                .collect(),
+            project_json_sha256: project_json_sha256.map(str::to_string),
+        };
+        let message = serde_json::to_vec(&terms)
+            .map_err(|e| format!("라이선스 직렬화 실패: {}", e))?;
+
+        Ok(LicenseBlock {
+            terms,
+            public_key: self.public_key_hex(),
+            signature: to_hex(&self.key.sign(&message).to_bytes()),
+        })
+    }
+}
+
+// 제품 실행 전 라이선스 확인 (문제가 있으면 에러 화면에 표시할 메시지)
+pub fn check_license(manifest: &BuildManifest) -> Result<(), String> {
+    let trusted_key = TRUSTED_ISSUER_KEY.map(payload_signature::parse_verifying_key).transpose()
+        .map_err(|e| format!("템플릿에 설정된 발급자 키가 잘못되었습니다: {}", e))?;
+    check_license_with(manifest, trusted_key.as_ref())
+}
+
+// 컴파일된 발급자 키로 라이선스 확인 (키가 없으면 라이선스 빌드를 열지 않음)
+// 매니페스트의 public_key는 누구나 바꿔 넣을 수 있으므로 서명 확인에 쓰지 않는다.
+fn check_license_with(manifest: &BuildManifest, trusted_key: Option<&VerifyingKey>) -> Result<(), String> {
+    let (license, trusted_key) = match (&manifest.license, trusted_key) {
+        (None, None) => return Ok(()),
+        (None, Some(_)) => {
+            return Err("라이선스가 없는 튜토리얼입니다. 발급된 빌드만 실행할 수 있습니다.".to_string());
+        }
+        (Some(_), None) => {
+            return Err("라이선스를 확인할 수 없는 실행 파일입니다. 발급자 키(TUTORIAL_LICENSE_ISSUER_KEY)로 빌드한 템플릿이 필요합니다.".to_string());
+        }
+        (Some(license), Some(trusted_key)) => (license, trusted_key),
+    };
+
+    verify_license_block(manifest, license, trusted_key)?;
+
+    let terms = &license.terms;
+    if let Some(expires_at) = terms.expires_at {
+        if now_unix() >= expires_at {
+            return Err(format!(
+                "사용 기간이 만료된 튜토리얼입니다. (만료일: {}, 발급: {})",
+                format_date(expires_at),
+                terms.issuer
+            ));
+        }
+    }
+
+    if !terms.allowed_machine_ids.is_empty() {
+        let machine_id = machine_id()?;
+        if !terms.allowed_machine_ids.contains(&machine_id) {
+            return Err(format!(
+                "이 컴퓨터에서는 실행할 수 없는 튜토리얼입니다. 발급자({})에게 기기 ID를 전달해주세요.\n기기 ID: {}",
+                terms.issuer, machine_id
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+// 라이선스 서명과 이 빌드(프로젝트 JSON)에 발급된 것인지 확인
+fn verify_license_block(manifest: &BuildManifest, license: &LicenseBlock, public_key: &VerifyingKey) -> Result<(), String> {
+    let signature = payload_signature::parse_signature(&license.signature)?;
+    let message = serde_json::to_vec(&license.terms)
+        .map_err(|e| format!("라이선스 직렬화 실패: {}", e))?;
+    public_key.verify_strict(&message, &signature)
+        .map_err(|_| "라이선스 서명이 올바르지 않습니다. 튜토리얼이 변조되었을 수 있습니다.".to_string())?;
+
+    if license.terms.project_json_sha256 != manifest.project_json_sha256 {
+        return Err("이 튜토리얼에 발급된 라이선스가 아닙니다.".to_string());
+    }
+    Ok(())
+}
+
+// 제품 내용을 꺼내려는 쪽이 라이선스 발급자인지 확인 (추출 시 사용 조건 대신)
+// 발급자는 만료되었거나 다른 기기에 묶인 빌드도 복원할 수 있다.
+pub fn check_issuer(manifest: &BuildManifest, signing_key_file: &Path) -> Result<(), String> {
+    let Some(license) = &manifest.license else {
+        return Ok(());
+    };
+    let key = payload_signature::load_signing_key(signing_key_file)?;
+    verify_license_block(manifest, license, &key.verifying_key())
+        .map_err(|e| format!("라이선스 발급자의 서명 키가 아닙니다: {}", e))
+}
+
+// 제품 템플릿에 이 발급자 공개 키가 컴파일되어 있는지 (TUTORIAL_LICENSE_ISSUER_KEY 값 검색)
+// 발급자 키가 없는 템플릿으로 만든 라이선스 제품은 실행되지 않으므로 빌드 전에 확인한다.
+pub fn template_trusts_issuer(template: &Path, public_key_hex: &str) -> Result<bool, String> {
+    // .app 번들이면 Contents/MacOS 안의 실행 파일
+    let files: Vec<PathBuf> = if template.is_dir() {
+        fs::read_dir(template.join("Contents").join("MacOS"))
+            .map_err(|e| format!("템플릿 번들 읽기 실패: {}", e))?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.is_file())
+            .collect()
+    } else {
+        vec![template.to_path_buf()]
+    };
+
+    let needles = [public_key_hex.to_ascii_lowercase(), public_key_hex.to_ascii_uppercase()];
+    for file in files {
+        if file_contains(&file, &needles)? {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+// 파일에 문자열이 들어 있는지 (큰 템플릿도 버퍼 단위로 읽어 검색)
+fn file_contains(path: &Path, needles: &[String]) -> Result<bool, String> {
+    let mut file = fs::File::open(path)
+        .map_err(|e| format!("템플릿 읽기 실패 ({}): {}", path.display(), e))?;
+    // 버퍼 경계에 걸친 문자열도 찾도록 앞 버퍼의 끝부분을 남겨 둠
+    let overlap = needles.iter().map(String::len).max().unwrap_or(0).saturating_sub(1);
+    let mut window = Vec::new();
+    let mut buf = vec![0u8; 1024 * 1024];
+
+    loop {
+        let read = file.read(&mut buf)
+            .map_err(|e| format!("템플릿 읽기 실패 ({}): {}", path.display(), e))?;
+        if read == 0 {
+            return Ok(false);
+        }
+        window.extend_from_slice(&buf[..read]);
+        if needles.iter().any(|needle| window.windows(needle.len()).any(|w| w == needle.as_bytes())) {
+            return Ok(true);
+        }
+        window.drain(..window.len() - window.len().min(overlap));
+    }
+}
+
+fn now_unix() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+fn normalize_machine_id(id: &str) -> String {
+    id.trim().to_ascii_lowercase()
+}
+
+// 이 컴퓨터의 기기 ID (OS 기기 식별자의 해시, 원본 값은 노출하지 않음)
+pub fn machine_id() -> Result<String, String> {
+    let raw = raw_machine_id()
+        .ok_or_else(|| "이 컴퓨터의 기기 ID를 확인할 수 없습니다.".to_string())?;
+    let digest = Sha256::digest(format!("tutorial-maker:{}", raw.trim()).as_bytes());
+    Ok(to_hex(&digest[..8]))
+}
+
+#[cfg(target_os = "linux")]
+fn raw_machine_id() -> Option<String> {
+    ["/etc/machine-id", "/var/lib/dbus/machine-id"]
+        .iter()
+        .filter_map(|path| std::fs::read_to_string(path).ok())
+        .find(|id| !id.trim().is_empty())
+}
+
+#[cfg(target_os = "macos")]
+fn raw_machine_id() -> Option<String> {
+    let output = std::process::Command::new("ioreg")
+        .args(["-rd1", "-c", "IOPlatformExpertDevice"])
+        .output()
+        .ok()?;
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .find(|line| line.contains("IOPlatformUUID"))
+        .and_then(|line| line.split('"').nth(3))
+        .map(str::to_string)
+}
+
+#[cfg(windows)]
+fn raw_machine_id() -> Option<String> {
+    use std::os::windows::process::CommandExt;
+    // 콘솔 창이 잠깐 뜨지 않도록 CREATE_NO_WINDOW
+    const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+    let output = std::process::Command::new("reg")
+        .args(["query", r"HKLM\SOFTWARE\Microsoft\Cryptography", "/v", "MachineGuid"])
+        .creation_flags(CREATE_NO_WINDOW)
+        .output()
+        .ok()?;
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .find(|line| line.contains("MachineGuid"))
+        .and_then(|line| line.split_whitespace().last())
+        .map(str::to_string)
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", windows)))]
+fn raw_machine_id() -> Option<String> {
+    None
+}
+
+// "YYYY-MM-DD"(UTC 0시) 또는 Unix 초를 Unix 초로 변환 (CLI --expires)
+pub fn parse_expiry(value: &str) -> Result<u64, String> {
+    let value = value.trim();
+    if let Ok(timestamp) = value.parse::<u64>() {
+        return Ok(timestamp);
+    }
+
+    let invalid = || format!("만료일 형식이 잘못되었습니다: {} (YYYY-MM-DD 또는 Unix 초)", value);
+    let parts: Vec<u32> = value.split('-')
+        .map(|p| p.parse::<u32>().map_err(|_| invalid()))
+        .collect::<Result<_, _>>()?;
+    let [year, month, day] = parts[..] else {
+        return Err(invalid());
+    };
+    if !(1970..=9999).contains(&year) || !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return Err(invalid());
+    }
+
+    Ok(days_from_civil(year, month, day) * 86_400)
+}
+
+// 1970-01-01부터의 일 수 (proleptic Gregorian)
+fn days_from_civil(year: u32, month: u32, day: u32) -> u64 {
+    let year = u64::from(if month <= 2 { year - 1 } else { year });
+    let era = year / 400;
+    let year_of_era = year - era * 400;
+    let month = month as u64;
+    let day_of_year = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day as u64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+// Unix 초 → "YYYY-MM-DD" (UTC)
+pub fn format_date(timestamp: u64) -> String {
+    let days = timestamp / 86_400 + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days - era * 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issuer(expires_at: Option<u64>, allowed_machine_ids: Vec<String>) -> LicenseIssuer {
+        LicenseIssuer {
+            options: LicenseOptions {
+                issuer: " 교육팀 ".to_string(),
+                expires_at,
+                allowed_machine_ids,
+                signing_key_file: String::new(),
+            },
+            key: SigningKey::from_bytes(&[9; 32]),
+        }
+    }
+
+    fn licensed_manifest(issuer: &LicenseIssuer) -> BuildManifest {
+        let mut manifest: BuildManifest = serde_json::from_str(
+            r#"{"project_json_offset":0,"project_json_size":0,"media":[],"project_json_sha256":"abc"}"#,
+        ).unwrap();
+        manifest.license = Some(issuer.issue(Some("abc")).unwrap());
+        manifest
+    }
+
+    fn trusted(issuer: &LicenseIssuer) -> VerifyingKey {
+        issuer.key.verifying_key()
+    }
+
+    #[test]
+    fn accepts_issued_license() {
+        let issuer = issuer(Some(now_unix() + 3600), Vec::new());
+        let manifest = licensed_manifest(&issuer);
+        assert_eq!(manifest.license.as_ref().unwrap().terms.issuer, "교육팀");
+        assert!(check_license_with(&manifest, Some(&trusted(&issuer))).is_ok());
+
+        // 직렬화를 거쳐도 서명 대상이 같아야 함
+        let json = serde_json::to_string(&manifest).unwrap();
+        assert!(check_license_with(&serde_json::from_str(&json).unwrap(), Some(&trusted(&issuer))).is_ok());
+
+        // 라이선스를 쓰지 않는 템플릿의 라이선스 없는 빌드
+        let mut unlicensed = manifest;
+        unlicensed.license = None;
+        assert!(check_license_with(&unlicensed, None).is_ok());
+    }
+
+    #[test]
+    fn fails_closed_without_trusted_issuer() {
+        let issuer = issuer(None, Vec::new());
+        let manifest = licensed_manifest(&issuer);
+
+        // 발급자 키가 컴파일되지 않은 실행 파일은 매니페스트의 공개 키를 믿지 않음
+        assert!(check_license_with(&manifest, None).unwrap_err().contains("TUTORIAL_LICENSE_ISSUER_KEY"));
+
+        // 발급자 키가 있으면 라이선스 없는 데이터는 거부
+        let mut unlicensed = manifest;
+        unlicensed.license = None;
+        assert!(check_license_with(&unlicensed, Some(&trusted(&issuer))).unwrap_err().contains("라이선스가 없는"));
+    }
+
+    #[test]
+    fn rejects_tampered_or_foreign_license() {
+        let issuer = issuer(None, Vec::new());
+        let trusted_key = trusted(&issuer);
+        let manifest = licensed_manifest(&issuer);
+
+        let mut tampered = manifest.clone();
+        tampered.license.as_mut().unwrap().terms.expires_at = Some(u64::MAX);
+        assert!(check_license_with(&tampered, Some(&trusted_key)).is_err());
+
+        // 다른 발급자가 자기 공개 키를 넣어 서명한 라이선스
+        let other = LicenseIssuer { key: SigningKey::from_bytes(&[8; 32]), ..issuer };
+        let foreign = licensed_manifest(&other);
+        assert!(check_license_with(&foreign, Some(&trusted(&other))).is_ok());
+        assert!(check_license_with(&foreign, Some(&trusted_key)).unwrap_err().contains("서명이 올바르지 않습니다"));
+
+        let mut bad_signature = manifest.clone();
+        bad_signature.license.as_mut().unwrap().signature = "00".repeat(10);
+        assert!(check_license_with(&bad_signature, Some(&trusted_key)).unwrap_err().contains("서명 형식"));
+
+        // 다른 빌드(프로젝트 JSON)로 옮겨 붙인 라이선스
+        let mut moved = manifest;
+        moved.project_json_sha256 = Some("def".to_string());
+        assert!(check_license_with(&moved, Some(&trusted_key)).is_err());
+    }
+
+    #[test]
+    fn rejects_expired_license() {
+        let issuer = issuer(Some(now_unix() - 1), Vec::new());
+        let manifest = licensed_manifest(&issuer);
+        assert!(check_license_with(&manifest, Some(&trusted(&issuer))).unwrap_err().contains("만료"));
+    }
+
+    #[test]
+    fn rejects_other_machines() {
+        let issuer = issuer(None, vec!["0000000000000000".to_string()]);
+        let manifest = licensed_manifest(&issuer);
+        assert!(check_license_with(&manifest, Some(&trusted(&issuer))).is_err());
+    }
+
+    #[test]
+    fn finds_issuer_key_in_template() {
+        let dir = std::env::temp_dir().join(format!("tutorial_license_template_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let public_key = issuer(None, Vec::new()).public_key_hex();
+
+        // 읽기 버퍼 경계에 걸친 키도 찾음
+        let template = dir.join("product-template");
+        let mut bytes = vec![0u8; 1024 * 1024 - 10];
+        bytes.extend_from_slice(public_key.to_ascii_uppercase().as_bytes());
+        bytes.extend_from_slice(b"tail");
+        fs::write(&template, &bytes).unwrap();
+        assert!(template_trusts_issuer(&template, &public_key).unwrap());
+
+        let other = LicenseIssuer { key: SigningKey::from_bytes(&[8; 32]), ..issuer(None, Vec::new()) };
+        assert!(!template_trusts_issuer(&template, &other.public_key_hex()).unwrap());
+
+        // .app 번들은 Contents/MacOS 안의 실행 파일에서 찾음
+        let bundle = dir.join("product-template.app");
+        fs::create_dir_all(bundle.join("Contents").join("MacOS")).unwrap();
+        fs::write(bundle.join("Contents").join("MacOS").join("app"), format!("key={};", public_key)).unwrap();
+        assert!(template_trusts_issuer(&bundle, &public_key).unwrap());
+        assert!(!template_trusts_issuer(&bundle, &other.public_key_hex()).unwrap());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn parses_and_formats_dates() {
+        assert_eq!(parse_expiry("1970-01-01").unwrap(), 0);
+        assert_eq!(parse_expiry("2024-02-29").unwrap(), 1_709_164_800);
+        assert_eq!(parse_expiry(" 1700000000 ").unwrap(), 1_700_000_000);
+        assert_eq!(format_date(1_709_164_800), "2024-02-29");
+        assert_eq!(format_date(parse_expiry("2099-12-31").unwrap() + 86_399), "2099-12-31");
+
+        for invalid in ["", "2024-13-01", "2024-01-32", "1969-12-31", "2024/01/01", "2024-01"] {
+            assert!(parse_expiry(invalid).is_err(), "{}", invalid);
+        }
+    }
+}
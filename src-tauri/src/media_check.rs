@@ -0,0 +1,121 @@
+// 빌드에 넣을 미디어 파일 확인
+//
+// 파일이 없거나 읽을 수 없는 미디어를 건너뛰고 빌드하면 빈 페이지가 있는 제품이 배포되므로,
+// 내장하기 전에 모두 찾아 어느 페이지에서 쓰는지와 함께 알린다.
+// 엄격 모드에서는 빌드를 중단하고, 아니면 빌드 결과에 목록을 담아 돌려준다.
+
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::project_model::Project;
+
+// 빌드에 넣지 못한 미디어
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MissingMedia {
+    pub id: String,
+    pub name: String,
+    pub file_path: String,
+    // 없음 / 읽기 실패 사유
+    pub reason: String,
+    // 이 미디어를 쓰는 페이지 ("페이지 N (제목)", 프로젝트를 읽을 수 없으면 비어 있음)
+    pub pages: Vec<String>,
+}
+
+// 미디어 파일을 읽을 수 있는지 확인하고, 읽을 수 없으면 사유와 함께 반환
+pub fn check_media(project: Option<&Project>, id: &str, name: &str, file_path: &str) -> Option<MissingMedia> {
+    let reason = unreadable_reason(Path::new(file_path))?;
+    Some(MissingMedia {
+        id: id.to_string(),
+        name: name.to_string(),
+        file_path: file_path.to_string(),
+        reason,
+        pages: project.map(|p| p.pages_using_media(id)).unwrap_or_default(),
+    })
+}
+
+fn unreadable_reason(path: &Path) -> Option<String> {
+    if path.as_os_str().is_empty() {
+        return Some("파일 경로가 없습니다.".to_string());
+    }
+    match fs::metadata(path) {
+        Err(e) if e.kind() == ErrorKind::NotFound => Some("파일이 없습니다.".to_string()),
+        Err(e) => Some(format!("파일 정보를 읽을 수 없습니다: {}", e)),
+        Ok(metadata) if !metadata.is_file() => Some("파일이 아닙니다.".to_string()),
+        Ok(_) => fs::File::open(path).err().map(|e| format!("파일을 열 수 없습니다: {}", e)),
+    }
+}
+
+// 경고/오류 메시지용 한 줄 설명
+pub fn describe(media: &MissingMedia) -> String {
+    let pages = if media.pages.is_empty() {
+        String::new()
+    } else {
+        format!(" - 사용하는 페이지: {}", media.pages.join(", "))
+    };
+    format!("{} ({}): {} [{}]{}", media.name, media.id, media.reason, media.file_path, pages)
+}
+
+// 엄격 모드 오류 메시지
+pub fn format_missing_media(missing: &[MissingMedia]) -> String {
+    let lines: Vec<String> = missing.iter().map(|m| format!("  - {}", describe(m))).collect();
+    format!("미디어 파일 {}개를 읽을 수 없습니다:\n{}", missing.len(), lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROJECT_JSON: &str = r#"{
+        "id": "p1",
+        "name": "안내",
+        "pages": [
+            { "id": "page-1", "mediaType": "video", "mediaId": "intro", "playType": "single" },
+            { "id": "page-2", "title": "마무리", "mediaType": "video", "mediaId": "intro", "playType": "loop" }
+        ]
+    }"#;
+
+    #[test]
+    fn readable_file_is_not_missing() {
+        let path = std::env::temp_dir().join(format!("media_check_{}.bin", std::process::id()));
+        fs::write(&path, b"media").unwrap();
+        let missing = check_media(None, "intro", "intro.mp4", &path.to_string_lossy());
+        let _ = fs::remove_file(&path);
+        assert!(missing.is_none());
+    }
+
+    #[test]
+    fn reports_missing_file_with_pages() {
+        let project = Project::parse(PROJECT_JSON).unwrap();
+        let path = std::env::temp_dir().join(format!("media_check_missing_{}.mp4", std::process::id()));
+        let missing = check_media(Some(&project), "intro", "intro.mp4", &path.to_string_lossy()).unwrap();
+        assert_eq!(missing.reason, "파일이 없습니다.");
+        assert_eq!(missing.pages, ["페이지 1", "페이지 2 (마무리)"]);
+        assert!(describe(&missing).ends_with(" - 사용하는 페이지: 페이지 1, 페이지 2 (마무리)"));
+
+        // 프로젝트를 읽을 수 없으면 페이지 없이 알림
+        let missing = check_media(None, "intro", "intro.mp4", &path.to_string_lossy()).unwrap();
+        assert!(missing.pages.is_empty());
+        assert!(!describe(&missing).contains("사용하는 페이지"));
+    }
+
+    #[test]
+    fn reports_empty_path_and_directory() {
+        let missing = check_media(None, "a", "a.png", "").unwrap();
+        assert_eq!(missing.reason, "파일 경로가 없습니다.");
+
+        let dir = std::env::temp_dir();
+        let missing = check_media(None, "b", "b.png", &dir.to_string_lossy()).unwrap();
+        assert_eq!(missing.reason, "파일이 아닙니다.");
+
+        let message = format_missing_media(&[
+            check_media(None, "a", "a.png", "").unwrap(),
+            missing,
+        ]);
+        assert!(message.starts_with("미디어 파일 2개를 읽을 수 없습니다:\n  - a.png (a): "));
+        assert_eq!(message.lines().count(), 3);
+    }
+}
@@ -0,0 +1,1108 @@
+// exe 끝에 내장된 튜토리얼 데이터(페이로드) 읽기
+//
+// 모든 포맷은 [본문][본문 크기: 8바이트 LE][매직 바이트] 트레일러로 끝난다.
+//   V1: 본문 = 프로젝트 JSON
+//   V2: 본문 = 빌드 매니페스트 JSON (미디어/프로젝트 JSON은 그 앞에 위치)
+// 매직 바이트 끝의 버전 번호로 포맷을 판별하므로, 새 포맷은 PayloadFormat에
+// variant를 추가하고 Payload::open에서 본문을 해석하면 된다.
+// 암호화된 엔트리는 복호화 → 압축 해제 순서로 읽는다. (payload_crypto 참고)
+
+use std::fs;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::compression::{self, MediaCompression};
+use crate::license::LicenseBlock;
+use crate::payload_crypto::{self, PayloadEncryption, PayloadKey};
+use crate::payload_signature::PayloadSignature;
+
+// 매직 바이트: exe 끝에 데이터가 있는지 확인하는 마커
+pub const MAGIC_BYTES: &[u8] = b"TUTORIALMAKER_DATA_V1";
+// 새 버전 매직 바이트 (바이너리 미디어 포함)
+pub const MAGIC_BYTES_V2: &[u8] = b"TUTORIALMAKER_DATA_V2";
+// 버전 번호를 제외한 매직 바이트 공통 접두사
+const MAGIC_PREFIX: &[u8] = b"TUTORIALMAKER_DATA_V";
+// 트레일러의 본문 크기 필드 (u64 little endian)
+const LENGTH_FIELD_SIZE: u64 = 8;
+
+// macOS .app 번들에서 페이로드를 담는 리소스 파일 (Contents/Resources 아래)
+pub const BUNDLE_PAYLOAD_RESOURCE: &str = "tutorial.payload";
+
+// 무결성 검증 시 프로젝트 JSON을 가리키는 ID
+pub const PROJECT_JSON_ENTRY_ID: &str = "project.json";
+
+// 무결성 검증 시 앱 아이콘을 가리키는 ID
+pub const APP_ICON_ENTRY_ID: &str = "app-icon";
+
+// 미디어 매니페스트 엔트리
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MediaManifestEntry {
+    pub id: String,
+    pub name: String,
+    pub mime_type: String,
+    pub offset: u64,
+    pub size: u64,
+    // 무결성 검증용 SHA-256 (hex, exe에 저장된 바이트 기준, 이전 빌드에는 없음)
+    #[serde(default)]
+    pub sha256: Option<String>,
+    // 압축 방식 (None이면 원본 그대로 저장됨, size는 저장된 크기)
+    #[serde(default)]
+    pub compression: Option<MediaCompression>,
+    // 압축 해제(복호화) 후 크기
+    #[serde(default)]
+    pub original_size: Option<u64>,
+    // 암호화 방식 (None이면 평문, 압축된 데이터를 암호화함)
+    #[serde(default)]
+    pub encryption: Option<PayloadEncryption>,
+    // 엔트리별 nonce (hex)
+    #[serde(default)]
+    pub nonce: Option<String>,
+    // 내용이 같아 다른 엔트리의 저장 데이터를 함께 쓰는 경우 그 엔트리 ID
+    #[serde(default)]
+    pub shared_from: Option<String>,
+    // 빌드 시 추출한 포스터 프레임이면 그 비디오 엔트리 ID
+    #[serde(default)]
+    pub poster_for: Option<String>,
+}
+
+impl MediaManifestEntry {
+    // 재생 시 사용되는 실제 미디어 크기
+    pub fn content_size(&self) -> u64 {
+        self.original_size.unwrap_or(self.size)
+    }
+
+    // 저장 데이터를 기록한 엔트리 ID (암호화 AAD로 사용)
+    pub fn stored_id(&self) -> &str {
+        self.shared_from.as_deref().unwrap_or(&self.id)
+    }
+}
+
+// 빌드 매니페스트
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BuildManifest {
+    pub project_json_offset: u64,
+    pub project_json_size: u64,
+    pub media: Vec<MediaManifestEntry>,
+    // 제품 창/작업 표시줄 아이콘 PNG (평문 저장)
+    pub app_icon_offset: Option<u64>,
+    pub app_icon_size: Option<u64>,
+    #[serde(default)]
+    pub app_icon_sha256: Option<String>,
+    #[serde(default)]
+    pub project_json_sha256: Option<String>,
+    // 시작 시 모든 미디어 해시까지 검증할지 여부
+    #[serde(default)]
+    pub verify_on_startup: bool,
+    #[serde(default)]
+    pub project_json_encryption: Option<PayloadEncryption>,
+    #[serde(default)]
+    pub project_json_nonce: Option<String>,
+    // 암호화에 사용한 키 식별자 (키가 맞는지 확인용)
+    #[serde(default)]
+    pub encryption_key_id: Option<String>,
+    // 기간/기기 제한 빌드의 서명된 라이선스
+    #[serde(default)]
+    pub license: Option<LicenseBlock>,
+    // 매니페스트(해시 포함)에 대한 Ed25519 서명 (payload_signature 참고)
+    #[serde(default)]
+    pub signature: Option<PayloadSignature>,
+}
+
+impl BuildManifest {
+    // 암호화된 엔트리가 하나라도 있는지
+    pub fn is_encrypted(&self) -> bool {
+        self.project_json_encryption.is_some() || self.media.iter().any(|m| m.encryption.is_some())
+    }
+}
+
+// 무결성 검증에 실패한 엔트리
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CorruptEntry {
+    pub id: String,
+    pub name: String,
+    pub reason: String,
+}
+
+// 페이로드 무결성 검증 결과
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IntegrityReport {
+    pub verified_count: usize,
+    pub unchecked_count: usize,
+    pub corrupt: Vec<CorruptEntry>,
+}
+
+// 손상된 엔트리 목록을 에러 메시지로 변환
+pub fn format_corrupt_entries(corrupt: &[CorruptEntry]) -> String {
+    let lines = corrupt.iter()
+        .map(|c| format!("  - {} ({}): {}", c.name, c.id, c.reason))
+        .collect::<Vec<_>>()
+        .join("\n");
+    format!("내장 데이터가 손상되었습니다:\n{}", lines)
+}
+
+// SHA-256 해시를 hex 문자열로 계산
+pub fn sha256_hex(data: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(data))
+}
+
+// 내장 데이터 포맷 버전
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PayloadFormat {
+    // 프로젝트 JSON만 내장 (미디어는 Base64로 JSON 안에 포함)
+    V1,
+    // 미디어 바이너리 + 프로젝트 JSON + 매니페스트
+    V2,
+}
+
+// 매니페스트 JSON 최대 크기 (손상된 길이 필드로 거대한 버퍼를 할당하지 않도록)
+const MAX_MANIFEST_SIZE: u64 = 64 * 1024 * 1024;
+
+// 페이로드를 읽을 수 있는 소스 (exe 파일, 퍼징 시에는 메모리 버퍼)
+pub trait PayloadSource: Read + Seek + Send {}
+
+impl<T: Read + Seek + Send> PayloadSource for T {}
+
+// exe 하나에 대한 내장 데이터 리더 (파일은 한 번만 열어 재사용)
+pub struct Payload {
+    source: Mutex<Box<dyn PayloadSource>>,
+    // 트레일러(본문 크기 + 매직 바이트) 시작 위치, 모든 데이터는 이 앞에 있어야 함
+    data_end: u64,
+    // 본문(V1 프로젝트 JSON / V2 매니페스트) 시작 위치
+    body_offset: u64,
+    format: PayloadFormat,
+    project_offset: u64,
+    project_size: u64,
+    manifest: Option<BuildManifest>,
+    // 암호화된 엔트리 복호화 키 (찾지 못했으면 그 이유)
+    key: Result<PayloadKey, String>,
+    // Range 요청마다 다시 풀지 않도록 최근에 압축 해제한 엔트리 보관
+    decompressed: Mutex<DecompressedCache>,
+    // 미디어를 처음 읽을 때 해시를 확인할지 (서명이 확인된 제품)
+    verify_media_on_read: AtomicBool,
+    // 저장 구간(offset, size)별 실제 해시 (같은 데이터를 공유하는 엔트리도 한 번만 계산)
+    range_hashes: Mutex<HashMap<(u64, u64), RangeHash>>,
+}
+
+// 한 번만 계산되는 구간 해시 (동시에 요청되면 먼저 계산하는 쪽을 기다림)
+type RangeHash = Arc<OnceLock<Result<String, String>>>;
+
+// 압축 해제한 엔트리 캐시 (저장 ID 기준, 오래된 것부터 버림)
+#[derive(Default)]
+struct DecompressedCache {
+    entries: VecDeque<(String, Arc<Vec<u8>>)>,
+    bytes: u64,
+}
+
+// 압축 대상은 이미지뿐이므로 최근 몇 개만 보관하면 충분함
+// (압축 해제 최대 크기보다 커야 어떤 압축 엔트리도 Range 요청마다 다시 풀지 않음)
+const DECOMPRESSED_CACHE_LIMIT: u64 = 2 * compression::MAX_DECOMPRESSED_SIZE;
+
+impl DecompressedCache {
+    fn get(&mut self, stored_id: &str) -> Option<Arc<Vec<u8>>> {
+        let index = self.entries.iter().position(|(id, _)| id == stored_id)?;
+        let entry = self.entries.remove(index)?;
+        let data = entry.1.clone();
+        self.entries.push_back(entry);
+        Some(data)
+    }
+
+    fn insert(&mut self, stored_id: &str, data: Arc<Vec<u8>>) {
+        let size = data.len() as u64;
+        if size > DECOMPRESSED_CACHE_LIMIT {
+            return;
+        }
+        while self.bytes + size > DECOMPRESSED_CACHE_LIMIT {
+            let Some((_, evicted)) = self.entries.pop_front() else {
+                break;
+            };
+            self.bytes -= evicted.len() as u64;
+        }
+        self.entries.push_back((stored_id.to_string(), data));
+        self.bytes += size;
+    }
+}
+
+impl Payload {
+    // exe를 열어 트레일러를 해석
+    pub fn open(exe_path: &Path) -> Result<Payload, String> {
+        let file = fs::File::open(exe_path)
+            .map_err(|e| format!("exe 파일 열기 실패: {}", e))?;
+
+        let file_size = file.metadata()
+            .map_err(|e| format!("파일 메타데이터 읽기 실패: {}", e))?
+            .len();
+
+        let mut payload = Payload::from_source(Box::new(file), file_size)?;
+
+        // 암호화된 빌드면 컴파일된 키나 라이선스 파일에서 복호화 키를 찾음
+        // (키가 없어도 검증/데이터 제거는 가능하므로 읽을 때 에러로 보고)
+        if payload.manifest.as_ref().is_some_and(BuildManifest::is_encrypted) {
+            payload.key = payload_crypto::find_runtime_key(exe_path);
+        }
+
+        Ok(payload)
+    }
+
+    // 임의의 소스에서 트레일러를 해석
+    // 길이/오프셋 필드는 모두 신뢰할 수 없는 값으로 보고 파일 크기와 대조한다.
+    pub fn from_source(mut source: Box<dyn PayloadSource>, file_size: u64) -> Result<Payload, String> {
+        // 매직 바이트 확인 (모든 버전의 길이가 같음)
+        let magic_len = MAGIC_BYTES_V2.len() as u64;
+        let Some(data_end) = file_size.checked_sub(magic_len + LENGTH_FIELD_SIZE) else {
+            return Err("내장된 프로젝트 데이터를 찾을 수 없습니다.".to_string());
+        };
+
+        source.seek(SeekFrom::Start(data_end + LENGTH_FIELD_SIZE))
+            .map_err(|e| format!("파일 탐색 실패: {}", e))?;
+
+        let mut magic_buf = vec![0u8; magic_len as usize];
+        source.read_exact(&mut magic_buf)
+            .map_err(|e| format!("매직 바이트 읽기 실패: {}", e))?;
+
+        let Some(version) = magic_buf.strip_prefix(MAGIC_PREFIX) else {
+            return Err("내장된 프로젝트 데이터를 찾을 수 없습니다.".to_string());
+        };
+        let format = match version {
+            b"1" => PayloadFormat::V1,
+            b"2" => PayloadFormat::V2,
+            other => {
+                return Err(format!(
+                    "지원하지 않는 데이터 포맷입니다 (V{}). 최신 버전의 Tutorial Maker가 필요합니다.",
+                    String::from_utf8_lossy(other)
+                ))
+            }
+        };
+
+        // 본문 크기 읽기 (매직 바이트 앞 8바이트)
+        source.seek(SeekFrom::Start(data_end))
+            .map_err(|e| format!("파일 탐색 실패: {}", e))?;
+
+        let mut len_buf = [0u8; LENGTH_FIELD_SIZE as usize];
+        source.read_exact(&mut len_buf)
+            .map_err(|e| format!("데이터 길이 읽기 실패: {}", e))?;
+
+        let body_size = u64::from_le_bytes(len_buf);
+        let Some(body_offset) = data_end.checked_sub(body_size) else {
+            return Err(format!(
+                "내장 데이터 길이가 잘못되었습니다 (본문 {} 바이트, 파일 크기 {} 바이트)",
+                body_size, file_size
+            ));
+        };
+
+        let mut payload = Payload {
+            source: Mutex::new(source),
+            data_end,
+            body_offset,
+            format,
+            project_offset: body_offset,
+            project_size: body_size,
+            manifest: None,
+            key: Err("복호화 키가 설정되지 않았습니다.".to_string()),
+            decompressed: Mutex::new(DecompressedCache::default()),
+            verify_media_on_read: AtomicBool::new(false),
+            range_hashes: Mutex::new(HashMap::new()),
+        };
+
+        if format == PayloadFormat::V2 {
+            if body_size > MAX_MANIFEST_SIZE {
+                return Err(format!(
+                    "매니페스트 크기가 비정상적입니다 ({} 바이트, 최대 {} 바이트)",
+                    body_size, MAX_MANIFEST_SIZE
+                ));
+            }
+
+            let manifest_buf = payload.read_at(body_offset, body_size)
+                .map_err(|e| format!("매니페스트 읽기 실패: {}", e))?;
+            let manifest: BuildManifest = serde_json::from_slice(&manifest_buf)
+                .map_err(|e| format!("매니페스트 파싱 실패: {}", e))?;
+
+            // 프로젝트 JSON은 매니페스트 앞에 있어야 함 (미디어 범위는 읽을 때/검증 시 확인)
+            check_range(manifest.project_json_offset, manifest.project_json_size, body_offset)
+                .map_err(|e| format!("프로젝트 데이터 위치가 잘못되었습니다: {}", e))?;
+
+            payload.project_offset = manifest.project_json_offset;
+            payload.project_size = manifest.project_json_size;
+            payload.manifest = Some(manifest);
+        }
+
+        Ok(payload)
+    }
+
+    // 현재 실행 파일의 페이로드 (프로세스 동안 한 번만 읽어 캐시)
+    pub fn current() -> Result<&'static Payload, String> {
+        static CURRENT: OnceLock<Result<Payload, String>> = OnceLock::new();
+
+        CURRENT
+            .get_or_init(|| {
+                Payload::open(&current_payload_path()?)
+            })
+            .as_ref()
+            .map_err(|e| e.clone())
+    }
+
+    // 복호화 키 직접 지정 (빌더에서 키 파일을 넘겨받은 경우)
+    pub fn set_key(&mut self, key: PayloadKey) {
+        self.key = Ok(key);
+    }
+
+    // 매니페스트의 키 식별자와 일치하는 복호화 키
+    fn key(&self) -> Result<&PayloadKey, String> {
+        let key = self.key.as_ref().map_err(|e| e.clone())?;
+        let expected = self.manifest.as_ref().and_then(|m| m.encryption_key_id.as_deref());
+        if expected.is_some_and(|id| id != key.key_id()) {
+            return Err("복호화 키가 이 튜토리얼과 맞지 않습니다. 라이선스 파일을 확인해주세요.".to_string());
+        }
+        Ok(key)
+    }
+
+    // 암호화된 엔트리 전체 복호화
+    fn decrypt_entry(&self, id: &str, nonce: Option<&str>, data: &[u8]) -> Result<Vec<u8>, String> {
+        let nonce = nonce.ok_or_else(|| format!("암호화 nonce가 없습니다 ({})", id))?;
+        payload_crypto::decrypt(self.key()?, nonce, id, data)
+    }
+
+    // 이후 미디어를 처음 읽을 때마다 해시 확인 (시작 시 전체 미디어를 해시하지 않음)
+    pub fn verify_media_on_read(&self) {
+        self.verify_media_on_read.store(true, Ordering::SeqCst);
+    }
+
+    // 미디어를 읽기 전 해시 확인 (verify_media_on_read가 켜진 경우만, 구간마다 한 번)
+    fn check_media(&self, entry: &MediaManifestEntry) -> Result<(), String> {
+        if !self.verify_media_on_read.load(Ordering::SeqCst) {
+            return Ok(());
+        }
+        match self.verify_entry(entry.offset, entry.size, entry.sha256.as_deref()) {
+            Ok(true) => Ok(()),
+            Ok(false) => Err(format!("해시가 없는 미디어는 읽을 수 없습니다 ({})", entry.id)),
+            Err(reason) => Err(format!("미디어 데이터가 손상되었습니다 ({}): {}", entry.id, reason)),
+        }
+    }
+
+    pub fn format(&self) -> PayloadFormat {
+        self.format
+    }
+
+    // V2 매니페스트 (V1이면 에러)
+    pub fn manifest(&self) -> Result<&BuildManifest, String> {
+        self.manifest.as_ref()
+            .ok_or_else(|| "V2 데이터 포맷이 아닙니다.".to_string())
+    }
+
+    // 내장 데이터가 시작되는 위치 (이 앞은 원본 exe)
+    pub fn payload_start(&self) -> Result<u64, String> {
+        let Some(manifest) = &self.manifest else {
+            return Ok(self.body_offset);
+        };
+
+        // 빌드 시 프로젝트 JSON 바로 뒤에 매니페스트를 기록하므로, 그렇지 않으면
+        // 오프셋을 신뢰할 수 없어 원본 exe 경계를 알 수 없음
+        if manifest.project_json_offset + manifest.project_json_size != self.body_offset {
+            return Err("내장 데이터 구조가 예상과 달라 원본 exe 경계를 찾을 수 없습니다.".to_string());
+        }
+
+        let media_offsets = manifest.media.iter().map(|m| m.offset);
+        let start = media_offsets
+            .chain(manifest.app_icon_offset)
+            .fold(manifest.project_json_offset, u64::min);
+
+        Ok(start)
+    }
+
+    // 파일의 지정 구간 읽기
+    fn read_at(&self, offset: u64, size: u64) -> Result<Vec<u8>, String> {
+        check_range(offset, size, self.data_end)?;
+
+        let mut file = self.source.lock()
+            .map_err(|_| "exe 파일 잠금 실패".to_string())?;
+
+        file.seek(SeekFrom::Start(offset))
+            .map_err(|e| format!("파일 탐색 실패: {}", e))?;
+
+        let mut buf = vec![0u8; size as usize];
+        file.read_exact(&mut buf)
+            .map_err(|e| format!("데이터 읽기 실패: {}", e))?;
+
+        Ok(buf)
+    }
+
+    // 내장된 프로젝트 JSON
+    pub fn project_json(&self) -> Result<String, String> {
+        let mut project_buf = self.read_at(self.project_offset, self.project_size)
+            .map_err(|e| format!("프로젝트 데이터 읽기 실패: {}", e))?;
+
+        if let Some(manifest) = self.manifest.as_ref().filter(|m| m.project_json_encryption.is_some()) {
+            project_buf = self.decrypt_entry(PROJECT_JSON_ENTRY_ID, manifest.project_json_nonce.as_deref(), &project_buf)?;
+        }
+
+        String::from_utf8(project_buf)
+            .map_err(|e| format!("프로젝트 데이터 디코딩 실패: {}", e))
+    }
+
+    // ID로 미디어 엔트리 찾기
+    pub fn find_media(&self, media_id: &str) -> Result<&MediaManifestEntry, String> {
+        self.manifest()?
+            .media
+            .iter()
+            .find(|m| m.id == media_id)
+            .ok_or_else(|| format!("미디어를 찾을 수 없습니다: {}", media_id))
+    }
+
+    // 미디어 엔트리 전체 읽기 (암호화된 경우 복호화, 압축된 경우 압축 해제)
+    pub fn read_media(&self, entry: &MediaManifestEntry) -> Result<Vec<u8>, String> {
+        self.check_media(entry)?;
+        let mut media_buf = self.read_at(entry.offset, entry.size)
+            .map_err(|e| format!("미디어 데이터 읽기 실패: {}", e))?;
+
+        if entry.encryption.is_some() {
+            media_buf = self.decrypt_entry(entry.stored_id(), entry.nonce.as_deref(), &media_buf)?;
+        }
+
+        match entry.compression {
+            Some(method) => compression::decompress(&media_buf, method, entry.content_size())
+                .map_err(|e| format!("미디어 압축 해제 실패 ({}): {}", entry.id, e)),
+            None => Ok(media_buf),
+        }
+    }
+
+    // 미디어 엔트리의 일부 범위 읽기 (end 포함, 압축 해제된 데이터 기준)
+    pub fn read_media_range(&self, entry: &MediaManifestEntry, start: u64, end: u64) -> Result<Vec<u8>, String> {
+        if start > end || end >= entry.content_size() {
+            return Err(format!(
+                "요청 범위가 잘못되었습니다 ({}-{}, 미디어 크기 {})",
+                start, end, entry.content_size()
+            ));
+        }
+        self.check_media(entry)?;
+
+        // 압축된 엔트리는 전체를 풀어서 잘라냄 (압축 대상은 이미지뿐이고, 푼 데이터는 캐시)
+        if entry.compression.is_some() {
+            let data = self.decompressed_media(entry)?;
+            return data.get(start as usize..=end as usize)
+                .map(<[u8]>::to_vec)
+                .ok_or_else(|| format!("압축 해제된 미디어 크기가 잘못되었습니다 ({})", entry.id));
+        }
+
+        if entry.encryption.is_some() {
+            return self.read_encrypted_range(entry, start, end);
+        }
+
+        let offset = entry.offset.checked_add(start)
+            .ok_or_else(|| format!("미디어 위치가 잘못되었습니다 ({})", entry.id))?;
+        self.read_at(offset, end - start + 1)
+            .map_err(|e| format!("미디어 데이터 읽기 실패: {}", e))
+    }
+
+    // 압축된 엔트리의 압축 해제 데이터 (캐시에 없으면 풀어서 보관)
+    fn decompressed_media(&self, entry: &MediaManifestEntry) -> Result<Arc<Vec<u8>>, String> {
+        let cached = self.decompressed.lock()
+            .map_err(|_| "미디어 캐시 잠금 실패".to_string())?
+            .get(entry.stored_id());
+        if let Some(data) = cached {
+            return Ok(data);
+        }
+
+        // 압축 해제는 잠금 없이 (다른 요청이 기다리지 않도록)
+        let data = Arc::new(self.read_media(entry)?);
+        self.decompressed.lock()
+            .map_err(|_| "미디어 캐시 잠금 실패".to_string())?
+            .insert(entry.stored_id(), data.clone());
+        Ok(data)
+    }
+
+    // 암호화된 (압축되지 않은) 엔트리에서 범위에 해당하는 청크만 읽어 복호화
+    fn read_encrypted_range(&self, entry: &MediaManifestEntry, start: u64, end: u64) -> Result<Vec<u8>, String> {
+        let nonce = entry.nonce.as_deref()
+            .ok_or_else(|| format!("암호화 nonce가 없습니다 ({})", entry.id))?;
+
+        let first_chunk = start / payload_crypto::CHUNK_SIZE;
+        let last_chunk = end / payload_crypto::CHUNK_SIZE;
+        let chunk_start = payload_crypto::encrypted_offset(first_chunk);
+        let chunk_end = payload_crypto::encrypted_offset(last_chunk + 1).min(entry.size);
+        if chunk_start >= chunk_end {
+            return Err(format!("암호화된 미디어 크기가 잘못되었습니다 ({})", entry.id));
+        }
+
+        let offset = entry.offset.checked_add(chunk_start)
+            .ok_or_else(|| format!("미디어 위치가 잘못되었습니다 ({})", entry.id))?;
+        let sealed = self.read_at(offset, chunk_end - chunk_start)
+            .map_err(|e| format!("미디어 데이터 읽기 실패: {}", e))?;
+        let plain = payload_crypto::decrypt_chunks(self.key()?, nonce, entry.stored_id(), entry.size, first_chunk, &sealed)?;
+
+        let skip = (start - first_chunk * payload_crypto::CHUNK_SIZE) as usize;
+        let len = (end - start + 1) as usize;
+        skip.checked_add(len)
+            .and_then(|range_end| plain.get(skip..range_end))
+            .map(<[u8]>::to_vec)
+            .ok_or_else(|| format!("암호화된 미디어 크기가 잘못되었습니다 ({})", entry.id))
+    }
+
+    // 파일의 특정 구간 SHA-256 계산 (대용량 미디어를 메모리에 올리지 않음)
+    // 버퍼 단위로 잠가 읽으므로 큰 비디오를 해시하는 동안에도 다른 요청이 파일을 읽을 수 있음
+    fn sha256_range(&self, offset: u64, size: u64) -> Result<String, String> {
+        let mut hasher = Sha256::new();
+        let mut position = offset;
+        let mut remaining = size;
+        let mut buf = vec![0u8; 64 * 1024];
+
+        while remaining > 0 {
+            let to_read = remaining.min(buf.len() as u64) as usize;
+            let mut file = self.source.lock()
+                .map_err(|_| "exe 파일 잠금 실패".to_string())?;
+            file.seek(SeekFrom::Start(position))
+                .map_err(|e| format!("파일 탐색 실패: {}", e))?;
+            file.read_exact(&mut buf[..to_read])
+                .map_err(|e| format!("데이터 읽기 실패: {}", e))?;
+            drop(file);
+
+            hasher.update(&buf[..to_read]);
+            position += to_read as u64;
+            remaining -= to_read as u64;
+        }
+
+        Ok(format!("{:x}", hasher.finalize()))
+    }
+
+    // 저장 구간의 해시 (처음 요청될 때 한 번만 계산해 보관)
+    fn stored_range_hash(&self, offset: u64, size: u64) -> Result<String, String> {
+        let cell = self.range_hashes.lock()
+            .map_err(|_| "해시 캐시 잠금 실패".to_string())?
+            .entry((offset, size))
+            .or_default()
+            .clone();
+        cell.get_or_init(|| self.sha256_range(offset, size)).clone()
+    }
+
+    // 하나의 엔트리 검증: 범위 확인 후 해시 비교
+    // 반환값: Ok(true) 검증됨, Ok(false) 해시 없음(이전 빌드), Err(사유) 손상
+    fn verify_entry(&self, offset: u64, size: u64, expected: Option<&str>) -> Result<bool, String> {
+        check_range(offset, size, self.data_end)?;
+
+        let Some(expected) = expected else {
+            return Ok(false);
+        };
+
+        let actual = self.stored_range_hash(offset, size)?;
+        if !actual.eq_ignore_ascii_case(expected) {
+            return Err(format!("해시 불일치 (예상 {}, 실제 {})", expected, actual));
+        }
+
+        Ok(true)
+    }
+
+    // V2: 매니페스트의 모든 엔트리 무결성 검증
+    // 같은 저장 데이터를 공유하는 엔트리는 구간 해시를 한 번만 계산
+    pub fn verify(&self, include_media: bool) -> Result<IntegrityReport, String> {
+        let manifest = self.manifest()?;
+
+        let mut report = IntegrityReport {
+            verified_count: 0,
+            unchecked_count: 0,
+            corrupt: Vec::new(),
+        };
+
+        let mut record = |result: Result<bool, String>, id: &str, name: &str| match result {
+            Ok(true) => report.verified_count += 1,
+            Ok(false) => report.unchecked_count += 1,
+            Err(reason) => report.corrupt.push(CorruptEntry {
+                id: id.to_string(),
+                name: name.to_string(),
+                reason,
+            }),
+        };
+
+        let project_result = self.verify_entry(
+            manifest.project_json_offset,
+            manifest.project_json_size,
+            manifest.project_json_sha256.as_deref(),
+        );
+        record(project_result, PROJECT_JSON_ENTRY_ID, PROJECT_JSON_ENTRY_ID);
+
+        if include_media {
+            for entry in &manifest.media {
+                let result = self.verify_entry(entry.offset, entry.size, entry.sha256.as_deref());
+                record(result, &entry.id, &entry.name);
+            }
+            if let (Some(offset), Some(size)) = (manifest.app_icon_offset, manifest.app_icon_size) {
+                let result = self.verify_entry(offset, size, manifest.app_icon_sha256.as_deref());
+                record(result, APP_ICON_ENTRY_ID, APP_ICON_ENTRY_ID);
+            }
+        }
+
+        Ok(report)
+    }
+
+    // 내장된 앱 아이콘 PNG (없으면 None, 해시가 있으면 확인)
+    pub fn app_icon(&self) -> Result<Option<Vec<u8>>, String> {
+        let manifest = self.manifest()?;
+        let (Some(offset), Some(size)) = (manifest.app_icon_offset, manifest.app_icon_size) else {
+            return Ok(None);
+        };
+
+        let icon = self.read_at(offset, size)
+            .map_err(|e| format!("앱 아이콘 읽기 실패: {}", e))?;
+        if let Some(expected) = &manifest.app_icon_sha256 {
+            if !sha256_hex(&icon).eq_ignore_ascii_case(expected) {
+                return Err("앱 아이콘 데이터가 손상되었습니다.".to_string());
+            }
+        }
+
+        Ok(Some(icon))
+    }
+}
+
+// 현재 프로세스의 페이로드가 저장된 파일
+fn current_payload_path() -> Result<PathBuf, String> {
+    // AppImage로 실행되면 current_exe는 마운트된 내부 바이너리이므로 AppImage 파일 자체를 읽음
+    if let Some(appimage) = std::env::var_os("APPIMAGE") {
+        return Ok(PathBuf::from(appimage));
+    }
+
+    let current_exe = std::env::current_exe().map_err(|e| e.to_string())?;
+
+    // macOS .app 번들: Contents/MacOS/<exe> → Contents/Resources/tutorial.payload
+    let bundle_resource = current_exe.parent()
+        .and_then(Path::parent)
+        .map(|contents| contents.join("Resources").join(BUNDLE_PAYLOAD_RESOURCE));
+    if let Some(resource) = bundle_resource.filter(|r| r.is_file()) {
+        return Ok(resource);
+    }
+
+    Ok(current_exe)
+}
+
+// [offset, offset + size) 구간이 데이터 영역 안에 있는지 확인
+fn check_range(offset: u64, size: u64, data_end: u64) -> Result<(), String> {
+    match offset.checked_add(size) {
+        Some(end) if end <= data_end => Ok(()),
+        _ => Err(format!(
+            "데이터가 잘렸습니다 (offset {}, size {}, 데이터 영역 크기 {})",
+            offset, size, data_end
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::compression::StreamCompressor;
+
+    // 페이로드 앞에 붙는 원본 exe 대신 쓰는 바이트
+    const STUB_EXE: &[u8] = b"MZ-template-executable";
+    const PROJECT_JSON: &str = r#"{"id":"p1","name":"테스트","pages":[]}"#;
+
+    // 빌드와 같은 배치로 V2 데이터 작성: [원본][미디어...][프로젝트 JSON][매니페스트][크기][매직]
+    struct TestBuild {
+        bytes: Vec<u8>,
+        media: Vec<MediaManifestEntry>,
+    }
+
+    impl TestBuild {
+        fn new() -> TestBuild {
+            TestBuild { bytes: STUB_EXE.to_vec(), media: Vec::new() }
+        }
+
+        // 저장할 바이트를 그대로 추가 (압축/암호화한 경우 엔트리 필드는 호출한 쪽에서 설정)
+        fn add(&mut self, id: &str, stored: &[u8]) -> &mut MediaManifestEntry {
+            self.media.push(MediaManifestEntry {
+                id: id.to_string(),
+                name: format!("{}.mp4", id),
+                mime_type: "video/mp4".to_string(),
+                offset: self.bytes.len() as u64,
+                size: stored.len() as u64,
+                sha256: Some(sha256_hex(stored)),
+                compression: None,
+                original_size: None,
+                encryption: None,
+                nonce: None,
+                shared_from: None,
+                poster_for: None,
+            });
+            self.bytes.extend_from_slice(stored);
+            self.media.last_mut().unwrap()
+        }
+
+        fn finish(self, project_json: &[u8], edit: impl FnOnce(&mut BuildManifest)) -> Vec<u8> {
+            let mut bytes = self.bytes;
+            let mut manifest = BuildManifest {
+                project_json_offset: bytes.len() as u64,
+                project_json_size: project_json.len() as u64,
+                media: self.media,
+                app_icon_offset: None,
+                app_icon_size: None,
+                app_icon_sha256: None,
+                project_json_sha256: Some(sha256_hex(project_json)),
+                verify_on_startup: false,
+                project_json_encryption: None,
+                project_json_nonce: None,
+                encryption_key_id: None,
+                license: None,
+                signature: None,
+            };
+            bytes.extend_from_slice(project_json);
+            edit(&mut manifest);
+
+            let json = serde_json::to_vec(&manifest).unwrap();
+            bytes.extend_from_slice(&json);
+            bytes.extend_from_slice(&(json.len() as u64).to_le_bytes());
+            bytes.extend_from_slice(MAGIC_BYTES_V2);
+            bytes
+        }
+    }
+
+    fn open(bytes: &[u8]) -> Result<Payload, String> {
+        Payload::from_source(Box::new(Cursor::new(bytes.to_vec())), bytes.len() as u64)
+    }
+
+    fn open_err(bytes: &[u8]) -> String {
+        match open(bytes) {
+            Ok(_) => panic!("잘못된 데이터가 열렸습니다"),
+            Err(e) => e,
+        }
+    }
+
+    fn pattern(len: usize) -> Vec<u8> {
+        (0..len).map(|i| (i * 31 % 251) as u8).collect()
+    }
+
+    fn two_media_build() -> Vec<u8> {
+        let mut build = TestBuild::new();
+        build.add("a", &pattern(1000));
+        build.add("b", b"second media");
+        build.finish(PROJECT_JSON.as_bytes(), |_| {})
+    }
+
+    #[test]
+    fn read_media_range_boundaries() {
+        let payload = open(&two_media_build()).unwrap();
+        let entry = payload.find_media("a").unwrap();
+        let data = pattern(1000);
+
+        assert_eq!(payload.read_media_range(entry, 0, 0).unwrap(), &data[..1]);
+        assert_eq!(payload.read_media_range(entry, 999, 999).unwrap(), &data[999..]);
+        assert_eq!(payload.read_media_range(entry, 0, 999).unwrap(), data);
+        assert_eq!(payload.read_media_range(entry, 100, 199).unwrap(), &data[100..200]);
+
+        assert!(payload.read_media_range(entry, 0, 1000).is_err());
+        assert!(payload.read_media_range(entry, 1000, 1000).is_err());
+        assert!(payload.read_media_range(entry, 5, 4).is_err());
+        assert!(payload.read_media_range(entry, 0, u64::MAX).is_err());
+    }
+
+    #[test]
+    fn decompressed_cache_evicts_oldest() {
+        let mut cache = DecompressedCache::default();
+        let half = DECOMPRESSED_CACHE_LIMIT as usize / 2;
+        cache.insert("a", Arc::new(vec![0; half]));
+        cache.insert("b", Arc::new(vec![0; half]));
+        assert!(cache.get("a").is_some());
+
+        // 가장 오래 쓰지 않은 b가 밀려남
+        cache.insert("c", Arc::new(vec![0; 1]));
+        assert!(cache.get("b").is_none());
+        assert!(cache.get("a").is_some());
+        assert!(cache.get("c").is_some());
+        assert_eq!(cache.bytes, half as u64 + 1);
+
+        // 한도보다 큰 데이터는 보관하지 않음
+        cache.insert("huge", Arc::new(vec![0; DECOMPRESSED_CACHE_LIMIT as usize + 1]));
+        assert!(cache.get("huge").is_none());
+        assert_eq!(cache.entries.len(), 2);
+
+        // 풀 수 있는 가장 큰 엔트리도 보관됨 (Range 요청마다 다시 풀지 않음)
+        cache.insert("largest", Arc::new(vec![0; compression::MAX_DECOMPRESSED_SIZE as usize]));
+        assert!(cache.get("largest").is_some());
+    }
+
+    #[test]
+    fn verify_reports_tampered_entries() {
+        let mut bytes = two_media_build();
+        let payload = open(&bytes).unwrap();
+        let offset = payload.find_media("b").unwrap().offset as usize;
+        drop(payload);
+        bytes[offset] ^= 0xFF;
+
+        let payload = open(&bytes).unwrap();
+        let report = payload.verify(true).unwrap();
+        assert_eq!(report.verified_count, 2);
+        assert_eq!(report.corrupt.len(), 1);
+        assert_eq!(report.corrupt[0].id, "b");
+        assert!(format_corrupt_entries(&report.corrupt).contains("b.mp4"));
+
+        // 미디어를 제외하면 프로젝트 JSON만 확인
+        let report = payload.verify(false).unwrap();
+        assert_eq!(report.verified_count, 1);
+        assert!(report.corrupt.is_empty());
+    }
+
+    #[test]
+    fn compressed_media_range() {
+        let data = pattern(300_000);
+        let mut compressor = StreamCompressor::new(MediaCompression::Zstd).unwrap();
+        let mut stored = compressor.update(&data).unwrap();
+        stored.extend_from_slice(&compressor.finish().unwrap());
+
+        let mut build = TestBuild::new();
+        let entry = build.add("img", &stored);
+        entry.compression = Some(MediaCompression::Zstd);
+        entry.original_size = Some(data.len() as u64);
+        let payload = open(&build.finish(PROJECT_JSON.as_bytes(), |_| {})).unwrap();
+        let entry = payload.find_media("img").unwrap();
+
+        assert_eq!(payload.read_media(entry).unwrap(), data);
+        assert_eq!(payload.read_media_range(entry, 0, 9).unwrap(), &data[..10]);
+        assert_eq!(payload.read_media_range(entry, 299_990, 299_999).unwrap(), &data[299_990..]);
+        assert!(payload.read_media_range(entry, 0, 300_000).is_err());
+        // 두 번째 요청부터는 캐시에서 자름
+        assert_eq!(payload.decompressed.lock().unwrap().entries.len(), 1);
+    }
+
+    #[test]
+    fn compressed_media_with_wrong_size_fails() {
+        let data = pattern(5000);
+        let mut compressor = StreamCompressor::new(MediaCompression::Deflate).unwrap();
+        let mut stored = compressor.update(&data).unwrap();
+        stored.extend_from_slice(&compressor.finish().unwrap());
+
+        let mut build = TestBuild::new();
+        let entry = build.add("img", &stored);
+        entry.compression = Some(MediaCompression::Deflate);
+        entry.original_size = Some(4000);
+        let payload = open(&build.finish(PROJECT_JSON.as_bytes(), |_| {})).unwrap();
+        let entry = payload.find_media("img").unwrap();
+
+        assert!(payload.read_media(entry).is_err());
+        assert!(payload.read_media_range(entry, 0, 10).is_err());
+    }
+
+    #[test]
+    fn v2_round_trip() {
+        let payload = open(&two_media_build()).unwrap();
+
+        assert_eq!(payload.format(), PayloadFormat::V2);
+        assert_eq!(payload.project_json().unwrap(), PROJECT_JSON);
+        assert_eq!(payload.payload_start().unwrap(), STUB_EXE.len() as u64);
+
+        let a = payload.find_media("a").unwrap();
+        assert_eq!(payload.read_media(a).unwrap(), pattern(1000));
+        let b = payload.find_media("b").unwrap();
+        assert_eq!(payload.read_media(b).unwrap(), b"second media");
+        assert!(payload.find_media("missing").is_err());
+
+        let report = payload.verify(true).unwrap();
+        assert_eq!(report.verified_count, 3);
+        assert_eq!(report.unchecked_count, 0);
+        assert!(report.corrupt.is_empty());
+    }
+
+    #[test]
+    fn v1_round_trip() {
+        let mut bytes = STUB_EXE.to_vec();
+        bytes.extend_from_slice(PROJECT_JSON.as_bytes());
+        bytes.extend_from_slice(&(PROJECT_JSON.len() as u64).to_le_bytes());
+        bytes.extend_from_slice(MAGIC_BYTES);
+
+        let payload = open(&bytes).unwrap();
+        assert_eq!(payload.format(), PayloadFormat::V1);
+        assert_eq!(payload.project_json().unwrap(), PROJECT_JSON);
+        assert_eq!(payload.payload_start().unwrap(), STUB_EXE.len() as u64);
+        assert!(payload.manifest().is_err());
+    }
+
+    #[test]
+    fn rejects_missing_or_truncated_trailer() {
+        let bytes = two_media_build();
+
+        assert!(open_err(b"").contains("찾을 수 없습니다"));
+        assert!(open_err(STUB_EXE).contains("찾을 수 없습니다"));
+        // 끝이 잘리면 매직 바이트가 맞지 않음
+        assert!(open_err(&bytes[..bytes.len() - 1]).contains("찾을 수 없습니다"));
+        // 앞이 잘리면 매니페스트 오프셋이 파일 밖을 가리킴
+        assert!(open(&bytes[STUB_EXE.len() + 100..]).is_err());
+    }
+
+    #[test]
+    fn rejects_corrupt_trailer() {
+        let bytes = two_media_build();
+        let length_offset = bytes.len() - MAGIC_BYTES_V2.len() - LENGTH_FIELD_SIZE as usize;
+
+        // 알 수 없는 포맷 버전
+        let mut future = bytes.clone();
+        *future.last_mut().unwrap() = b'9';
+        assert!(open_err(&future).contains("V9"));
+
+        // 본문 크기가 파일보다 큼
+        let mut too_long = bytes.clone();
+        too_long[length_offset..length_offset + 8].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert!(open_err(&too_long).contains("길이가 잘못되었습니다"));
+
+        // 매니페스트 크기 제한
+        let mut huge = vec![0u8; MAX_MANIFEST_SIZE as usize + 1];
+        huge.extend_from_slice(&(MAX_MANIFEST_SIZE + 1).to_le_bytes());
+        huge.extend_from_slice(MAGIC_BYTES_V2);
+        assert!(open_err(&huge).contains("비정상적"));
+
+        // 매니페스트 JSON 손상
+        let mut garbled = bytes.clone();
+        garbled[length_offset - 1] = b'!';
+        assert!(open_err(&garbled).contains("파싱 실패"));
+
+        // 프로젝트 JSON 위치가 매니페스트 뒤를 가리킴
+        let mut build = TestBuild::new();
+        build.add("a", b"data");
+        let misplaced = build.finish(PROJECT_JSON.as_bytes(), |m| m.project_json_offset += 1000);
+        assert!(open_err(&misplaced).contains("프로젝트 데이터 위치"));
+    }
+
+    #[test]
+    fn rejects_media_outside_data_area() {
+        let mut build = TestBuild::new();
+        build.add("a", b"data");
+        let bytes = build.finish(PROJECT_JSON.as_bytes(), |m| {
+            m.media[0].offset = u64::MAX - 1;
+        });
+        let payload = open(&bytes).unwrap();
+        let entry = payload.find_media("a").unwrap();
+
+        assert!(payload.read_media(entry).is_err());
+        assert!(payload.read_media_range(entry, 0, 1).is_err());
+        assert_eq!(payload.verify(true).unwrap().corrupt.len(), 1);
+    }
+
+    #[test]
+    fn encrypted_media_range_across_chunks() {
+        let key_hex = "11".repeat(payload_crypto::KEY_SIZE);
+        let key = PayloadKey::from_hex(&key_hex).unwrap();
+        let chunk = payload_crypto::CHUNK_SIZE as usize;
+        let data = pattern(chunk * 2 + 100);
+        let nonce = payload_crypto::new_nonce();
+        let sealed = payload_crypto::encrypt(&key, &nonce, "video", &data).unwrap();
+        let project_nonce = payload_crypto::new_nonce();
+        let project = payload_crypto::encrypt(&key, &project_nonce, PROJECT_JSON_ENTRY_ID, PROJECT_JSON.as_bytes()).unwrap();
+
+        let mut build = TestBuild::new();
+        let entry = build.add("video", &sealed);
+        entry.encryption = Some(PayloadEncryption::Aes256GcmChunked);
+        entry.nonce = Some(nonce);
+        entry.original_size = Some(data.len() as u64);
+        let bytes = build.finish(&project, |m| {
+            m.project_json_encryption = Some(PayloadEncryption::Aes256GcmChunked);
+            m.project_json_nonce = Some(project_nonce);
+            m.encryption_key_id = Some(key.key_id().to_string());
+        });
+
+        // 키가 없으면 읽을 수 없음
+        let payload = open(&bytes).unwrap();
+        assert!(payload.project_json().is_err());
+        assert!(payload.read_media(payload.find_media("video").unwrap()).is_err());
+
+        let mut payload = open(&bytes).unwrap();
+        payload.set_key(key);
+        let entry = payload.find_media("video").unwrap();
+        assert_eq!(payload.project_json().unwrap(), PROJECT_JSON);
+        assert_eq!(payload.read_media(entry).unwrap(), data);
+
+        let last = data.len() - 1;
+        for (start, end) in [(0, 0), (chunk - 1, chunk), (chunk, chunk), (chunk - 10, chunk * 2 + 10), (chunk * 2, last), (last, last), (0, last)] {
+            let range = payload.read_media_range(entry, start as u64, end as u64).unwrap();
+            assert_eq!(range, &data[start..=end], "범위 {}-{}", start, end);
+        }
+        assert!(payload.read_media_range(entry, 0, data.len() as u64).is_err());
+    }
+
+    #[test]
+    fn encrypted_media_with_wrong_key_fails() {
+        let key = PayloadKey::from_hex(&"22".repeat(payload_crypto::KEY_SIZE)).unwrap();
+        let other = PayloadKey::from_hex(&"33".repeat(payload_crypto::KEY_SIZE)).unwrap();
+        let nonce = payload_crypto::new_nonce();
+        let sealed = payload_crypto::encrypt(&key, &nonce, "video", &pattern(100)).unwrap();
+
+        let mut build = TestBuild::new();
+        let entry = build.add("video", &sealed);
+        entry.encryption = Some(PayloadEncryption::Aes256GcmChunked);
+        entry.nonce = Some(nonce);
+        entry.original_size = Some(100);
+        let bytes = build.finish(PROJECT_JSON.as_bytes(), |m| m.encryption_key_id = Some(key.key_id().to_string()));
+
+        let mut payload = open(&bytes).unwrap();
+        payload.set_key(other);
+        let entry = payload.find_media("video").unwrap();
+        assert!(payload.read_media(entry).unwrap_err().contains("맞지 않습니다"));
+        assert!(payload.read_media_range(entry, 0, 10).is_err());
+    }
+
+    #[test]
+    fn verify_media_on_read_rejects_tampered_media() {
+        let mut build = TestBuild::new();
+        build.add("a", &pattern(1000));
+        build.add("b", b"second media");
+        build.add("legacy", b"no hash").sha256 = None;
+        let mut bytes = build.finish(PROJECT_JSON.as_bytes(), |_| {});
+        let offset = open(&bytes).unwrap().find_media("b").unwrap().offset as usize;
+        bytes[offset] ^= 0xFF;
+
+        // 켜기 전에는 해시를 확인하지 않음
+        let payload = open(&bytes).unwrap();
+        let b = payload.find_media("b").unwrap();
+        assert!(payload.read_media(b).is_ok());
+
+        payload.verify_media_on_read();
+        let a = payload.find_media("a").unwrap();
+        assert_eq!(payload.read_media_range(a, 0, 9).unwrap(), &pattern(1000)[..10]);
+        assert!(payload.read_media(b).unwrap_err().contains("손상"));
+        assert!(payload.read_media_range(b, 0, 0).is_err());
+        let legacy = payload.find_media("legacy").unwrap();
+        assert!(payload.read_media(legacy).unwrap_err().contains("해시가 없는"));
+    }
+
+    #[test]
+    fn shared_entries_hash_stored_range_once() {
+        let mut build = TestBuild::new();
+        let original = build.add("a", &pattern(1000)).clone();
+        build.media.push(MediaManifestEntry {
+            id: "copy".to_string(),
+            shared_from: Some("a".to_string()),
+            ..original
+        });
+        let payload = open(&build.finish(PROJECT_JSON.as_bytes(), |_| {})).unwrap();
+        payload.verify_media_on_read();
+
+        assert_eq!(payload.verify(true).unwrap().verified_count, 3);
+        let copy = payload.find_media("copy").unwrap();
+        assert_eq!(payload.read_media(copy).unwrap(), pattern(1000));
+        // 프로젝트 JSON 구간 + 공유된 미디어 구간
+        assert_eq!(payload.range_hashes.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn app_icon_hash_is_checked() {
+        let icon = b"\x89PNG icon bytes";
+        let mut build = TestBuild::new();
+        let icon_offset = build.bytes.len() as u64;
+        build.bytes.extend_from_slice(icon);
+        let bytes = build.finish(PROJECT_JSON.as_bytes(), |m| {
+            m.app_icon_offset = Some(icon_offset);
+            m.app_icon_size = Some(icon.len() as u64);
+            m.app_icon_sha256 = Some(sha256_hex(icon));
+        });
+
+        let payload = open(&bytes).unwrap();
+        assert_eq!(payload.app_icon().unwrap().unwrap(), icon);
+        assert_eq!(payload.payload_start().unwrap(), icon_offset);
+
+        let mut tampered = bytes.clone();
+        tampered[icon_offset as usize] ^= 0xFF;
+        let payload = open(&tampered).unwrap();
+        assert!(payload.app_icon().is_err());
+        assert_eq!(payload.verify(true).unwrap().corrupt[0].id, APP_ICON_ENTRY_ID);
+    }
+}
@@ -0,0 +1,107 @@
+// 제품의 내장 데이터 사용 허가 (서명, 무결성, 기간/기기 라이선스)
+//
+// 제품 시작 시 한 번 확인하고 결과를 앱 state(PayloadAccess)에 보관한다.
+// 내장 데이터를 내보내는 모든 경로(프로젝트 JSON, 미디어 매니페스트, 미디어 명령,
+// tutorial-media 프로토콜, 추출)는 Payload::current() 대신 `payload`로 데이터를 얻어
+// 확인에 실패한 제품이 어느 경로로도 내용을 내보내지 않도록 한다.
+// 다른 제품 exe에서 .tutorial을 복원할 때는 `check_extract`로 같은 서명/무결성 확인을 거치고,
+// 실행 파일 옆 라이선스 파일 대신 작성자가 명시한 키 파일로만 암호화/라이선스 빌드를 연다.
+
+use std::path::Path;
+
+use tauri::Manager;
+
+use crate::license;
+use crate::payload::{format_corrupt_entries, Payload, PayloadFormat};
+use crate::payload_crypto::PayloadKey;
+use crate::payload_signature;
+
+// 시작 시 확인한 결과 (실패했으면 에러 화면에 표시할 메시지)
+pub struct PayloadAccess {
+    result: Result<(), String>,
+}
+
+impl PayloadAccess {
+    // 현재 실행 파일의 내장 데이터 확인 (내장 데이터가 없거나 V1이면 확인할 것이 없음)
+    pub fn check() -> PayloadAccess {
+        let result = match Payload::current() {
+            Ok(payload) if payload.format() == PayloadFormat::V2 => check_payload(payload),
+            _ => Ok(()),
+        };
+        if let Err(e) = &result {
+            log::warn!("내장 데이터 사용 불가: {}", e);
+        }
+        PayloadAccess { result }
+    }
+}
+
+fn check_payload(payload: &Payload) -> Result<(), String> {
+    check_contents(payload)?;
+
+    // 기간/기기 제한 빌드 확인
+    license::check_license(payload.manifest()?)
+}
+
+// 서명과 무결성 확인 (실행/추출 공통)
+fn check_contents(payload: &Payload) -> Result<(), String> {
+    let manifest = payload.manifest()?;
+
+    // 서명 확인 (템플릿에 공개 키가 컴파일된 경우)
+    let signed = payload_signature::check_signature(manifest)?;
+
+    // 시작 시 무결성 검증 (미디어는 빌드 옵션이 켜진 경우에만)
+    let report = payload.verify(manifest.verify_on_startup)?;
+    if !report.corrupt.is_empty() {
+        return Err(format_corrupt_entries(&report.corrupt));
+    }
+
+    // 서명된 제품의 미디어는 시작 시 모두 해시하지 않고 처음 읽을 때 확인
+    if signed {
+        payload.verify_media_on_read();
+    }
+    Ok(())
+}
+
+// 제품 exe에서 내용을 꺼낼 때 작성자가 명시한 키 파일
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ExtractKeys<'a> {
+    // 암호화된 빌드의 복호화 키 파일 (빌드 시 --key-file)
+    pub key_file: Option<&'a Path>,
+    // 라이선스 빌드의 발급자 서명 키 파일 (빌드 시 --license-key)
+    pub license_key_file: Option<&'a Path>,
+}
+
+// 다른 제품 exe(V2)의 내용을 꺼내도 되는지 확인하고 복호화 키 설정
+// 암호화/라이선스 빌드는 키 파일이 없으면 거부한다. (라이선스 파일을 옆에 두거나
+// 키가 컴파일된 템플릿으로 실행하는 것만으로는 원본 프로젝트를 꺼낼 수 없음)
+pub fn check_extract(payload: &mut Payload, keys: ExtractKeys) -> Result<(), String> {
+    let manifest = payload.manifest()?;
+    let encrypted = manifest.is_encrypted();
+    let licensed = manifest.license.is_some();
+
+    if encrypted {
+        let key_file = keys.key_file
+            .ok_or_else(|| "암호화된 제품입니다. 빌드에 사용한 키 파일(--key-file)이 필요합니다.".to_string())?;
+        payload.set_key(PayloadKey::load_file(key_file)?);
+    }
+    if licensed {
+        let license_key_file = keys.license_key_file
+            .ok_or_else(|| "라이선스가 들어간 제품입니다. 발급자 서명 키 파일(--license-key)이 필요합니다.".to_string())?;
+        license::check_issuer(payload.manifest()?, license_key_file)?;
+    }
+
+    check_contents(payload)?;
+
+    // 꺼내는 미디어는 시작 시 검증 옵션과 관계없이 모두 읽을 때 해시 확인
+    payload.verify_media_on_read();
+    Ok(())
+}
+
+// 사용이 허가된 현재 실행 파일의 내장 데이터
+// 시작 시 확인하지 않았으면(state 없음) 허가하지 않음
+pub fn payload(app: &tauri::AppHandle) -> Result<&'static Payload, String> {
+    let access = app.try_state::<PayloadAccess>()
+        .ok_or_else(|| "내장 데이터 사용 허가를 확인하지 않았습니다.".to_string())?;
+    access.result.clone()?;
+    Payload::current()
+}
@@ -0,0 +1,407 @@
+// 내장 데이터 암호화 (선택 사항)
+//
+// 미디어와 프로젝트 JSON을 AES-256-GCM으로 암호화한다. 비디오도 Range 요청으로
+// 일부만 읽을 수 있도록 엔트리를 CHUNK_SIZE 단위 청크로 나누어 각각 암호화한다.
+//   청크 nonce = 엔트리 nonce(7바이트, 엔트리마다 무작위) + 청크 번호(u32 BE) + 마지막 청크 표시(1바이트)
+//   AAD       = 엔트리 ID (다른 엔트리와 바꿔치기 방지)
+// 복호화 키는 템플릿 빌드 시 TUTORIAL_PAYLOAD_KEY 환경 변수로 컴파일해 넣거나,
+// 제품 옆의 라이선스 파일(tutorial.license, hex 키)로 전달한다.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use aes_gcm::aead::{Aead, KeyInit, OsRng, Payload as AeadPayload};
+use aes_gcm::aead::rand_core::RngCore;
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+// 키 길이 (AES-256)
+pub const KEY_SIZE: usize = 32;
+// 청크 하나의 평문 크기
+pub const CHUNK_SIZE: u64 = 64 * 1024;
+// 청크마다 붙는 인증 태그 크기
+const TAG_SIZE: u64 = 16;
+// 엔트리 nonce 길이 (청크 번호와 마지막 표시를 붙여 12바이트 GCM nonce가 됨)
+const NONCE_PREFIX_SIZE: usize = 7;
+
+// 제품 옆에 두는 라이선스(키) 파일 이름
+pub const LICENSE_FILE_NAME: &str = "tutorial.license";
+
+// 템플릿에 컴파일해 넣는 키 (hex, 빌드 시 환경 변수로 지정)
+const COMPILED_KEY: Option<&str> = option_env!("TUTORIAL_PAYLOAD_KEY");
+
+// 엔트리 암호화 방식
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PayloadEncryption {
+    Aes256GcmChunked,
+}
+
+// 내장 데이터 암호화 키
+pub struct PayloadKey {
+    cipher: Aes256Gcm,
+    key_id: String,
+}
+
+impl PayloadKey {
+    fn from_bytes(bytes: &[u8; KEY_SIZE]) -> PayloadKey {
+        PayloadKey {
+            cipher: Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(bytes)),
+            key_id: key_id(bytes),
+        }
+    }
+
+    // 새 무작위 키 생성, (키, hex 문자열) 반환
+    pub fn generate() -> (PayloadKey, String) {
+        let mut bytes = [0u8; KEY_SIZE];
+        OsRng.fill_bytes(&mut bytes);
+        (PayloadKey::from_bytes(&bytes), to_hex(&bytes))
+    }
+
+    // hex 문자열(64자)에서 키 생성
+    pub fn from_hex(value: &str) -> Result<PayloadKey, String> {
+        let bytes = from_hex(value.trim())
+            .and_then(|b| <[u8; KEY_SIZE]>::try_from(b).ok())
+            .ok_or_else(|| format!("암호화 키 형식이 잘못되었습니다. ({}자리 hex 필요)", KEY_SIZE * 2))?;
+        Ok(PayloadKey::from_bytes(&bytes))
+    }
+
+    // 키 파일(라이선스 파일) 읽기
+    pub fn load_file(path: &Path) -> Result<PayloadKey, String> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| format!("키 파일 읽기 실패 ({}): {}", path.display(), e))?;
+        PayloadKey::from_hex(&content)
+    }
+
+    // 템플릿에 컴파일된 키
+    pub fn compiled() -> Option<Result<PayloadKey, String>> {
+        COMPILED_KEY.map(PayloadKey::from_hex)
+    }
+
+    // 매니페스트에 기록하는 키 식별자 (키 자체는 노출하지 않음)
+    pub fn key_id(&self) -> &str {
+        &self.key_id
+    }
+}
+
+// 키 SHA-256 앞 8바이트
+fn key_id(bytes: &[u8]) -> String {
+    to_hex(&Sha256::digest(bytes)[..8])
+}
+
+pub fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+pub fn from_hex(value: &str) -> Option<Vec<u8>> {
+    value.as_bytes()
+        .chunks(2)
+        .map(|pair| match pair {
+            [high, low] => Some((hex_digit(*high)? << 4) | hex_digit(*low)?),
+            _ => None,
+        })
+        .collect()
+}
+
+fn hex_digit(c: u8) -> Option<u8> {
+    (c as char).to_digit(16).map(|d| d as u8)
+}
+
+// 제품 실행 시 사용할 키 찾기: 컴파일된 키 → 페이로드 파일 옆 라이선스 파일
+// (.app 번들이면 번들 옆 라이선스 파일도 확인)
+pub fn find_runtime_key(payload_path: &Path) -> Result<PayloadKey, String> {
+    if let Some(key) = PayloadKey::compiled() {
+        return key;
+    }
+
+    let mut candidates: Vec<PathBuf> = Vec::new();
+    if let Some(dir) = payload_path.parent() {
+        candidates.push(dir.join(LICENSE_FILE_NAME));
+
+        // <name>.app/Contents/Resources/tutorial.payload → <name>.app 옆
+        let bundle = dir.parent()
+            .filter(|contents| contents.file_name().is_some_and(|n| n == "Contents"))
+            .and_then(Path::parent)
+            .and_then(Path::parent);
+        if let Some(bundle_dir) = bundle {
+            candidates.push(bundle_dir.join(LICENSE_FILE_NAME));
+        }
+    }
+
+    match candidates.iter().find(|p| p.is_file()) {
+        Some(path) => PayloadKey::load_file(path),
+        None => Err(format!(
+            "암호화된 튜토리얼입니다. 실행 파일 옆에 라이선스 파일({})이 필요합니다.",
+            LICENSE_FILE_NAME
+        )),
+    }
+}
+
+// 빌드 시 사용할 키 결정: 키 파일 → 컴파일된 키 → (generate가 켜져 있을 때만) 새 키 생성
+// 반환값의 hex 문자열은 새로 만든 키일 때만 있으며, 제품 옆 라이선스 파일로 저장해야 함
+// (컴파일된 키는 같은 TUTORIAL_PAYLOAD_KEY로 빌드된 템플릿에서만 열 수 있음)
+// 새 키는 평문 파일로 제품과 함께 배포되므로 명시적으로 요청한 경우에만 만든다.
+pub fn resolve_build_key(key_file: Option<&Path>, generate: bool) -> Result<(PayloadKey, Option<String>), String> {
+    if let Some(path) = key_file {
+        return Ok((PayloadKey::load_file(path)?, None));
+    }
+    if let Some(key) = PayloadKey::compiled() {
+        return Ok((key?, None));
+    }
+    if !generate {
+        return Err(
+            "암호화 키가 없습니다. 키 파일을 지정하거나 TUTORIAL_PAYLOAD_KEY로 빌드한 템플릿을 사용하세요. \
+             (새 키를 만들어 제품 옆에 평문 라이선스 파일로 저장하려면 키 생성 옵션을 켜세요)"
+                .to_string(),
+        );
+    }
+    let (key, hex) = PayloadKey::generate();
+    Ok((key, Some(hex)))
+}
+
+// 제품 옆 라이선스 파일 경로 (.app 번들이면 번들 옆)
+pub fn license_path(product_path: &Path) -> PathBuf {
+    product_path.with_file_name(LICENSE_FILE_NAME)
+}
+
+// 엔트리 nonce 생성 (hex)
+pub fn new_nonce() -> String {
+    let mut prefix = [0u8; NONCE_PREFIX_SIZE];
+    OsRng.fill_bytes(&mut prefix);
+    to_hex(&prefix)
+}
+
+fn parse_nonce(nonce: &str) -> Result<[u8; NONCE_PREFIX_SIZE], String> {
+    from_hex(nonce)
+        .and_then(|b| <[u8; NONCE_PREFIX_SIZE]>::try_from(b).ok())
+        .ok_or_else(|| "암호화 nonce 형식이 잘못되었습니다.".to_string())
+}
+
+// 암호문 크기 → 청크 수 (마지막 청크만 CHUNK_SIZE보다 작을 수 있음)
+fn chunk_count_for_encrypted(encrypted_size: u64) -> Result<u64, String> {
+    let count = encrypted_size.div_ceil(CHUNK_SIZE + TAG_SIZE).max(1);
+    if encrypted_size < count * TAG_SIZE {
+        return Err(format!("암호화된 데이터 크기가 잘못되었습니다 ({} 바이트)", encrypted_size));
+    }
+    Ok(count)
+}
+
+// 청크 번호로 암호문 안의 위치 계산
+pub fn encrypted_offset(chunk_index: u64) -> u64 {
+    chunk_index.saturating_mul(CHUNK_SIZE + TAG_SIZE)
+}
+
+fn chunk_nonce(prefix: &[u8; NONCE_PREFIX_SIZE], index: u64, last: bool) -> Result<[u8; 12], String> {
+    let index = u32::try_from(index)
+        .map_err(|_| "암호화할 데이터가 너무 큽니다.".to_string())?;
+    let mut nonce = [0u8; 12];
+    nonce[..NONCE_PREFIX_SIZE].copy_from_slice(prefix);
+    nonce[NONCE_PREFIX_SIZE..11].copy_from_slice(&index.to_be_bytes());
+    nonce[11] = last as u8;
+    Ok(nonce)
+}
+
+// 엔트리 전체 암호화
+pub fn encrypt(key: &PayloadKey, nonce: &str, entry_id: &str, data: &[u8]) -> Result<Vec<u8>, String> {
+    let mut encryptor = StreamEncryptor::new(key, nonce, entry_id)?;
+    let mut output = encryptor.update(data)?;
+    output.extend_from_slice(&encryptor.finish()?);
+    Ok(output)
+}
+
+// 엔트리를 나누어 받으면서 암호화 (대용량 미디어를 메모리에 올리지 않음)
+// 마지막 청크 표시를 위해 다음 데이터가 들어오거나 finish될 때까지 청크 하나를 보관한다.
+pub struct StreamEncryptor<'a> {
+    key: &'a PayloadKey,
+    prefix: [u8; NONCE_PREFIX_SIZE],
+    entry_id: &'a str,
+    index: u64,
+    pending: Vec<u8>,
+}
+
+impl<'a> StreamEncryptor<'a> {
+    pub fn new(key: &'a PayloadKey, nonce: &str, entry_id: &'a str) -> Result<StreamEncryptor<'a>, String> {
+        Ok(StreamEncryptor {
+            key,
+            prefix: parse_nonce(nonce)?,
+            entry_id,
+            index: 0,
+            pending: Vec::with_capacity(CHUNK_SIZE as usize),
+        })
+    }
+
+    // 데이터를 추가하고 완성된 청크의 암호문 반환
+    pub fn update(&mut self, mut data: &[u8]) -> Result<Vec<u8>, String> {
+        let mut output = Vec::new();
+        while !data.is_empty() {
+            if self.pending.len() == CHUNK_SIZE as usize {
+                output.extend_from_slice(&self.seal(false)?);
+            }
+            let take = (CHUNK_SIZE as usize - self.pending.len()).min(data.len());
+            self.pending.extend_from_slice(&data[..take]);
+            data = &data[take..];
+        }
+        Ok(output)
+    }
+
+    // 남은 데이터를 마지막 청크로 암호화 (빈 엔트리도 태그 하나는 기록)
+    pub fn finish(mut self) -> Result<Vec<u8>, String> {
+        self.seal(true)
+    }
+
+    fn seal(&mut self, last: bool) -> Result<Vec<u8>, String> {
+        let nonce = chunk_nonce(&self.prefix, self.index, last)?;
+        let sealed = self.key.cipher
+            .encrypt(Nonce::from_slice(&nonce), AeadPayload { msg: &self.pending, aad: self.entry_id.as_bytes() })
+            .map_err(|_| format!("데이터 암호화 실패 ({})", self.entry_id))?;
+        self.index += 1;
+        self.pending.clear();
+        Ok(sealed)
+    }
+}
+
+// 연속된 청크 복호화
+// first_chunk: data가 시작하는 청크 번호, encrypted_size: 엔트리 전체 암호문 크기
+pub fn decrypt_chunks(
+    key: &PayloadKey,
+    nonce: &str,
+    entry_id: &str,
+    encrypted_size: u64,
+    first_chunk: u64,
+    data: &[u8],
+) -> Result<Vec<u8>, String> {
+    let prefix = parse_nonce(nonce)?;
+    let count = chunk_count_for_encrypted(encrypted_size)?;
+    let mut output = Vec::with_capacity(data.len());
+
+    for (i, sealed) in data.chunks((CHUNK_SIZE + TAG_SIZE) as usize).enumerate() {
+        let index = first_chunk + i as u64;
+        let nonce = chunk_nonce(&prefix, index, index + 1 == count)?;
+        let plain = key.cipher
+            .decrypt(Nonce::from_slice(&nonce), AeadPayload { msg: sealed, aad: entry_id.as_bytes() })
+            .map_err(|_| format!("데이터 복호화 실패 ({}): 키가 다르거나 데이터가 손상되었습니다.", entry_id))?;
+        output.extend_from_slice(&plain);
+    }
+
+    Ok(output)
+}
+
+// 엔트리 전체 복호화
+pub fn decrypt(key: &PayloadKey, nonce: &str, entry_id: &str, data: &[u8]) -> Result<Vec<u8>, String> {
+    decrypt_chunks(key, nonce, entry_id, data.len() as u64, 0, data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_key(byte: u8) -> PayloadKey {
+        PayloadKey::from_bytes(&[byte; KEY_SIZE])
+    }
+
+    fn pattern(len: usize) -> Vec<u8> {
+        (0..len).map(|i| (i * 7 % 253) as u8).collect()
+    }
+
+    #[test]
+    fn round_trip_at_chunk_edges() {
+        let key = test_key(1);
+        let nonce = new_nonce();
+        let chunk = CHUNK_SIZE as usize;
+
+        for len in [0, 1, chunk - 1, chunk, chunk + 1, chunk * 3] {
+            let data = pattern(len);
+            let sealed = encrypt(&key, &nonce, "entry", &data).unwrap();
+            let chunks = len.div_ceil(chunk).max(1) as u64;
+            assert_eq!(sealed.len() as u64, len as u64 + chunks * TAG_SIZE, "크기 {}", len);
+            assert_eq!(decrypt(&key, &nonce, "entry", &sealed).unwrap(), data, "크기 {}", len);
+        }
+    }
+
+    #[test]
+    fn stream_encryptor_matches_one_shot() {
+        let key = test_key(2);
+        let nonce = new_nonce();
+        let data = pattern(CHUNK_SIZE as usize * 2 + 10);
+
+        let mut encryptor = StreamEncryptor::new(&key, &nonce, "entry").unwrap();
+        let mut streamed = Vec::new();
+        for piece in data.chunks(1000) {
+            streamed.extend_from_slice(&encryptor.update(piece).unwrap());
+        }
+        streamed.extend_from_slice(&encryptor.finish().unwrap());
+
+        assert_eq!(streamed, encrypt(&key, &nonce, "entry", &data).unwrap());
+    }
+
+    #[test]
+    fn decrypt_chunks_from_middle() {
+        let key = test_key(3);
+        let nonce = new_nonce();
+        let chunk = CHUNK_SIZE as usize;
+        let data = pattern(chunk * 3 + 5);
+        let sealed = encrypt(&key, &nonce, "entry", &data).unwrap();
+
+        let start = encrypted_offset(1) as usize;
+        let end = encrypted_offset(3) as usize;
+        let plain = decrypt_chunks(&key, &nonce, "entry", sealed.len() as u64, 1, &sealed[start..end]).unwrap();
+        assert_eq!(plain, &data[chunk..chunk * 3]);
+
+        // 마지막 청크 (표시가 맞아야 복호화됨)
+        let plain = decrypt_chunks(&key, &nonce, "entry", sealed.len() as u64, 3, &sealed[end..]).unwrap();
+        assert_eq!(plain, &data[chunk * 3..]);
+    }
+
+    #[test]
+    fn detects_tampering() {
+        let key = test_key(4);
+        let nonce = new_nonce();
+        let data = pattern(CHUNK_SIZE as usize * 2 + 100);
+        let sealed = encrypt(&key, &nonce, "entry", &data).unwrap();
+
+        // 다른 키, 다른 엔트리 ID(AAD), 다른 nonce
+        assert!(decrypt(&test_key(5), &nonce, "entry", &sealed).is_err());
+        assert!(decrypt(&key, &nonce, "other", &sealed).is_err());
+        assert!(decrypt(&key, &new_nonce(), "entry", &sealed).is_err());
+
+        // 바이트 변조
+        let mut flipped = sealed.clone();
+        flipped[10] ^= 1;
+        assert!(decrypt(&key, &nonce, "entry", &flipped).is_err());
+
+        // 마지막 청크를 잘라내면 앞 청크가 마지막 표시와 맞지 않음
+        let truncated = &sealed[..encrypted_offset(2) as usize];
+        assert!(decrypt(&key, &nonce, "entry", truncated).is_err());
+
+        // 태그보다 짧은 데이터
+        assert!(decrypt(&key, &nonce, "entry", &sealed[..TAG_SIZE as usize - 1]).is_err());
+    }
+
+    #[test]
+    fn parses_hex_keys_and_nonces() {
+        let hex = "ab".repeat(KEY_SIZE);
+        let key = PayloadKey::from_hex(&format!(" {}\n", hex)).unwrap();
+        assert_eq!(key.key_id(), PayloadKey::from_hex(&hex.to_uppercase()).unwrap().key_id());
+        assert_eq!(key.key_id().len(), 16);
+        assert!(!hex.contains(key.key_id()));
+
+        assert!(PayloadKey::from_hex(&hex[..KEY_SIZE * 2 - 2]).is_err());
+        assert!(PayloadKey::from_hex(&format!("{}0", hex)).is_err());
+        assert!(PayloadKey::from_hex(&"zz".repeat(KEY_SIZE)).is_err());
+
+        assert_eq!(new_nonce().len(), NONCE_PREFIX_SIZE * 2);
+        assert!(parse_nonce("0011").is_err());
+        assert_eq!(from_hex(&to_hex(&[0, 1, 254, 255])).unwrap(), [0, 1, 254, 255]);
+    }
+
+    #[test]
+    fn build_key_requires_opt_in() {
+        if PayloadKey::compiled().is_some() {
+            return;
+        }
+        assert!(resolve_build_key(None, false).is_err());
+        let (key, hex) = resolve_build_key(None, true).unwrap();
+        assert_eq!(PayloadKey::from_hex(&hex.unwrap()).unwrap().key_id(), key.key_id());
+    }
+}
@@ -0,0 +1,248 @@
+// 내장 데이터 서명 (QA 승인 이후 변경되지 않았음을 증명)
+//
+// 매니페스트에는 프로젝트 JSON과 모든 미디어의 SHA-256이 들어 있으므로, 빌드 시
+// 매니페스트(서명 필드 제외)를 Ed25519로 서명하면 내장된 모든 내용이 서명으로 보호된다.
+// 확인은 매니페스트 서명 → 각 엔트리 해시 순서로 한다.
+// 템플릿 빌드 시 TUTORIAL_PAYLOAD_VERIFY_KEY(서명 공개 키 hex)를 컴파일해 넣으면 제품은
+// 그 키로 서명된 데이터만 열고, 각 미디어는 처음 읽을 때 해시를 확인한다.
+// 라이선스 블록(license)도 같은 Ed25519 키 파일/hex 해석 함수를 사용한다.
+
+use std::fs;
+use std::path::Path;
+
+use ed25519_dalek::{Signature, Signer, SigningKey, VerifyingKey};
+use serde::{Deserialize, Serialize};
+
+use crate::payload::{format_corrupt_entries, BuildManifest, IntegrityReport, Payload, APP_ICON_ENTRY_ID, PROJECT_JSON_ENTRY_ID};
+use crate::payload_crypto::{from_hex, to_hex};
+
+// 템플릿에 컴파일해 넣는 서명 확인용 공개 키 (hex)
+const TRUSTED_VERIFY_KEY: Option<&str> = option_env!("TUTORIAL_PAYLOAD_VERIFY_KEY");
+
+// 라이선스 등 같은 키로 만든 다른 서명과 구분하기 위한 접두사
+const SIGNATURE_CONTEXT: &[u8] = b"tutorial-maker payload signature v1\n";
+
+// 매니페스트에 들어가는 서명
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PayloadSignature {
+    // 서명한 공개 키와 서명 (hex)
+    pub public_key: String,
+    pub signature: String,
+}
+
+// 서명 확인 결과 (verify 명령)
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignatureReport {
+    pub public_key: String,
+    pub integrity: IntegrityReport,
+}
+
+// 서명 키를 읽어 둔 서명자
+pub struct PayloadSigner {
+    key: SigningKey,
+}
+
+impl PayloadSigner {
+    // Ed25519 서명 키 파일 (64자리 hex 시드)
+    pub fn load(path: &Path) -> Result<PayloadSigner, String> {
+        Ok(PayloadSigner { key: load_signing_key(path)? })
+    }
+
+    // 제품 템플릿에 컴파일해 넣을 공개 키 (hex)
+    pub fn public_key_hex(&self) -> String {
+        to_hex(self.key.verifying_key().as_bytes())
+    }
+
+    // 완성된 매니페스트(해시, 라이선스 포함)에 대한 서명
+    pub fn sign(&self, manifest: &BuildManifest) -> Result<PayloadSignature, String> {
+        let message = signed_message(manifest)?;
+        Ok(PayloadSignature {
+            public_key: self.public_key_hex(),
+            signature: to_hex(&self.key.sign(&message).to_bytes()),
+        })
+    }
+}
+
+// 서명 대상: 서명 필드를 뺀 매니페스트 JSON
+fn signed_message(manifest: &BuildManifest) -> Result<Vec<u8>, String> {
+    let unsigned = BuildManifest { signature: None, ..manifest.clone() };
+    let json = serde_json::to_vec(&unsigned)
+        .map_err(|e| format!("매니페스트 직렬화 실패: {}", e))?;
+    Ok([SIGNATURE_CONTEXT, &json].concat())
+}
+
+// Ed25519 서명 키 파일 읽기 (32바이트 시드, 64자리 hex)
+pub fn load_signing_key(path: &Path) -> Result<SigningKey, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("서명 키 파일 읽기 실패 ({}): {}", path.display(), e))?;
+    from_hex(content.trim())
+        .and_then(|b| <[u8; 32]>::try_from(b).ok())
+        .map(|seed| SigningKey::from_bytes(&seed))
+        .ok_or_else(|| "서명 키 형식이 잘못되었습니다. (64자리 hex 필요)".to_string())
+}
+
+// Ed25519 공개 키 hex 해석
+pub fn parse_verifying_key(value: &str) -> Result<VerifyingKey, String> {
+    from_hex(value.trim())
+        .and_then(|b| <[u8; 32]>::try_from(b).ok())
+        .and_then(|bytes| VerifyingKey::from_bytes(&bytes).ok())
+        .ok_or_else(|| "공개 키 형식이 잘못되었습니다.".to_string())
+}
+
+// Ed25519 서명 hex 해석
+pub fn parse_signature(value: &str) -> Result<Signature, String> {
+    from_hex(value)
+        .and_then(|b| <[u8; 64]>::try_from(b).ok())
+        .map(|bytes| Signature::from_bytes(&bytes))
+        .ok_or_else(|| "서명 형식이 잘못되었습니다.".to_string())
+}
+
+// 템플릿에 컴파일된 공개 키
+fn trusted_key() -> Result<Option<VerifyingKey>, String> {
+    TRUSTED_VERIFY_KEY.map(parse_verifying_key).transpose()
+        .map_err(|e| format!("템플릿에 설정된 서명 공개 키가 잘못되었습니다: {}", e))
+}
+
+// 매니페스트 서명 확인 (해시가 없는 엔트리는 서명으로 보호되지 않으므로 거부)
+fn verify_manifest(manifest: &BuildManifest, key: &VerifyingKey) -> Result<(), String> {
+    let signature = manifest.signature.as_ref()
+        .ok_or_else(|| "서명되지 않은 튜토리얼입니다.".to_string())?;
+
+    if parse_verifying_key(&signature.public_key)? != *key {
+        return Err(format!("다른 키로 서명된 튜토리얼입니다. (서명 키: {})", signature.public_key));
+    }
+    let message = signed_message(manifest)?;
+    key.verify_strict(&message, &parse_signature(&signature.signature)?)
+        .map_err(|_| "서명이 올바르지 않습니다. 승인 이후 튜토리얼이 변경되었습니다.".to_string())?;
+
+    let unhashed = manifest.media.iter()
+        .find(|m| m.sha256.is_none())
+        .map(|m| m.id.as_str())
+        .or(manifest.project_json_sha256.is_none().then_some(PROJECT_JSON_ENTRY_ID))
+        .or((manifest.app_icon_offset.is_some() && manifest.app_icon_sha256.is_none()).then_some(APP_ICON_ENTRY_ID));
+    if let Some(id) = unhashed {
+        return Err(format!("해시가 없는 데이터가 있어 서명을 확인할 수 없습니다: {}", id));
+    }
+
+    Ok(())
+}
+
+// 제품 시작 시 서명 확인 (컴파일된 공개 키가 없으면 확인하지 않음)
+// 반환값: 서명이 확인되었는지 (확인되면 미디어를 읽을 때마다 해시를 검증해야 함)
+pub fn check_signature(manifest: &BuildManifest) -> Result<bool, String> {
+    let Some(key) = trusted_key()? else {
+        return Ok(false);
+    };
+    verify_manifest(manifest, &key)?;
+    Ok(true)
+}
+
+// 매니페스트 서명과 모든 엔트리 해시 확인
+// public_key가 없으면 컴파일된 공개 키 사용
+pub fn verify_payload(payload: &Payload, public_key: Option<&str>) -> Result<SignatureReport, String> {
+    let key = match public_key {
+        Some(hex) => parse_verifying_key(hex)?,
+        None => trusted_key()?.ok_or_else(|| {
+            "서명을 확인할 공개 키가 없습니다. 공개 키를 지정하거나 TUTORIAL_PAYLOAD_VERIFY_KEY를 설정해 빌드해주세요.".to_string()
+        })?,
+    };
+
+    verify_manifest(payload.manifest()?, &key)?;
+
+    let integrity = payload.verify(true)?;
+    if !integrity.corrupt.is_empty() {
+        return Err(format_corrupt_entries(&integrity.corrupt));
+    }
+
+    Ok(SignatureReport {
+        public_key: to_hex(key.as_bytes()),
+        integrity,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_signer(seed: u8) -> PayloadSigner {
+        PayloadSigner { key: SigningKey::from_bytes(&[seed; 32]) }
+    }
+
+    fn signed_manifest(signer: &PayloadSigner) -> BuildManifest {
+        let mut manifest: BuildManifest = serde_json::from_str(
+            r#"{"project_json_offset":0,"project_json_size":10,"project_json_sha256":"aa",
+                "media":[{"id":"m1","name":"intro.mp4","mime_type":"video/mp4","offset":10,"size":5,"sha256":"bb"}]}"#,
+        ).unwrap();
+        manifest.signature = Some(signer.sign(&manifest).unwrap());
+        manifest
+    }
+
+    fn key_of(signer: &PayloadSigner) -> VerifyingKey {
+        parse_verifying_key(&signer.public_key_hex()).unwrap()
+    }
+
+    #[test]
+    fn verifies_signed_manifest() {
+        let signer = test_signer(1);
+        let manifest = signed_manifest(&signer);
+        assert!(verify_manifest(&manifest, &key_of(&signer)).is_ok());
+
+        // 매니페스트 JSON을 다시 읽어도 서명 대상이 같아야 함
+        let json = serde_json::to_vec(&manifest).unwrap();
+        let reparsed: BuildManifest = serde_json::from_slice(&json).unwrap();
+        assert!(verify_manifest(&reparsed, &key_of(&signer)).is_ok());
+    }
+
+    #[test]
+    fn rejects_modified_manifest() {
+        let signer = test_signer(1);
+        let manifest = signed_manifest(&signer);
+        let key = key_of(&signer);
+
+        let mut media_hash = manifest.clone();
+        media_hash.media[0].sha256 = Some("cc".to_string());
+        assert!(verify_manifest(&media_hash, &key).unwrap_err().contains("변경"));
+
+        let mut options = manifest.clone();
+        options.verify_on_startup = true;
+        assert!(verify_manifest(&options, &key).is_err());
+
+        let mut unsigned = manifest.clone();
+        unsigned.signature = None;
+        assert!(verify_manifest(&unsigned, &key).unwrap_err().contains("서명되지 않은"));
+
+        // 다른 키로 서명한 매니페스트
+        assert!(verify_manifest(&signed_manifest(&test_signer(2)), &key).unwrap_err().contains("다른 키"));
+    }
+
+    #[test]
+    fn rejects_entries_without_hash() {
+        let signer = test_signer(1);
+        let mut manifest = signed_manifest(&signer);
+        manifest.media[0].sha256 = None;
+        manifest.signature = Some(signer.sign(&manifest).unwrap());
+
+        let error = verify_manifest(&manifest, &key_of(&signer)).unwrap_err();
+        assert!(error.contains("m1"));
+    }
+
+    #[test]
+    fn signature_differs_from_plain_manifest_signature() {
+        // 같은 키로 만든 다른 서명(라이선스 등)과 섞이지 않도록 접두사를 붙여 서명
+        let signer = test_signer(1);
+        let manifest = signed_manifest(&signer);
+        let plain = serde_json::to_vec(&BuildManifest { signature: None, ..manifest.clone() }).unwrap();
+        let signature = parse_signature(&manifest.signature.unwrap().signature).unwrap();
+        assert!(key_of(&signer).verify_strict(&plain, &signature).is_err());
+    }
+
+    #[test]
+    fn parses_key_and_signature_hex() {
+        assert!(parse_verifying_key(&test_signer(1).public_key_hex()).is_ok());
+        assert!(parse_verifying_key("abcd").is_err());
+        assert!(parse_verifying_key(&"zz".repeat(32)).is_err());
+        assert!(parse_signature(&"00".repeat(64)).is_ok());
+        assert!(parse_signature(&"00".repeat(63)).is_err());
+    }
+}
@@ -0,0 +1,757 @@
+// PE(Windows exe) 리소스 편집기
+//
+// 외부 도구(rcedit) 없이 exe의 RT_ICON / RT_GROUP_ICON 리소스를 교체한다.
+// 기존 리소스 트리(버전 정보, 매니페스트 등)를 모두 읽어 아이콘만 바꾼 뒤
+// 새 리소스 섹션으로 다시 써서, 어느 OS에서든 Windows 제품 exe를 만들 수 있다.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use ico::IconDir;
+
+const RT_ICON: u32 = 3;
+const RT_GROUP_ICON: u32 = 14;
+
+// 데이터 디렉토리 인덱스
+const DIR_RESOURCE: usize = 2;
+const DIR_SECURITY: usize = 4;
+
+const SECTION_HEADER_SIZE: usize = 40;
+// PE 명세상 FileAlignment 최대값
+const MAX_FILE_ALIGNMENT: u32 = 0x10000;
+// IMAGE_SCN_CNT_INITIALIZED_DATA | IMAGE_SCN_MEM_READ
+const RSRC_CHARACTERISTICS: u32 = 0x4000_0040;
+
+// 리소스 이름: 문자열 이름이 ID보다 먼저 정렬되어야 하므로 variant 순서가 중요함
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum ResourceName {
+    Name(Vec<u16>),
+    Id(u32),
+}
+
+#[derive(Debug, Clone)]
+struct ResourceData {
+    code_page: u32,
+    data: Vec<u8>,
+}
+
+// 타입 -> 이름 -> 언어 -> 데이터
+type ResourceTree = BTreeMap<ResourceName, BTreeMap<ResourceName, BTreeMap<u32, ResourceData>>>;
+
+#[derive(Debug, Clone)]
+struct Section {
+    header_offset: usize,
+    virtual_size: u32,
+    virtual_address: u32,
+    raw_size: u32,
+    raw_offset: u32,
+}
+
+// PE 헤더에서 필요한 정보
+struct PeLayout {
+    coff_offset: usize,
+    optional_offset: usize,
+    data_dir_offset: usize,
+    section_table_offset: usize,
+    section_alignment: u32,
+    file_alignment: u32,
+    size_of_headers: u32,
+    sections: Vec<Section>,
+}
+
+// 손상된 exe의 오프셋/크기로 범위 밖을 읽지 않도록 모든 읽기는 여기서 범위 확인
+fn read_bytes(buf: &[u8], offset: usize, len: usize) -> Result<&[u8], String> {
+    offset.checked_add(len)
+        .and_then(|end| buf.get(offset..end))
+        .ok_or_else(|| format!("PE 파일이 손상되었습니다 (offset {})", offset))
+}
+
+fn read_u16(buf: &[u8], offset: usize) -> Result<u16, String> {
+    read_bytes(buf, offset, 2).map(|b| u16::from_le_bytes([b[0], b[1]]))
+}
+
+fn read_u32(buf: &[u8], offset: usize) -> Result<u32, String> {
+    read_bytes(buf, offset, 4).map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+// 헤더 값으로 계산한 위치/크기가 u32를 넘으면 손상된 exe
+fn checked_sum(a: u32, b: u32) -> Result<u32, String> {
+    a.checked_add(b)
+        .ok_or_else(|| "PE 헤더의 위치/크기 값이 올바르지 않습니다.".to_string())
+}
+
+fn write_u16(buf: &mut [u8], offset: usize, value: u16) {
+    buf[offset..offset + 2].copy_from_slice(&value.to_le_bytes());
+}
+
+fn write_u32(buf: &mut [u8], offset: usize, value: u32) {
+    buf[offset..offset + 4].copy_from_slice(&value.to_le_bytes());
+}
+
+fn align_up(value: u32, alignment: u32) -> Result<u32, String> {
+    if alignment == 0 {
+        return Ok(value);
+    }
+    value.div_ceil(alignment)
+        .checked_mul(alignment)
+        .ok_or_else(|| "PE 헤더의 위치/크기 값이 올바르지 않습니다.".to_string())
+}
+
+fn parse_layout(pe: &[u8]) -> Result<PeLayout, String> {
+    if pe.get(0..2) != Some(b"MZ") {
+        return Err("유효한 exe 파일이 아닙니다 (MZ 시그니처 없음)".to_string());
+    }
+
+    let pe_offset = read_u32(pe, 0x3C)? as usize;
+    if read_bytes(pe, pe_offset, 4).ok() != Some(b"PE\0\0") {
+        return Err("유효한 exe 파일이 아닙니다 (PE 시그니처 없음)".to_string());
+    }
+
+    let coff_offset = pe_offset + 4;
+    let number_of_sections = read_u16(pe, coff_offset + 2)? as usize;
+    let size_of_optional_header = read_u16(pe, coff_offset + 16)? as usize;
+    let optional_offset = coff_offset + 20;
+
+    let (data_dir_offset, rva_count_offset) = match read_u16(pe, optional_offset)? {
+        0x10b => (optional_offset + 96, optional_offset + 92),
+        0x20b => (optional_offset + 112, optional_offset + 108),
+        magic => return Err(format!("지원하지 않는 PE 형식입니다 (magic 0x{:x})", magic)),
+    };
+
+    if (read_u32(pe, rva_count_offset)? as usize) <= DIR_SECURITY {
+        return Err("PE 데이터 디렉토리가 부족합니다.".to_string());
+    }
+    // 다시 쓸 때 갱신하는 체크섬 필드와 데이터 디렉토리까지 파일 안에 있어야 함
+    read_u32(pe, optional_offset + 64)?;
+    read_u32(pe, data_dir_offset + DIR_SECURITY * 8 + 4)?;
+
+    let section_table_offset = optional_offset + size_of_optional_header;
+    let mut sections = Vec::with_capacity(number_of_sections);
+    for i in 0..number_of_sections {
+        let header_offset = section_table_offset + i * SECTION_HEADER_SIZE;
+        read_bytes(pe, header_offset, SECTION_HEADER_SIZE)?;
+        sections.push(Section {
+            header_offset,
+            virtual_size: read_u32(pe, header_offset + 8)?,
+            virtual_address: read_u32(pe, header_offset + 12)?,
+            raw_size: read_u32(pe, header_offset + 16)?,
+            raw_offset: read_u32(pe, header_offset + 20)?,
+        });
+    }
+
+    // 정렬 값은 2의 거듭제곱 (파일 정렬은 최대 64KB), 손상된 값으로 거대한 출력을 만들지 않도록 확인
+    let section_alignment = read_u32(pe, optional_offset + 32)?;
+    let file_alignment = read_u32(pe, optional_offset + 36)?;
+    if !file_alignment.is_power_of_two() || file_alignment > MAX_FILE_ALIGNMENT
+        || !section_alignment.is_power_of_two() || section_alignment < file_alignment
+    {
+        return Err(format!(
+            "PE 정렬 값이 올바르지 않습니다 (섹션 0x{:x}, 파일 0x{:x})",
+            section_alignment, file_alignment
+        ));
+    }
+
+    Ok(PeLayout {
+        coff_offset,
+        optional_offset,
+        data_dir_offset,
+        section_table_offset,
+        section_alignment,
+        file_alignment,
+        size_of_headers: read_u32(pe, optional_offset + 60)?,
+        sections,
+    })
+}
+
+impl PeLayout {
+    fn data_dir(&self, pe: &[u8], index: usize) -> Result<(u32, u32), String> {
+        let offset = self.data_dir_offset + index * 8;
+        Ok((read_u32(pe, offset)?, read_u32(pe, offset + 4)?))
+    }
+
+    // RVA → 파일 위치 (섹션의 파일 데이터 밖이거나 파일 끝을 넘으면 None)
+    fn rva_to_offset(&self, rva: u32, file_len: usize) -> Option<usize> {
+        self.sections.iter().find_map(|s| {
+            let size = s.virtual_size.max(s.raw_size);
+            if rva >= s.virtual_address && rva - s.virtual_address < size {
+                let delta = rva - s.virtual_address;
+                if delta >= s.raw_size {
+                    return None;
+                }
+                s.raw_offset.checked_add(delta)
+                    .map(|offset| offset as usize)
+                    .filter(|offset| *offset < file_len)
+            } else {
+                None
+            }
+        })
+    }
+
+    // 다시 쓸 때 갱신하는 헤더 필드(체크섬, 데이터 디렉토리, 섹션 테이블)가 끝나는 위치
+    fn headers_end(&self) -> usize {
+        (self.optional_offset + 68)
+            .max(self.data_dir_offset + (DIR_SECURITY + 1) * 8)
+            .max(self.section_table_offset + self.sections.len() * SECTION_HEADER_SIZE)
+    }
+
+    // 모든 섹션의 파일 데이터가 끝나는 위치 (섹션이 없으면 헤더 끝)
+    fn raw_end(&self) -> Result<u32, String> {
+        self.sections.iter()
+            .map(|s| checked_sum(s.raw_offset, s.raw_size))
+            .collect::<Result<Vec<_>, _>>()
+            .map(|ends| ends.into_iter().max().unwrap_or(self.size_of_headers))
+    }
+}
+
+// PE 이미지(헤더 + 모든 섹션)가 끝나는 파일 위치, 그 뒤는 overlay
+// headers: 파일 앞부분 (섹션 테이블까지 포함하면 충분)
+pub fn image_end(headers: &[u8]) -> Result<u64, String> {
+    let layout = parse_layout(headers)?;
+    let sections_end = layout.sections.iter()
+        .map(|s| s.raw_offset as u64 + s.raw_size as u64)
+        .max()
+        .unwrap_or(0);
+
+    Ok(sections_end.max(layout.size_of_headers as u64))
+}
+
+// 리소스 디렉토리 엔트리의 이름 읽기
+fn read_resource_name(rsrc: &[u8], raw: u32) -> Result<ResourceName, String> {
+    if raw & 0x8000_0000 == 0 {
+        return Ok(ResourceName::Id(raw));
+    }
+
+    let offset = (raw & 0x7FFF_FFFF) as usize;
+    let len = read_u16(rsrc, offset)? as usize;
+    let name = (0..len)
+        .map(|i| read_u16(rsrc, offset + 2 + i * 2))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(ResourceName::Name(name))
+}
+
+// 리소스 디렉토리 하나의 (이름, 엔트리 값) 목록 읽기
+fn read_directory(rsrc: &[u8], offset: usize) -> Result<Vec<(ResourceName, u32)>, String> {
+    let named = read_u16(rsrc, offset + 12)? as usize;
+    let ids = read_u16(rsrc, offset + 14)? as usize;
+
+    (0..named + ids)
+        .map(|i| {
+            let entry = offset + 16 + i * 8;
+            let name = read_resource_name(rsrc, read_u32(rsrc, entry)?)?;
+            Ok((name, read_u32(rsrc, entry + 4)?))
+        })
+        .collect()
+}
+
+fn read_resource_tree(pe: &[u8], layout: &PeLayout, rsrc_rva: u32) -> Result<ResourceTree, String> {
+    let rsrc = layout.rva_to_offset(rsrc_rva, pe.len())
+        .and_then(|offset| pe.get(offset..))
+        .ok_or_else(|| "리소스 섹션을 찾을 수 없습니다.".to_string())?;
+
+    // 트리 깊이는 타입 / 이름 / 언어 3단계로 고정
+    let subdirectory = |value: u32| -> Result<usize, String> {
+        if value & 0x8000_0000 == 0 {
+            return Err("리소스 트리 구조가 올바르지 않습니다.".to_string());
+        }
+        Ok((value & 0x7FFF_FFFF) as usize)
+    };
+
+    let mut tree = ResourceTree::new();
+    for (type_name, type_value) in read_directory(rsrc, 0)? {
+        let names = tree.entry(type_name).or_default();
+
+        for (name, name_value) in read_directory(rsrc, subdirectory(type_value)?)? {
+            let langs = names.entry(name).or_default();
+
+            for (lang, data_entry) in read_directory(rsrc, subdirectory(name_value)?)? {
+                let ResourceName::Id(lang) = lang else {
+                    return Err("리소스 언어 ID가 올바르지 않습니다.".to_string());
+                };
+                if data_entry & 0x8000_0000 != 0 {
+                    return Err("리소스 트리 구조가 올바르지 않습니다.".to_string());
+                }
+
+                let data_entry = data_entry as usize;
+                let data_rva = read_u32(rsrc, data_entry)?;
+                let data_size = read_u32(rsrc, data_entry + 4)? as usize;
+                let code_page = read_u32(rsrc, data_entry + 8)?;
+
+                let data_offset = layout.rva_to_offset(data_rva, pe.len())
+                    .ok_or_else(|| format!("리소스 데이터 위치가 올바르지 않습니다 (RVA 0x{:x})", data_rva))?;
+                let data = read_bytes(pe, data_offset, data_size)
+                    .map_err(|_| "리소스 데이터가 잘렸습니다.".to_string())?
+                    .to_vec();
+
+                langs.insert(lang, ResourceData { code_page, data });
+            }
+        }
+    }
+
+    Ok(tree)
+}
+
+// 리소스 트리를 섹션 데이터로 직렬화 (section_rva: 새 섹션의 RVA)
+fn write_resource_tree(tree: &ResourceTree, section_rva: u32) -> Result<Vec<u8>, String> {
+    let dir_size = |count: usize| 16 + count * 8;
+
+    // 1. 디렉토리 테이블 영역 크기
+    let mut tables_size = dir_size(tree.len());
+    for names in tree.values() {
+        tables_size += dir_size(names.len());
+        for langs in names.values() {
+            tables_size += dir_size(langs.len());
+        }
+    }
+
+    // 2. 문자열 이름 영역
+    let mut string_offsets: BTreeMap<&[u16], usize> = BTreeMap::new();
+    let mut strings = Vec::new();
+    let all_names = tree.iter().flat_map(|(t, names)| std::iter::once(t).chain(names.keys()));
+    for name in all_names {
+        if let ResourceName::Name(units) = name {
+            string_offsets.entry(units.as_slice()).or_insert_with(|| {
+                let offset = tables_size + strings.len();
+                strings.extend_from_slice(&(units.len() as u16).to_le_bytes());
+                for unit in units {
+                    strings.extend_from_slice(&unit.to_le_bytes());
+                }
+                offset
+            });
+        }
+    }
+    while (tables_size + strings.len()) % 4 != 0 {
+        strings.push(0);
+    }
+
+    // 3. 데이터 엔트리와 실제 데이터 위치
+    let data_count: usize = tree.values()
+        .flat_map(|names| names.values())
+        .map(|langs| langs.len())
+        .sum();
+    let entries_offset = tables_size + strings.len();
+    let mut data_offset = entries_offset + data_count * 16;
+
+    let mut out = vec![0u8; data_offset];
+    out[tables_size..entries_offset].copy_from_slice(&strings);
+
+    let name_value = |name: &ResourceName| match name {
+        ResourceName::Id(id) => *id,
+        ResourceName::Name(units) => 0x8000_0000 | string_offsets[units.as_slice()] as u32,
+    };
+    let write_dir_header = |out: &mut Vec<u8>, offset: usize, keys: Vec<&ResourceName>| {
+        let named = keys.iter().filter(|k| matches!(k, ResourceName::Name(_))).count();
+        write_u16(out, offset + 12, named as u16);
+        write_u16(out, offset + 14, (keys.len() - named) as u16);
+    };
+
+    let mut next_table = dir_size(tree.len());
+    let mut next_entry = entries_offset;
+    write_dir_header(&mut out, 0, tree.keys().collect());
+
+    for (type_index, (type_name, names)) in tree.iter().enumerate() {
+        let type_table = next_table;
+        next_table += dir_size(names.len());
+        write_u32(&mut out, 16 + type_index * 8, name_value(type_name));
+        write_u32(&mut out, 16 + type_index * 8 + 4, 0x8000_0000 | type_table as u32);
+        write_dir_header(&mut out, type_table, names.keys().collect());
+
+        for (name_index, (name, langs)) in names.iter().enumerate() {
+            let lang_table = next_table;
+            next_table += dir_size(langs.len());
+            let entry = type_table + 16 + name_index * 8;
+            write_u32(&mut out, entry, name_value(name));
+            write_u32(&mut out, entry + 4, 0x8000_0000 | lang_table as u32);
+
+            let lang_keys: Vec<ResourceName> = langs.keys().map(|l| ResourceName::Id(*l)).collect();
+            write_dir_header(&mut out, lang_table, lang_keys.iter().collect());
+
+            for (lang_index, (lang, resource)) in langs.iter().enumerate() {
+                let entry = lang_table + 16 + lang_index * 8;
+                write_u32(&mut out, entry, *lang);
+                write_u32(&mut out, entry + 4, next_entry as u32);
+
+                // 데이터는 8바이트 정렬
+                data_offset = align_up(data_offset as u32, 8)? as usize;
+                write_u32(&mut out, next_entry, checked_sum(section_rva, data_offset as u32)?);
+                write_u32(&mut out, next_entry + 4, resource.data.len() as u32);
+                write_u32(&mut out, next_entry + 8, resource.code_page);
+                next_entry += 16;
+
+                out.resize(data_offset, 0);
+                out.extend_from_slice(&resource.data);
+                data_offset = out.len();
+            }
+        }
+    }
+
+    Ok(out)
+}
+
+// 기존 아이콘 리소스를 IconDir의 이미지들로 교체
+fn replace_icon_resources(tree: &mut ResourceTree, icon_dir: &IconDir) -> Result<(), String> {
+    if icon_dir.entries().is_empty() {
+        return Err("아이콘 이미지가 없습니다.".to_string());
+    }
+
+    // 기존 첫 번째 아이콘 그룹의 이름/언어를 유지 (Explorer는 첫 그룹을 exe 아이콘으로 사용)
+    let (group_name, lang) = tree.get(&ResourceName::Id(RT_GROUP_ICON))
+        .and_then(|groups| groups.iter().next())
+        .map(|(name, langs)| (name.clone(), langs.keys().next().copied().unwrap_or(0)))
+        .unwrap_or((ResourceName::Id(1), 0));
+
+    tree.remove(&ResourceName::Id(RT_ICON));
+    tree.remove(&ResourceName::Id(RT_GROUP_ICON));
+
+    // GRPICONDIR 헤더: reserved, type(1 = icon), count
+    let mut group = Vec::new();
+    group.extend_from_slice(&0u16.to_le_bytes());
+    group.extend_from_slice(&1u16.to_le_bytes());
+    group.extend_from_slice(&(icon_dir.entries().len() as u16).to_le_bytes());
+
+    let mut icons = BTreeMap::new();
+    for (index, entry) in icon_dir.entries().iter().enumerate() {
+        let icon_id = index as u16 + 1;
+
+        // GRPICONDIRENTRY: 256px는 0으로 기록
+        group.push(entry.width().min(256) as u8);
+        group.push(entry.height().min(256) as u8);
+        group.push(0); // color count
+        group.push(0); // reserved
+        group.extend_from_slice(&1u16.to_le_bytes()); // planes
+        group.extend_from_slice(&entry.bits_per_pixel().to_le_bytes());
+        group.extend_from_slice(&(entry.data().len() as u32).to_le_bytes());
+        group.extend_from_slice(&icon_id.to_le_bytes());
+
+        let mut langs = BTreeMap::new();
+        langs.insert(lang, ResourceData { code_page: 0, data: entry.data().to_vec() });
+        icons.insert(ResourceName::Id(icon_id as u32), langs);
+    }
+
+    let mut group_langs = BTreeMap::new();
+    group_langs.insert(lang, ResourceData { code_page: 0, data: group });
+    let mut groups = BTreeMap::new();
+    groups.insert(group_name, group_langs);
+
+    tree.insert(ResourceName::Id(RT_ICON), icons);
+    tree.insert(ResourceName::Id(RT_GROUP_ICON), groups);
+
+    Ok(())
+}
+
+// PE 체크섬 재계산 (16비트 워드 합 + 파일 크기)
+fn update_checksum(pe: &mut [u8], optional_offset: usize) {
+    let checksum_offset = optional_offset + 64;
+    write_u32(pe, checksum_offset, 0);
+
+    let mut sum: u64 = 0;
+    for chunk in pe.chunks(2) {
+        let word = if chunk.len() == 2 {
+            u16::from_le_bytes([chunk[0], chunk[1]])
+        } else {
+            chunk[0] as u16
+        };
+        sum += word as u64;
+        sum = (sum & 0xFFFF) + (sum >> 16);
+    }
+    sum = (sum & 0xFFFF) + (sum >> 16);
+
+    write_u32(pe, checksum_offset, (sum as u32).wrapping_add(pe.len() as u32));
+}
+
+// exe 바이트의 아이콘 리소스를 교체한 새 exe 바이트 반환
+pub fn replace_icon_in_pe(pe: &[u8], icon_dir: &IconDir) -> Result<Vec<u8>, String> {
+    let layout = parse_layout(pe)?;
+
+    let mut tree = match layout.data_dir(pe, DIR_RESOURCE)? {
+        (0, _) => ResourceTree::new(),
+        (rsrc_rva, _) => read_resource_tree(pe, &layout, rsrc_rva)?,
+    };
+    replace_icon_resources(&mut tree, icon_dir)?;
+
+    let last_section = layout.sections.iter()
+        .max_by_key(|s| s.virtual_address)
+        .ok_or_else(|| "PE 섹션이 없습니다.".to_string())?;
+    let raw_end = layout.raw_end()? as usize;
+    if raw_end > pe.len() {
+        return Err("exe 파일이 잘렸습니다 (섹션 데이터가 파일 끝을 넘음)".to_string());
+    }
+
+    // 서명(인증서)은 파일 끝에 붙어 있으며, 내용이 바뀌면 어차피 무효가 되므로 제거
+    // (섹션 데이터 안을 가리키면 잘라낼 수 없으므로 손상된 exe로 처리)
+    let mut out = pe.to_vec();
+    let (cert_offset, cert_size) = layout.data_dir(pe, DIR_SECURITY)?;
+    if cert_size != 0 {
+        let cert_offset = cert_offset as usize;
+        if cert_offset < raw_end.max(layout.headers_end()) || cert_offset > out.len() {
+            return Err(format!("exe 서명 위치가 올바르지 않습니다 (offset {})", cert_offset));
+        }
+        out.truncate(cert_offset);
+        write_u32(&mut out, layout.data_dir_offset + DIR_SECURITY * 8, 0);
+        write_u32(&mut out, layout.data_dir_offset + DIR_SECURITY * 8 + 4, 0);
+    }
+    if out.len() > raw_end {
+        return Err("exe 끝에 추가 데이터가 있어 아이콘을 변경할 수 없습니다. (이미 빌드된 exe인지 확인하세요)".to_string());
+    }
+
+    // 리소스 섹션이 마지막 섹션이면 그 자리에 다시 쓰고, 아니면 새 섹션을 추가
+    let (rsrc_rva, _) = layout.data_dir(pe, DIR_RESOURCE)?;
+    let reuse_last = rsrc_rva != 0 && rsrc_rva == last_section.virtual_address
+        && checked_sum(last_section.raw_offset, last_section.raw_size)? as usize == raw_end;
+
+    let (header_offset, section_rva, raw_offset) = if reuse_last {
+        (last_section.header_offset, last_section.virtual_address, last_section.raw_offset)
+    } else {
+        let header_offset = layout.section_table_offset + layout.sections.len() * SECTION_HEADER_SIZE;
+        let first_raw = layout.sections.iter()
+            .map(|s| s.raw_offset)
+            .filter(|offset| *offset != 0)
+            .min()
+            .unwrap_or(layout.size_of_headers);
+        let header_space = (layout.size_of_headers.min(first_raw) as usize).min(out.len());
+        if header_offset + SECTION_HEADER_SIZE > header_space {
+            return Err("새 리소스 섹션을 추가할 헤더 공간이 없습니다.".to_string());
+        }
+
+        let section_rva = align_up(
+            checked_sum(last_section.virtual_address, last_section.virtual_size.max(last_section.raw_size))?,
+            layout.section_alignment,
+        )?;
+        let raw_offset = align_up(raw_end as u32, layout.file_alignment)?;
+
+        let number_of_sections = layout.sections.len() as u16 + 1;
+        write_u16(&mut out, layout.coff_offset + 2, number_of_sections);
+        out[header_offset..header_offset + SECTION_HEADER_SIZE].fill(0);
+        out[header_offset..header_offset + 8].copy_from_slice(b".rsrc\0\0\0");
+        write_u32(&mut out, header_offset + 36, RSRC_CHARACTERISTICS);
+
+        (header_offset, section_rva, raw_offset)
+    };
+
+    let rsrc = write_resource_tree(&tree, section_rva)?;
+    let virtual_size = u32::try_from(rsrc.len())
+        .map_err(|_| "리소스 섹션이 너무 큽니다.".to_string())?;
+    let raw_size = align_up(virtual_size, layout.file_alignment)?;
+
+    out.resize(raw_offset as usize, 0);
+    out.extend_from_slice(&rsrc);
+    out.resize(checked_sum(raw_offset, raw_size)? as usize, 0);
+
+    // 섹션 헤더 / 데이터 디렉토리 / SizeOfImage 갱신
+    write_u32(&mut out, header_offset + 8, virtual_size);
+    write_u32(&mut out, header_offset + 12, section_rva);
+    write_u32(&mut out, header_offset + 16, raw_size);
+    write_u32(&mut out, header_offset + 20, raw_offset);
+    write_u32(&mut out, layout.data_dir_offset + DIR_RESOURCE * 8, section_rva);
+    write_u32(&mut out, layout.data_dir_offset + DIR_RESOURCE * 8 + 4, virtual_size);
+
+    let size_of_image = align_up(checked_sum(section_rva, virtual_size)?, layout.section_alignment)?;
+    write_u32(&mut out, layout.optional_offset + 56, size_of_image);
+
+    update_checksum(&mut out, layout.optional_offset);
+
+    Ok(out)
+}
+
+// exe 파일의 아이콘을 제자리에서 교체
+pub fn replace_exe_icon(exe_path: &Path, icon_dir: &IconDir) -> Result<(), String> {
+    let pe = fs::read(exe_path)
+        .map_err(|e| format!("exe 파일 읽기 실패: {}", e))?;
+
+    let patched = replace_icon_in_pe(&pe, icon_dir)?;
+
+    fs::write(exe_path, patched)
+        .map_err(|e| format!("exe 파일 쓰기 실패: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use ico::{IconDirEntry, IconImage, ResourceType};
+
+    use super::*;
+
+    const OPTIONAL_OFFSET: usize = 0x58;
+    const DATA_DIR_OFFSET: usize = OPTIONAL_OFFSET + 112;
+    const SECTION_TABLE_OFFSET: usize = OPTIONAL_OFFSET + 240;
+
+    // 헤더(0x200) + .text 섹션 하나(0x200)로 된 최소 PE32+
+    fn minimal_pe() -> Vec<u8> {
+        let mut pe = vec![0u8; 0x400];
+        pe[0..2].copy_from_slice(b"MZ");
+        write_u32(&mut pe, 0x3C, 0x40);
+        pe[0x40..0x44].copy_from_slice(b"PE\0\0");
+        write_u16(&mut pe, 0x44, 0x8664);
+        write_u16(&mut pe, 0x46, 1);
+        write_u16(&mut pe, 0x54, 240);
+        write_u16(&mut pe, OPTIONAL_OFFSET, 0x20b);
+        write_u32(&mut pe, OPTIONAL_OFFSET + 32, 0x1000);
+        write_u32(&mut pe, OPTIONAL_OFFSET + 36, 0x200);
+        write_u32(&mut pe, OPTIONAL_OFFSET + 56, 0x2000);
+        write_u32(&mut pe, OPTIONAL_OFFSET + 60, 0x200);
+        write_u32(&mut pe, OPTIONAL_OFFSET + 108, 16);
+        pe[SECTION_TABLE_OFFSET..SECTION_TABLE_OFFSET + 8].copy_from_slice(b".text\0\0\0");
+        write_u32(&mut pe, SECTION_TABLE_OFFSET + 8, 0x200);
+        write_u32(&mut pe, SECTION_TABLE_OFFSET + 12, 0x1000);
+        write_u32(&mut pe, SECTION_TABLE_OFFSET + 16, 0x200);
+        write_u32(&mut pe, SECTION_TABLE_OFFSET + 20, 0x200);
+        pe
+    }
+
+    fn icon(sizes: &[u32]) -> IconDir {
+        let mut dir = IconDir::new(ResourceType::Icon);
+        for &size in sizes {
+            let image = IconImage::from_rgba_data(size, size, vec![0x80; (size * size * 4) as usize]);
+            dir.add_entry(IconDirEntry::encode(&image).unwrap());
+        }
+        dir
+    }
+
+    fn resource_tree(pe: &[u8]) -> ResourceTree {
+        let layout = parse_layout(pe).unwrap();
+        let (rva, _) = layout.data_dir(pe, DIR_RESOURCE).unwrap();
+        read_resource_tree(pe, &layout, rva).unwrap()
+    }
+
+    fn icon_count(tree: &ResourceTree) -> usize {
+        tree[&ResourceName::Id(RT_ICON)].len()
+    }
+
+    #[test]
+    fn adds_resource_section() {
+        let out = replace_icon_in_pe(&minimal_pe(), &icon(&[16, 32])).unwrap();
+        let layout = parse_layout(&out).unwrap();
+
+        assert_eq!(layout.sections.len(), 2);
+        assert_eq!(layout.sections[1].virtual_address, 0x2000);
+        assert_eq!(layout.sections[1].raw_offset, 0x400);
+        assert_eq!(image_end(&out).unwrap(), out.len() as u64);
+        assert_eq!(read_u32(&out, OPTIONAL_OFFSET + 56).unwrap(), 0x3000);
+
+        let tree = resource_tree(&out);
+        assert_eq!(icon_count(&tree), 2);
+        let group = &tree[&ResourceName::Id(RT_GROUP_ICON)][&ResourceName::Id(1)][&0].data;
+        assert_eq!(read_u16(group, 4).unwrap(), 2);
+    }
+
+    #[test]
+    fn rewrites_existing_resource_section() {
+        let first = replace_icon_in_pe(&minimal_pe(), &icon(&[16, 32, 48])).unwrap();
+        let second = replace_icon_in_pe(&first, &icon(&[16])).unwrap();
+        let layout = parse_layout(&second).unwrap();
+
+        // 마지막 리소스 섹션을 그 자리에 다시 씀
+        assert_eq!(layout.sections.len(), 2);
+        assert!(second.len() < first.len());
+        assert_eq!(icon_count(&resource_tree(&second)), 1);
+        assert_eq!(image_end(&second).unwrap(), second.len() as u64);
+    }
+
+    #[test]
+    fn resource_tree_round_trip() {
+        let name = |s: &str| ResourceName::Name(s.encode_utf16().collect());
+        let resource = |code_page: u32, data: &[u8]| ResourceData { code_page, data: data.to_vec() };
+
+        let mut tree = ResourceTree::new();
+        tree.entry(ResourceName::Id(16)).or_default()
+            .entry(ResourceName::Id(1)).or_default()
+            .insert(1042, resource(0, b"version info"));
+        let named = tree.entry(name("CONFIG")).or_default();
+        named.entry(name("DEFAULT")).or_default().insert(0, resource(1252, b"a"));
+        named.entry(ResourceName::Id(7)).or_default().insert(1033, resource(0, b"odd length"));
+
+        let section_rva = 0x3000;
+        let rsrc = write_resource_tree(&tree, section_rva).unwrap();
+        let layout = PeLayout {
+            coff_offset: 0,
+            optional_offset: 0,
+            data_dir_offset: 0,
+            section_table_offset: 0,
+            section_alignment: 0x1000,
+            file_alignment: 0x200,
+            size_of_headers: 0,
+            sections: vec![Section {
+                header_offset: 0,
+                virtual_size: rsrc.len() as u32,
+                virtual_address: section_rva,
+                raw_size: rsrc.len() as u32,
+                raw_offset: 0,
+            }],
+        };
+        let parsed = read_resource_tree(&rsrc, &layout, section_rva).unwrap();
+
+        let flatten = |tree: &ResourceTree| -> Vec<(ResourceName, ResourceName, u32, u32, Vec<u8>)> {
+            tree.iter()
+                .flat_map(|(t, names)| names.iter().flat_map(move |(n, langs)| {
+                    langs.iter().map(move |(lang, r)| (t.clone(), n.clone(), *lang, r.code_page, r.data.clone()))
+                }))
+                .collect()
+        };
+        assert_eq!(flatten(&parsed), flatten(&tree));
+    }
+
+    #[test]
+    fn strips_signature_and_updates_checksum() {
+        let mut pe = minimal_pe();
+        let cert_offset = pe.len() as u32;
+        pe.extend_from_slice(&[0xAB; 64]);
+        write_u32(&mut pe, DATA_DIR_OFFSET + DIR_SECURITY * 8, cert_offset);
+        write_u32(&mut pe, DATA_DIR_OFFSET + DIR_SECURITY * 8 + 4, 64);
+
+        let out = replace_icon_in_pe(&pe, &icon(&[16])).unwrap();
+        let layout = parse_layout(&out).unwrap();
+        assert_eq!(layout.data_dir(&out, DIR_SECURITY).unwrap(), (0, 0));
+        assert!(!out.windows(64).any(|w| w == [0xAB; 64]));
+
+        let checksum = read_u32(&out, OPTIONAL_OFFSET + 64).unwrap();
+        let mut recomputed = out.clone();
+        update_checksum(&mut recomputed, OPTIONAL_OFFSET);
+        assert_ne!(checksum, 0);
+        assert_eq!(read_u32(&recomputed, OPTIONAL_OFFSET + 64).unwrap(), checksum);
+    }
+
+    #[test]
+    fn rejects_appended_data() {
+        let mut pe = minimal_pe();
+        pe.extend_from_slice(b"TUTORIALMAKER_DATA_V2");
+        let error = replace_icon_in_pe(&pe, &icon(&[16])).unwrap_err();
+        assert!(error.contains("추가 데이터"));
+    }
+
+    #[test]
+    fn rejects_corrupt_headers() {
+        let icon = icon(&[16]);
+        let corrupt = |edit: &dyn Fn(&mut Vec<u8>)| {
+            let mut pe = minimal_pe();
+            edit(&mut pe);
+            replace_icon_in_pe(&pe, &icon).unwrap_err()
+        };
+
+        assert!(corrupt(&|pe| pe[0] = b'X').contains("MZ"));
+        assert!(corrupt(&|pe| write_u32(pe, 0x3C, u32::MAX)).contains("PE 시그니처"));
+        assert!(corrupt(&|pe| write_u32(pe, OPTIONAL_OFFSET + 36, 0x300)).contains("정렬"));
+        assert!(corrupt(&|pe| write_u32(pe, OPTIONAL_OFFSET + 32, 0x100)).contains("정렬"));
+        assert!(corrupt(&|pe| write_u16(pe, 0x46, 200)).contains("손상"));
+        // 섹션 데이터가 파일 끝을 넘음
+        assert!(corrupt(&|pe| pe.truncate(0x300)).contains("잘렸습니다"));
+        assert!(corrupt(&|pe| write_u32(pe, SECTION_TABLE_OFFSET + 20, u32::MAX)).contains("올바르지 않습니다"));
+        // 서명 위치가 섹션 데이터 안을 가리킴
+        assert!(corrupt(&|pe| {
+            write_u32(pe, DATA_DIR_OFFSET + DIR_SECURITY * 8, 0x300);
+            write_u32(pe, DATA_DIR_OFFSET + DIR_SECURITY * 8 + 4, 0x10);
+        }).contains("서명 위치"));
+    }
+
+    #[test]
+    fn image_end_covers_headers_and_sections() {
+        let pe = minimal_pe();
+        assert_eq!(image_end(&pe).unwrap(), 0x400);
+
+        // 섹션이 없으면 헤더 크기까지
+        let mut headers_only = pe.clone();
+        write_u16(&mut headers_only, 0x46, 0);
+        assert_eq!(image_end(&headers_only).unwrap(), 0x200);
+
+        assert!(image_end(&pe[..0x100]).is_err());
+    }
+}
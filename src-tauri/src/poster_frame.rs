@@ -0,0 +1,119 @@
+// 비디오 포스터 프레임 추출 (빌드 시)
+//
+// 번들된 ffmpeg(resources/ffmpeg[.exe]) 또는 시스템 PATH의 ffmpeg로 각 비디오의 한 프레임을
+// JPEG로 뽑아 제품에 함께 내장한다. 제품은 비디오가 로드되기 전까지 이 이미지를 보여준다.
+// ffmpeg를 찾지 못하거나 추출에 실패하면 포스터 없이 빌드를 계속한다.
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use tauri::Manager;
+
+// 포스터 엔트리 ID 접미사 ({비디오 ID}.poster, 프론트엔드는 매니페스트의 poster_for로 찾음)
+pub const POSTER_ID_SUFFIX: &str = ".poster";
+pub const POSTER_MIME_TYPE: &str = "image/jpeg";
+
+// 첫 프레임은 검은 화면인 경우가 많아 1초 지점을 우선 사용
+const POSTER_SEEK_SECONDS: &str = "1";
+// 포스터 최대 너비 (원본이 더 작으면 그대로)
+const POSTER_SCALE_FILTER: &str = "scale='min(1280,iw)':-2";
+
+#[cfg(windows)]
+const FFMPEG_NAME: &str = "ffmpeg.exe";
+#[cfg(not(windows))]
+const FFMPEG_NAME: &str = "ffmpeg";
+
+pub fn is_video(mime_type: &str) -> bool {
+    mime_type.starts_with("video/")
+}
+
+pub fn poster_id(media_id: &str) -> String {
+    format!("{}{}", media_id, POSTER_ID_SUFFIX)
+}
+
+// 번들된 ffmpeg → 시스템 ffmpeg 순서로 찾기
+pub fn find_ffmpeg(app: &tauri::AppHandle) -> Option<PathBuf> {
+    let mut candidates = Vec::new();
+
+    // 1. Tauri 리소스 경로 (번들된 앱)
+    if let Ok(resource_path) = app.path().resource_dir() {
+        candidates.push(resource_path.join(FFMPEG_NAME));
+    }
+
+    // 2. 실행 파일 옆 (resources 하위 폴더 포함)
+    if let Some(exe_dir) = env::current_exe().ok().and_then(|p| p.parent().map(Path::to_path_buf)) {
+        candidates.push(exe_dir.join(FFMPEG_NAME));
+        candidates.push(exe_dir.join("resources").join(FFMPEG_NAME));
+    }
+
+    // 3. 개발 모드: 프로젝트 src-tauri/resources
+    if let Ok(current_dir) = env::current_dir() {
+        candidates.push(current_dir.join("src-tauri").join("resources").join(FFMPEG_NAME));
+        candidates.push(current_dir.join("resources").join(FFMPEG_NAME));
+    }
+
+    if let Some(bundled) = candidates.into_iter().find(|path| path.is_file()) {
+        return Some(bundled);
+    }
+
+    // 4. 시스템 PATH
+    let system = PathBuf::from(FFMPEG_NAME);
+    ffmpeg_command(&system)
+        .arg("-version")
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|_| system)
+}
+
+fn ffmpeg_command(ffmpeg: &Path) -> Command {
+    let mut command = Command::new(ffmpeg);
+    command.stdin(Stdio::null());
+
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        // 콘솔 창이 잠깐 뜨지 않도록 CREATE_NO_WINDOW
+        const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+        command.creation_flags(CREATE_NO_WINDOW);
+    }
+
+    command
+}
+
+// 비디오에서 포스터 프레임 하나를 JPEG로 저장
+pub fn extract_poster(ffmpeg: &Path, video: &Path, output: &Path) -> Result<(), String> {
+    // 1초보다 짧은 비디오는 탐색 지점에 프레임이 없으므로 처음부터 다시 시도
+    run_ffmpeg(ffmpeg, video, output, Some(POSTER_SEEK_SECONDS))
+        .or_else(|_| run_ffmpeg(ffmpeg, video, output, None))
+}
+
+fn run_ffmpeg(ffmpeg: &Path, video: &Path, output: &Path, seek: Option<&str>) -> Result<(), String> {
+    let _ = fs::remove_file(output);
+
+    let mut command = ffmpeg_command(ffmpeg);
+    command.args(["-hide_banner", "-loglevel", "error", "-y"]);
+    if let Some(seek) = seek {
+        command.args(["-ss", seek]);
+    }
+    let result = command
+        .arg("-i")
+        .arg(video)
+        .args(["-frames:v", "1", "-vf", POSTER_SCALE_FILTER, "-q:v", "3"])
+        .arg(output)
+        .output()
+        .map_err(|e| format!("ffmpeg 실행 실패: {}", e))?;
+
+    let written = fs::metadata(output).map(|m| m.len() > 0).unwrap_or(false);
+    if result.status.success() && written {
+        return Ok(());
+    }
+
+    let stderr = String::from_utf8_lossy(&result.stderr);
+    Err(match stderr.lines().map(str::trim).rfind(|line| !line.is_empty()) {
+        Some(line) => format!("포스터 프레임 추출 실패: {}", line),
+        None => "포스터 프레임 추출 실패: 프레임을 읽지 못했습니다.".to_string(),
+    })
+}
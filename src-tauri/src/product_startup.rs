@@ -0,0 +1,84 @@
+// 제품 시작 시 내장 데이터의 설정으로 메인 창 생성
+//
+// tauri.conf.json의 메인 창은 자동 생성하지 않고(create: false) setup에서 만든다.
+// 제품 exe로 실행되면(내장 데이터가 있으면) 창이 보이기 전에 프로젝트 설정의 창 크기,
+// 전체화면, 제목과 내장된 앱 아이콘을 적용하고, 빌더로 실행되면 설정 파일 그대로 만든다.
+
+use tauri::image::Image;
+use tauri::{Manager, WebviewWindowBuilder};
+
+use crate::exit_guard;
+use crate::payload::Payload;
+use crate::payload_access;
+use crate::project_model::Project;
+
+// tauri.conf.json과 capabilities에서 쓰는 메인 창 라벨
+const MAIN_WINDOW_LABEL: &str = "main";
+
+// 제품 exe에 내장된 프로젝트 (빌더로 실행되면 None)
+// 사용 허가(서명/무결성/라이선스)를 거쳐 읽고, 허가되지 않았거나 키 문제 등으로 읽지 못하면 Err
+// (창은 기본 설정으로 열고 오류는 화면에서 표시)
+pub fn embedded_project(app: &tauri::AppHandle) -> Option<Result<Project, String>> {
+    Payload::current().ok()?;
+    let project = payload_access::payload(app)
+        .and_then(|payload| payload.project_json())
+        .and_then(|json| Project::parse(&json));
+    if let Err(e) = &project {
+        log::warn!("프로젝트 설정 읽기 실패: {}", e);
+    }
+    Some(project)
+}
+
+// 메인 창 생성 (CLI 서브커맨드로 실행되면 호출하지 않음)
+pub fn create_main_window(app: &tauri::App, project: Option<&Project>) -> tauri::Result<()> {
+    let Some(config) = app.config().app.windows.iter().find(|w| w.label == MAIN_WINDOW_LABEL) else {
+        return Ok(());
+    };
+    let mut builder = WebviewWindowBuilder::from_config(app.handle(), config)?;
+
+    if let Some(project) = project {
+        let settings = &project.settings;
+        let title = if project.app_title.is_empty() { &project.name } else { &project.app_title };
+        builder = builder
+            .title(title)
+            .inner_size(settings.window_width, settings.window_height)
+            .center()
+            .fullscreen(settings.fullscreen);
+    }
+
+    // 종료 키 판단은 Rust에서 하므로 키 입력을 전달하는 스크립트를 넣음
+    if exit_guard::listens_for_keys(app) {
+        builder = builder.initialization_script(exit_guard::KEY_LISTENER_SCRIPT);
+    }
+
+    // 빌더로 실행되면 내장 데이터가 없음
+    if let Some(icon) = Payload::current().ok().and_then(embedded_icon) {
+        builder = builder.icon(icon)?;
+    }
+
+    builder.build().map(|_| ())
+}
+
+// 내장된 앱 아이콘 (창/작업 표시줄 아이콘)
+// Windows 이외 대상은 실행 파일 아이콘을 바꿀 수 없으므로 이 아이콘이 유일한 앱 아이콘
+fn embedded_icon(payload: &Payload) -> Option<Image<'static>> {
+    let icon = match payload.app_icon() {
+        Ok(icon) => icon?,
+        Err(e) => {
+            log::warn!("앱 아이콘 읽기 실패: {}", e);
+            return None;
+        }
+    };
+
+    match image::load_from_memory(&icon) {
+        Ok(img) => {
+            let rgba = img.to_rgba8();
+            let (width, height) = rgba.dimensions();
+            Some(Image::new_owned(rgba.into_raw(), width, height))
+        }
+        Err(e) => {
+            log::warn!("앱 아이콘 디코딩 실패: {}", e);
+            None
+        }
+    }
+}
@@ -0,0 +1,236 @@
+// 제품 빌드 대상 플랫폼
+//
+// 페이로드 트레일러 자체는 포맷에 무관하므로 대상별로 달라지는 것은
+// 템플릿 파일 이름과 페이로드를 붙이는 위치뿐이다.
+//   Windows  : product-template.exe        → exe 끝에 append
+//   Linux    : product-template            → ELF 바이너리 끝에 append
+//   AppImage : product-template.AppImage   → AppImage 파일 끝에 append
+//   macOS    : product-template.app (번들) → Contents/Resources/tutorial.payload 파일
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::payload::BUNDLE_PAYLOAD_RESOURCE;
+use crate::pe_resources;
+
+// 유효한 템플릿으로 간주하는 최소 크기 (빈 파일/잘못 복사된 파일 방지)
+const MIN_TEMPLATE_SIZE: u64 = 1_000_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ProductTarget {
+    Windows,
+    Linux,
+    AppImage,
+    MacOs,
+}
+
+impl ProductTarget {
+    // 빌더가 실행 중인 OS의 기본 대상
+    pub fn host() -> Self {
+        if cfg!(windows) {
+            ProductTarget::Windows
+        } else if cfg!(target_os = "macos") {
+            ProductTarget::MacOs
+        } else {
+            ProductTarget::Linux
+        }
+    }
+
+    // CLI 인자 값 해석
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value.to_ascii_lowercase().as_str() {
+            "windows" | "win" | "exe" => Ok(ProductTarget::Windows),
+            "linux" | "elf" => Ok(ProductTarget::Linux),
+            "appimage" => Ok(ProductTarget::AppImage),
+            "macos" | "mac" | "app" => Ok(ProductTarget::MacOs),
+            other => Err(format!(
+                "알 수 없는 빌드 대상입니다: {} (windows, linux, appimage, macos 중 선택)",
+                other
+            )),
+        }
+    }
+
+    // 번들/리소스 폴더에서 찾을 템플릿 이름
+    pub fn template_name(self) -> &'static str {
+        match self {
+            ProductTarget::Windows => "product-template.exe",
+            ProductTarget::Linux => "product-template",
+            ProductTarget::AppImage => "product-template.AppImage",
+            ProductTarget::MacOs => "product-template.app",
+        }
+    }
+
+    // 템플릿이 단일 파일이 아닌 디렉토리 번들인지 여부
+    pub fn is_bundle(self) -> bool {
+        self == ProductTarget::MacOs
+    }
+
+    // 실행 파일 아이콘 리소스를 직접 교체할 수 있는지 여부 (PE만 지원)
+    pub fn supports_icon_resource(self) -> bool {
+        self == ProductTarget::Windows
+    }
+
+    // 템플릿 후보가 사용 가능한지 확인
+    pub fn is_valid_template(self, path: &Path) -> bool {
+        if self.is_bundle() {
+            // .app 번들: 실행 파일이 들어 있는 Contents/MacOS 폴더가 있어야 함
+            return path.join("Contents").join("MacOS").is_dir();
+        }
+
+        fs::metadata(path)
+            .map(|m| m.is_file() && m.len() > MIN_TEMPLATE_SIZE)
+            .unwrap_or(false)
+    }
+}
+
+// 제품 경로에서 페이로드가 저장되는 파일 (.app 번들이면 번들 안의 리소스 파일)
+pub fn payload_path(product_path: &Path) -> PathBuf {
+    if product_path.is_dir() {
+        return product_path
+            .join("Contents")
+            .join("Resources")
+            .join(BUNDLE_PAYLOAD_RESOURCE);
+    }
+    product_path.to_path_buf()
+}
+
+// 실행 파일 본체가 끝나는 위치 (그 뒤는 append된 데이터)
+// headers: 파일 앞부분. 알 수 없는 형식(번들 리소스 파일 등)은 0
+pub fn image_end(headers: &[u8]) -> Result<u64, String> {
+    if headers.starts_with(b"MZ") {
+        return pe_resources::image_end(headers);
+    }
+    if headers.starts_with(b"\x7fELF") {
+        return elf_image_end(headers);
+    }
+    Ok(0)
+}
+
+// ELF 헤더의 프로그램/섹션 헤더 테이블 끝 위치
+fn elf_image_end(headers: &[u8]) -> Result<u64, String> {
+    let corrupt = || "ELF 파일이 손상되었습니다.".to_string();
+
+    let is_64 = match headers.get(4) {
+        Some(1) => false,
+        Some(2) => true,
+        _ => return Err(corrupt()),
+    };
+    let little_endian = match headers.get(5) {
+        Some(1) => true,
+        Some(2) => false,
+        _ => return Err(corrupt()),
+    };
+
+    let read = |offset: usize, size: usize| -> Result<u64, String> {
+        let bytes = headers.get(offset..offset + size).ok_or_else(corrupt)?;
+        let push = |value: u64, byte: &u8| (value << 8) | *byte as u64;
+        Ok(if little_endian {
+            bytes.iter().rev().fold(0, push)
+        } else {
+            bytes.iter().fold(0, push)
+        })
+    };
+
+    // (e_phoff, e_shoff, e_phentsize, e_phnum, e_shentsize, e_shnum)
+    let (ph_off, sh_off, ph_entsize, ph_num, sh_entsize, sh_num) = if is_64 {
+        (read(0x20, 8)?, read(0x28, 8)?, read(0x36, 2)?, read(0x38, 2)?, read(0x3A, 2)?, read(0x3C, 2)?)
+    } else {
+        (read(0x1C, 4)?, read(0x20, 4)?, read(0x2A, 2)?, read(0x2C, 2)?, read(0x2E, 2)?, read(0x30, 2)?)
+    };
+
+    let ph_end = ph_off.saturating_add(ph_entsize * ph_num);
+    let sh_end = sh_off.saturating_add(sh_entsize * sh_num);
+
+    Ok(ph_end.max(sh_end))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // ELF 헤더만 있는 파일 (프로그램/섹션 헤더 테이블 위치만 채움)
+    fn elf_header(is_64: bool, little_endian: bool) -> Vec<u8> {
+        let mut headers = vec![0u8; 64];
+        headers[..4].copy_from_slice(b"\x7fELF");
+        headers[4] = if is_64 { 2 } else { 1 };
+        headers[5] = if little_endian { 1 } else { 2 };
+
+        let mut put = |offset: usize, size: usize, value: u64| {
+            let bytes = value.to_le_bytes();
+            let field = &mut headers[offset..offset + size];
+            field.copy_from_slice(&bytes[..size]);
+            if !little_endian {
+                field.reverse();
+            }
+        };
+        if is_64 {
+            put(0x20, 8, 64);
+            put(0x28, 8, 10_000);
+            put(0x36, 2, 56);
+            put(0x38, 2, 9);
+            put(0x3A, 2, 64);
+            put(0x3C, 2, 30);
+        } else {
+            put(0x1C, 4, 52);
+            put(0x20, 4, 5_000);
+            put(0x2A, 2, 32);
+            put(0x2C, 2, 8);
+            put(0x2E, 2, 40);
+            put(0x30, 2, 20);
+        }
+        headers
+    }
+
+    #[test]
+    fn elf_image_end_reads_header_tables() {
+        assert_eq!(image_end(&elf_header(true, true)).unwrap(), 10_000 + 64 * 30);
+        assert_eq!(image_end(&elf_header(true, false)).unwrap(), 10_000 + 64 * 30);
+        assert_eq!(image_end(&elf_header(false, true)).unwrap(), 5_000 + 40 * 20);
+        assert_eq!(image_end(&elf_header(false, false)).unwrap(), 5_000 + 40 * 20);
+
+        // 프로그램 헤더 테이블이 더 뒤에 있는 경우
+        let mut headers = elf_header(true, true);
+        headers[0x20..0x28].copy_from_slice(&20_000u64.to_le_bytes());
+        assert_eq!(image_end(&headers).unwrap(), 20_000 + 56 * 9);
+    }
+
+    #[test]
+    fn elf_image_end_rejects_corrupt_headers() {
+        let mut bad_class = elf_header(true, true);
+        bad_class[4] = 3;
+        assert!(image_end(&bad_class).is_err());
+
+        let mut bad_endian = elf_header(true, true);
+        bad_endian[5] = 0;
+        assert!(image_end(&bad_endian).is_err());
+
+        assert!(image_end(&elf_header(true, true)[..0x30]).is_err());
+
+        // 오프셋이 u64 끝을 넘어도 패닉 없이 계산
+        let mut overflow = elf_header(true, true);
+        overflow[0x28..0x30].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert_eq!(image_end(&overflow).unwrap(), u64::MAX);
+    }
+
+    #[test]
+    fn unknown_format_has_no_image() {
+        assert_eq!(image_end(b"TUTORIALMAKER_DATA_V2").unwrap(), 0);
+        assert_eq!(image_end(b"").unwrap(), 0);
+        assert!(image_end(b"MZ").is_err());
+    }
+
+    #[test]
+    fn parses_target_names() {
+        assert_eq!(ProductTarget::parse("WIN").unwrap(), ProductTarget::Windows);
+        assert_eq!(ProductTarget::parse("elf").unwrap(), ProductTarget::Linux);
+        assert_eq!(ProductTarget::parse("AppImage").unwrap(), ProductTarget::AppImage);
+        assert_eq!(ProductTarget::parse("app").unwrap(), ProductTarget::MacOs);
+        assert!(ProductTarget::parse("android").is_err());
+        assert!(ProductTarget::MacOs.is_bundle());
+        assert!(ProductTarget::Windows.supports_icon_resource());
+        assert!(!ProductTarget::Linux.supports_icon_resource());
+    }
+}
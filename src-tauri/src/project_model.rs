@@ -0,0 +1,400 @@
+// 프로젝트 데이터 모델 (프론트엔드 src/types/project.ts와 대응)
+//
+// 빌드 명령은 프로젝트 JSON을 그대로 내장하지만, 내장하기 전에 이 타입으로 읽어
+// 깨진 참조(삭제된 페이지로 가는 goto, 빌드 정보에 없는 미디어 등)를 검사한다.
+// 알 수 없는 필드는 무시하고, 이전 버전 프로젝트에 없던 필드는 기본값을 쓴다.
+
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Project {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub app_icon: Option<String>,
+    #[serde(default)]
+    pub app_title: String,
+    pub pages: Vec<Page>,
+    #[serde(default)]
+    pub settings: ProjectSettings,
+    #[serde(default)]
+    pub created_at: u64,
+    #[serde(default)]
+    pub updated_at: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ProjectSettings {
+    pub window_width: f64,
+    pub window_height: f64,
+    pub fullscreen: bool,
+    // "ESC", "F11" 등
+    pub exit_key: Option<String>,
+    // 종료 시 확인할 관리자 PIN 해시 (exit_guard::hash_pin, 없으면 확인 없이 종료)
+    pub exit_pin_hash: Option<String>,
+    // Alt+F4/창 닫기 버튼으로 닫지 못하게 막고 PIN 입력을 요청
+    pub block_close: bool,
+    pub show_progress: bool,
+    pub show_home_button: bool,
+    pub show_back_button: bool,
+    // 마지막 페이지 후 첫 페이지로
+    pub loop_at_end: bool,
+}
+
+// 빌더의 새 프로젝트 기본값과 같음
+impl Default for ProjectSettings {
+    fn default() -> Self {
+        ProjectSettings {
+            window_width: 1920.0,
+            window_height: 1080.0,
+            fullscreen: true,
+            exit_key: None,
+            exit_pin_hash: None,
+            block_close: false,
+            show_progress: false,
+            show_home_button: false,
+            show_back_button: false,
+            loop_at_end: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MediaType {
+    Video,
+    Image,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PlayType {
+    Loop,
+    Single,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ShowTiming {
+    Immediate,
+    AfterVideo,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Page {
+    pub id: String,
+    // 페이지 제목 (기본값: "페이지 N")
+    #[serde(default)]
+    pub title: String,
+    #[serde(default)]
+    pub order: u32,
+    pub media_type: MediaType,
+    #[serde(default)]
+    pub media_id: String,
+    pub play_type: PlayType,
+    // single 모드에서 재생 횟수 (1~20, 기본값: 1)
+    #[serde(default)]
+    pub play_count: Option<u32>,
+    #[serde(default)]
+    pub buttons: Vec<PageButton>,
+    #[serde(default)]
+    pub touch_areas: Vec<TouchArea>,
+}
+
+// 퍼센트 좌표 (0-100)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Position {
+    pub x: f64,
+    pub y: f64,
+}
+
+// 퍼센트 크기 (0-100)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Size {
+    pub width: f64,
+    pub height: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PageButton {
+    pub id: String,
+    pub image_id: String,
+    pub position: Position,
+    pub size: Size,
+    pub action: NavigationAction,
+    pub show_timing: ShowTiming,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TouchArea {
+    pub id: String,
+    pub position: Position,
+    pub size: Size,
+    pub action: NavigationAction,
+    pub show_timing: ShowTiming,
+    // 디버그 모드에서 테두리 표시
+    #[serde(default)]
+    pub debug_visible: Option<bool>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ActionType {
+    Next,
+    Goto,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NavigationAction {
+    #[serde(rename = "type")]
+    pub kind: ActionType,
+    // kind가 Goto일 때 사용
+    #[serde(default)]
+    pub target_page_id: Option<String>,
+}
+
+impl Project {
+    pub fn parse(project_json: &str) -> Result<Project, String> {
+        serde_json::from_str(project_json)
+            .map_err(|e| format!("프로젝트 데이터 형식이 올바르지 않습니다: {}", e))
+    }
+
+    // 미디어를 페이지 미디어나 버튼 이미지로 쓰는 페이지 ("페이지 N (제목)")
+    pub fn pages_using_media(&self, media_id: &str) -> Vec<String> {
+        self.pages.iter().enumerate()
+            .filter(|(_, page)| page.media_id == media_id || page.buttons.iter().any(|b| b.image_id == media_id))
+            .map(|(index, page)| page_label(index, page))
+            .collect()
+    }
+}
+
+// 편집기 드래그 계산에서 생기는 부동소수점 오차 허용
+const GEOMETRY_TOLERANCE: f64 = 0.01;
+
+// 빌드 전 검사에서 발견한 문제 하나
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectIssue {
+    // 문제가 있는 페이지 (프로젝트 전체 문제면 None)
+    pub page_id: Option<String>,
+    pub message: String,
+}
+
+// 프로젝트를 빌드 정보와 대조해 문제를 모두 찾음
+// media_ids: 빌드에 함께 전달된 미디어 ID (페이지 미디어 + 버튼 이미지)
+pub fn validate_project(project: &Project, media_ids: &HashSet<&str>) -> Vec<ProjectIssue> {
+    let mut issues = Vec::new();
+
+    if project.pages.is_empty() {
+        issues.push(ProjectIssue { page_id: None, message: "페이지가 없습니다.".to_string() });
+    }
+
+    let page_ids: HashSet<&str> = project.pages.iter().map(|p| p.id.as_str()).collect();
+
+    for (index, page) in project.pages.iter().enumerate() {
+        let label = page_label(index, page);
+        let mut report = |message: String| {
+            issues.push(ProjectIssue { page_id: Some(page.id.clone()), message: format!("{}: {}", label, message) });
+        };
+
+        // 빈 페이지 / 빌드 정보에 없는 미디어
+        if page.media_id.is_empty() {
+            report("미디어 파일이 없습니다.".to_string());
+        } else if !media_ids.contains(page.media_id.as_str()) {
+            report(format!("미디어({})를 찾을 수 없습니다.", page.media_id));
+        }
+
+        for button in &page.buttons {
+            let name = format!("버튼 {}", button.id);
+            if !media_ids.contains(button.image_id.as_str()) {
+                report(format!("{} 이미지({})를 찾을 수 없습니다.", name, button.image_id));
+            }
+            if let Some(message) = check_geometry(&button.position, &button.size) {
+                report(format!("{} {}", name, message));
+            }
+            if let Some(message) = check_action(&button.action, &page_ids) {
+                report(format!("{} {}", name, message));
+            }
+        }
+
+        for area in &page.touch_areas {
+            let name = format!("터치 영역 {}", area.id);
+            if let Some(message) = check_geometry(&area.position, &area.size) {
+                report(format!("{} {}", name, message));
+            }
+            if let Some(message) = check_action(&area.action, &page_ids) {
+                report(format!("{} {}", name, message));
+            }
+        }
+    }
+
+    issues
+}
+
+fn page_label(index: usize, page: &Page) -> String {
+    if page.title.is_empty() {
+        format!("페이지 {}", index + 1)
+    } else {
+        format!("페이지 {} ({})", index + 1, page.title)
+    }
+}
+
+// 위치/크기가 화면(0-100%) 안에 있는지
+fn check_geometry(position: &Position, size: &Size) -> Option<String> {
+    let in_range = |value: f64| value.is_finite() && (-GEOMETRY_TOLERANCE..=100.0 + GEOMETRY_TOLERANCE).contains(&value);
+    let valid = [position.x, position.y, size.width, size.height].into_iter().all(in_range)
+        && size.width > 0.0
+        && size.height > 0.0
+        && position.x + size.width <= 100.0 + GEOMETRY_TOLERANCE
+        && position.y + size.height <= 100.0 + GEOMETRY_TOLERANCE;
+
+    (!valid).then(|| format!(
+        "위치/크기가 화면을 벗어납니다. (x {}, y {}, 너비 {}, 높이 {})",
+        position.x, position.y, size.width, size.height
+    ))
+}
+
+// goto 대상 페이지가 있는지
+fn check_action(action: &NavigationAction, page_ids: &HashSet<&str>) -> Option<String> {
+    if action.kind != ActionType::Goto {
+        return None;
+    }
+    match action.target_page_id.as_deref() {
+        None | Some("") => Some("이동할 페이지가 지정되지 않았습니다.".to_string()),
+        Some(target) if !page_ids.contains(target) => Some(format!("이동할 페이지({})가 없습니다.", target)),
+        Some(_) => None,
+    }
+}
+
+// 오류 메시지용 문제 목록
+pub fn format_issues(issues: &[ProjectIssue]) -> String {
+    let lines: Vec<String> = issues.iter().map(|issue| format!("  - {}", issue.message)).collect();
+    format!("프로젝트 검사에서 {}개 문제가 발견되었습니다:\n{}", issues.len(), lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROJECT_JSON: &str = r#"{
+        "id": "p1",
+        "name": "안내",
+        "unknownField": true,
+        "pages": [
+            {
+                "id": "page-1",
+                "mediaType": "video",
+                "mediaId": "intro",
+                "playType": "single",
+                "buttons": [{
+                    "id": "next",
+                    "imageId": "button-img",
+                    "position": { "x": 80, "y": 80 },
+                    "size": { "width": 20, "height": 20 },
+                    "action": { "type": "goto", "targetPageId": "page-2" },
+                    "showTiming": "after-video"
+                }]
+            },
+            {
+                "id": "page-2",
+                "title": "마무리",
+                "mediaType": "image",
+                "mediaId": "outro",
+                "playType": "loop",
+                "touchAreas": [{
+                    "id": "area",
+                    "position": { "x": 0, "y": 0 },
+                    "size": { "width": 100, "height": 100.005 },
+                    "action": { "type": "next" },
+                    "showTiming": "immediate"
+                }]
+            }
+        ]
+    }"#;
+
+    fn project() -> Project {
+        Project::parse(PROJECT_JSON).unwrap()
+    }
+
+    fn all_media() -> HashSet<&'static str> {
+        ["intro", "outro", "button-img"].into_iter().collect()
+    }
+
+    fn messages(project: &Project, media_ids: &HashSet<&str>) -> Vec<String> {
+        validate_project(project, media_ids).into_iter().map(|issue| issue.message).collect()
+    }
+
+    #[test]
+    fn parses_with_defaults() {
+        let project = project();
+        assert_eq!(project.settings.window_width, 1920.0);
+        assert!(project.settings.loop_at_end);
+        assert_eq!(project.pages[1].touch_areas[0].action.kind, ActionType::Next);
+        assert_eq!(project.pages_using_media("button-img"), ["페이지 1"]);
+        assert_eq!(project.pages_using_media("outro"), ["페이지 2 (마무리)"]);
+
+        assert!(Project::parse("{}").is_err());
+        assert!(Project::parse("not json").is_err());
+    }
+
+    #[test]
+    fn valid_project_has_no_issues() {
+        assert!(validate_project(&project(), &all_media()).is_empty());
+    }
+
+    #[test]
+    fn reports_missing_pages_and_media() {
+        let mut empty = project();
+        empty.pages.clear();
+        let issues = validate_project(&empty, &all_media());
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].page_id, None);
+
+        let mut project = project();
+        project.pages[1].media_id.clear();
+        let media: HashSet<&str> = ["outro"].into_iter().collect();
+        let messages = messages(&project, &media);
+        assert_eq!(messages, [
+            "페이지 1: 미디어(intro)를 찾을 수 없습니다.",
+            "페이지 1: 버튼 next 이미지(button-img)를 찾을 수 없습니다.",
+            "페이지 2 (마무리): 미디어 파일이 없습니다.",
+        ]);
+    }
+
+    #[test]
+    fn reports_broken_goto_targets() {
+        let mut project = project();
+        project.pages[0].buttons[0].action.target_page_id = Some("deleted".to_string());
+        project.pages[1].touch_areas[0].action = NavigationAction { kind: ActionType::Goto, target_page_id: None };
+
+        let issues = validate_project(&project, &all_media());
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].page_id.as_deref(), Some("page-1"));
+        assert!(issues[0].message.contains("이동할 페이지(deleted)가 없습니다"));
+        assert!(issues[1].message.contains("이동할 페이지가 지정되지 않았습니다"));
+        assert!(format_issues(&issues).starts_with("프로젝트 검사에서 2개 문제"));
+    }
+
+    #[test]
+    fn reports_geometry_outside_screen() {
+        for (x, width) in [(90.0, 20.0), (-5.0, 10.0), (10.0, 0.0), (f64::NAN, 10.0)] {
+            let mut project = project();
+            let button = &mut project.pages[0].buttons[0];
+            button.position.x = x;
+            button.size.width = width;
+            let messages = messages(&project, &all_media());
+            assert_eq!(messages.len(), 1, "x {}, 너비 {}", x, width);
+            assert!(messages[0].contains("화면을 벗어납니다"));
+        }
+    }
+}
@@ -0,0 +1,201 @@
+// 개발 환경에서 템플릿 실행 파일 빌드
+//
+// 번들된 템플릿이 없을 때 사용하는 폴백. 호스트 OS에 맞게 npm/cargo를 직접 실행하고,
+// 자식 프로세스 출력은 줄 단위로 build-progress 이벤트로 전달한다.
+
+use std::collections::VecDeque;
+use std::env;
+use std::io::{BufRead, BufReader, Read};
+use std::path::PathBuf;
+use std::process::{Child, Command, Stdio};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use crate::build_progress::{self, BuildStage};
+
+// 템플릿으로 사용할 앱 바이너리 이름 (src-tauri/Cargo.toml의 package name)
+const TEMPLATE_BINARY_NAME: &str = "app";
+
+// 빌드 프로세스 취소 확인 주기
+const CHILD_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+// 빌드 실패 시 에러 메시지에 포함할 마지막 출력 줄 수
+const LOG_TAIL_LINES: usize = 40;
+
+// 호스트 OS에 맞는 npm 실행 파일 (Windows의 npm은 배치 파일)
+fn npm_command() -> Command {
+    if cfg!(windows) {
+        Command::new("npm.cmd")
+    } else {
+        Command::new("npm")
+    }
+}
+
+// 자식 프로세스와 그 하위 프로세스(npm → node, cargo → rustc) 종료
+fn kill_process_tree(child: &mut Child) {
+    #[cfg(windows)]
+    {
+        let _ = Command::new("taskkill")
+            .args(["/PID", &child.id().to_string(), "/T", "/F"])
+            .output();
+    }
+    #[cfg(unix)]
+    {
+        let _ = Command::new("kill")
+            .args(["-TERM", &format!("-{}", child.id())])
+            .output();
+    }
+    let _ = child.kill();
+    let _ = child.wait();
+}
+
+// 자식 프로세스 출력을 줄 단위로 진행 이벤트로 보내고 마지막 몇 줄은 보관
+fn forward_output_lines(
+    app: tauri::AppHandle,
+    pipe: impl Read + Send + 'static,
+    tail: Arc<Mutex<VecDeque<String>>>,
+) -> JoinHandle<()> {
+    std::thread::spawn(move || {
+        let mut reader = BufReader::new(pipe);
+        let mut buf = Vec::new();
+
+        // Windows 콘솔 출력은 UTF-8이 아닐 수 있으므로 바이트 단위로 읽음
+        while matches!(reader.read_until(b'\n', &mut buf), Ok(n) if n > 0) {
+            let line = String::from_utf8_lossy(&buf).trim_end().to_string();
+            buf.clear();
+            if line.is_empty() {
+                continue;
+            }
+
+            build_progress::stage(&app, BuildStage::BuildingTemplate, line.clone());
+
+            if let Ok(mut tail) = tail.lock() {
+                if tail.len() == LOG_TAIL_LINES {
+                    tail.pop_front();
+                }
+                tail.push_back(line);
+            }
+        }
+    })
+}
+
+// 빌드 명령 실행 (취소되면 프로세스 트리 종료)
+fn run_build_command(app: &tauri::AppHandle, mut command: Command, description: &str) -> Result<(), String> {
+    command.stdout(Stdio::piped()).stderr(Stdio::piped());
+    // 취소 시 하위 프로세스까지 함께 종료할 수 있도록 별도 그룹으로 실행
+    #[cfg(unix)]
+    std::os::unix::process::CommandExt::process_group(&mut command, 0);
+
+    let mut child = command.spawn()
+        .map_err(|e| format!("{} 실행 실패: {}", description, e))?;
+
+    let tail = Arc::new(Mutex::new(VecDeque::new()));
+    let mut readers = Vec::new();
+    if let Some(stdout) = child.stdout.take() {
+        readers.push(forward_output_lines(app.clone(), stdout, tail.clone()));
+    }
+    if let Some(stderr) = child.stderr.take() {
+        readers.push(forward_output_lines(app.clone(), stderr, tail.clone()));
+    }
+
+    let status = loop {
+        if build_progress::is_cancelled(app) {
+            kill_process_tree(&mut child);
+            return Err(build_progress::BUILD_CANCELLED.to_string());
+        }
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) => std::thread::sleep(CHILD_POLL_INTERVAL),
+            Err(e) => return Err(format!("빌드 프로세스 대기 실패: {}", e)),
+        }
+    };
+
+    for reader in readers {
+        let _ = reader.join();
+    }
+
+    if !status.success() {
+        let output = tail.lock()
+            .map(|tail| tail.iter().cloned().collect::<Vec<_>>().join("\n"))
+            .unwrap_or_default();
+        return Err(format!("{} 실패 ({}):\n{}", description, status, output));
+    }
+
+    Ok(())
+}
+
+// src-tauri와 package.json이 있는 프로젝트 루트 찾기
+fn find_project_root() -> Result<PathBuf, String> {
+    let current_dir = env::current_dir().map_err(|e| e.to_string())?;
+
+    // 프로젝트 루트 찾기 - 여러 경로 시도
+    let mut candidates: Vec<PathBuf> = vec![
+        current_dir.clone(),
+        current_dir.join("..").canonicalize().unwrap_or_default(),
+    ];
+
+    // 실행 파일 위치 기준
+    if let Ok(exe_path) = env::current_exe() {
+        if let Some(exe_dir) = exe_path.parent() {
+            candidates.push(exe_dir.to_path_buf());
+            if let Some(parent) = exe_dir.parent() {
+                candidates.push(parent.to_path_buf());
+                // target/debug 에서 실행될 경우: target/debug -> target -> src-tauri -> project_root
+                if let Some(grandparent) = parent.parent() {
+                    candidates.push(grandparent.to_path_buf());
+                    if let Some(greatgrandparent) = grandparent.parent() {
+                        candidates.push(greatgrandparent.to_path_buf());
+                    }
+                }
+            }
+        }
+    }
+
+    candidates.iter()
+        .find(|p| p.join("src-tauri").exists() && p.join("package.json").exists())
+        .cloned()
+        .ok_or_else(|| {
+            let tried = candidates.iter()
+                .map(|p| format!("  - {}", p.display()))
+                .collect::<Vec<_>>()
+                .join("\n");
+            format!("개발 환경을 찾을 수 없습니다.\n\n시도한 경로:\n{}", tried)
+        })
+}
+
+// 프론트엔드 + 릴리스 바이너리를 빌드하고 결과 실행 파일 경로 반환
+pub fn build_from_source(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    let project_root = find_project_root()?;
+    let tauri_dir = project_root.join("src-tauri");
+
+    // 1. 프론트엔드 빌드 (dist, 템플릿은 재생 전용 제품 모드)
+    build_progress::stage(app, BuildStage::BuildingTemplate, "프론트엔드 빌드 중...");
+    let mut npm = npm_command();
+    npm.args(["run", "build"])
+        .env("VITE_APP_MODE", "product")
+        .current_dir(&project_root);
+    run_build_command(app, npm, "프론트엔드 빌드")?;
+
+    // 2. 릴리스 바이너리 빌드 (tauri build와 같이 dist를 내장하도록 custom-protocol 활성화)
+    build_progress::stage(app, BuildStage::BuildingTemplate, "템플릿 실행 파일 빌드 중... (몇 분 소요될 수 있습니다)");
+    let mut cargo = Command::new("cargo");
+    cargo
+        .args(["build", "--release", "--features", "tauri/custom-protocol"])
+        .current_dir(&tauri_dir);
+    run_build_command(app, cargo, "템플릿 빌드")?;
+
+    // CARGO_TARGET_DIR이 설정되어 있으면 그 위치에 빌드됨 (상대 경로는 src-tauri 기준)
+    let target_dir = env::var_os("CARGO_TARGET_DIR")
+        .map(|dir| tauri_dir.join(dir))
+        .unwrap_or_else(|| tauri_dir.join("target"));
+    let built_exe = target_dir
+        .join("release")
+        .join(format!("{}{}", TEMPLATE_BINARY_NAME, env::consts::EXE_SUFFIX));
+
+    if !built_exe.exists() {
+        return Err(format!("빌드된 실행 파일을 찾을 수 없습니다: {}", built_exe.display()));
+    }
+
+    Ok(built_exe)
+}
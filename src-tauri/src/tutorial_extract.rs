@@ -0,0 +1,576 @@
+// 제품 exe → .tutorial 프로젝트 복원
+//
+// 배포된 exe만 남아 있을 때, 내장된 V2 매니페스트에서 프로젝트 JSON과 모든
+// 미디어를 꺼내 프론트엔드 exporter(projectExporter.ts)와 같은 구조의 zip으로 저장한다.
+//   manifest.json, project.json, media/<mediaId>.<ext>, buttons/<imageId>.<ext>, icons/<appIcon>.<ext>
+// 매니페스트의 ID/파일명은 신뢰하지 않고 [A-Za-z0-9_-]가 아닌 ID는 순번 ID로 바꿔 기록한다.
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+use serde::Serialize;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+use crate::payload::{MediaManifestEntry, Payload, PayloadFormat};
+use crate::payload_access::{self, ExtractKeys};
+
+// 복원된 앱 아이콘에 붙이는 ID (빌드 시 원래 아이콘 ID는 내장되지 않음)
+const RESTORED_APP_ICON_ID: &str = "app-icon";
+// 미디어를 zip에 옮길 때 한 번에 읽는 크기 (대용량 비디오를 메모리에 모두 올리지 않음)
+const EXTRACT_CHUNK_SIZE: u64 = 4 * 1024 * 1024;
+
+// .tutorial 파일의 manifest.json (프론트엔드 TutorialManifest와 동일)
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TutorialManifest {
+    version: &'static str,
+    format_version: u32,
+    created_at: u64,
+    created_with: &'static str,
+    project_name: String,
+}
+
+// 복원 결과 요약
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExtractSummary {
+    pub project_name: String,
+    pub media_count: usize,
+    pub button_count: usize,
+    pub has_app_icon: bool,
+}
+
+// 매니페스트에 기록된 MIME 타입 또는 파일명으로 확장자 결정
+fn extension_for(entry: &MediaManifestEntry) -> String {
+    let from_mime = match entry.mime_type.as_str() {
+        "video/mp4" => Some("mp4"),
+        "video/webm" => Some("webm"),
+        "video/avi" => Some("avi"),
+        "image/png" => Some("png"),
+        "image/jpeg" => Some("jpg"),
+        "image/gif" => Some("gif"),
+        "image/webp" => Some("webp"),
+        _ => None,
+    };
+    if let Some(ext) = from_mime {
+        return ext.to_string();
+    }
+
+    Path::new(&entry.name)
+        .extension()
+        .and_then(|e| e.to_str())
+        .filter(|e| !e.is_empty() && e.chars().all(|c| c.is_ascii_alphanumeric()))
+        .map(|e| e.to_ascii_lowercase())
+        .unwrap_or_else(|| {
+            if entry.mime_type.starts_with("video/") { "mp4" } else { "png" }.to_string()
+        })
+}
+
+// zip 항목 이름에 그대로 쓸 수 있는 ID인지 (경로 구분자, "..", 드라이브 문자 등 차단)
+fn is_safe_id(id: &str) -> bool {
+    !id.is_empty() && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+// 매니페스트의 ID를 믿을 수 없으므로 zip 항목에 쓸 수 없는 ID는 순번 ID로 바꿈
+// 반환값은 바뀐 ID만 담은 (원래 ID → 새 ID)
+fn renamed_media_ids(media: &[MediaManifestEntry]) -> HashMap<String, String> {
+    let used: HashSet<&str> = media.iter()
+        .map(|m| m.id.as_str())
+        .filter(|id| is_safe_id(id))
+        .collect();
+    let mut renamed = HashMap::new();
+    let mut next = 0;
+
+    for entry in media.iter().filter(|m| !is_safe_id(&m.id)) {
+        if renamed.contains_key(&entry.id) {
+            continue;
+        }
+        let new_id = loop {
+            next += 1;
+            let candidate = format!("media-{}", next);
+            if !used.contains(candidate.as_str()) {
+                break candidate;
+            }
+        };
+        renamed.insert(entry.id.clone(), new_id);
+    }
+    renamed
+}
+
+// 프로젝트 JSON의 페이지 미디어/버튼 이미지 참조를 바뀐 ID로 교체
+fn rename_media_references(project: &mut serde_json::Value, renamed: &HashMap<String, String>) {
+    if renamed.is_empty() {
+        return;
+    }
+
+    let pages = project.get_mut("pages").and_then(|p| p.as_array_mut());
+    for page in pages.into_iter().flatten() {
+        rename_field(page, "mediaId", renamed);
+
+        let buttons = page.get_mut("buttons").and_then(|b| b.as_array_mut());
+        for button in buttons.into_iter().flatten() {
+            rename_field(button, "imageId", renamed);
+        }
+    }
+}
+
+fn rename_field(value: &mut serde_json::Value, field: &str, renamed: &HashMap<String, String>) {
+    let Some(slot) = value.get_mut(field) else {
+        return;
+    };
+    if let Some(new_id) = slot.as_str().and_then(|id| renamed.get(id)).cloned() {
+        *slot = new_id.into();
+    }
+}
+
+// 프로젝트 JSON에서 페이지 미디어 ID와 버튼 이미지 ID 수집
+fn collect_media_ids(project: &serde_json::Value) -> (HashSet<String>, HashSet<String>) {
+    let mut page_media = HashSet::new();
+    let mut button_images = HashSet::new();
+
+    let pages = project.get("pages").and_then(|p| p.as_array());
+    for page in pages.into_iter().flatten() {
+        if let Some(media_id) = page.get("mediaId").and_then(|v| v.as_str()) {
+            page_media.insert(media_id.to_string());
+        }
+
+        let buttons = page.get("buttons").and_then(|b| b.as_array());
+        for button in buttons.into_iter().flatten() {
+            if let Some(image_id) = button.get("imageId").and_then(|v| v.as_str()) {
+                button_images.insert(image_id.to_string());
+            }
+        }
+    }
+
+    (page_media, button_images)
+}
+
+fn write_entry(
+    zip: &mut ZipWriter<fs::File>,
+    path: &str,
+    data: &[u8],
+    method: CompressionMethod,
+) -> Result<(), String> {
+    let options = SimpleFileOptions::default()
+        .compression_method(method)
+        .large_file(data.len() as u64 >= u32::MAX as u64);
+
+    zip.start_file(path, options)
+        .and_then(|_| zip.write_all(data).map_err(Into::into))
+        .map_err(|e| format!("{} 쓰기 실패: {}", path, e))
+}
+
+// 미디어 엔트리를 청크 단위로 읽어 zip 항목 하나로 기록
+fn write_media_entry(
+    zip: &mut ZipWriter<fs::File>,
+    path: &str,
+    payload: &Payload,
+    entry: &MediaManifestEntry,
+) -> Result<(), String> {
+    let total = entry.content_size();
+    let options = SimpleFileOptions::default()
+        .compression_method(CompressionMethod::Stored)
+        .large_file(total >= u32::MAX as u64);
+    zip.start_file(path, options)
+        .map_err(|e| format!("{} 쓰기 실패: {}", path, e))?;
+
+    let mut start = 0;
+    while start < total {
+        let end = (start + EXTRACT_CHUNK_SIZE).min(total) - 1;
+        zip.write_all(&payload.read_media_range(entry, start, end)?)
+            .map_err(|e| format!("{} 쓰기 실패: {}", path, e))?;
+        start = end + 1;
+    }
+    Ok(())
+}
+
+// 제품 exe에서 .tutorial 파일 복원
+// 서명/무결성을 확인하고, 암호화/라이선스 빌드는 작성자의 키 파일이 있어야 꺼냄 (payload_access)
+pub fn extract_exe_to_tutorial(exe_path: &Path, output_path: &Path, keys: ExtractKeys) -> Result<ExtractSummary, String> {
+    let mut payload = Payload::open(exe_path)?;
+    if payload.format() != PayloadFormat::V2 {
+        return Err("V1 포맷 exe는 복원할 수 없습니다. (미디어가 프로젝트 JSON에 포함된 구버전 빌드)".to_string());
+    }
+    payload_access::check_extract(&mut payload, keys)?;
+
+    let project_json = payload.project_json()?;
+    let mut project: serde_json::Value = serde_json::from_str(&project_json)
+        .map_err(|e| format!("프로젝트 데이터 파싱 실패: {}", e))?;
+
+    let project_name = project.get("name")
+        .and_then(|n| n.as_str())
+        .unwrap_or("Unknown")
+        .to_string();
+
+    // zip 항목 이름에 쓸 수 없는 미디어 ID는 바꾸고 프로젝트의 참조도 맞춤
+    let renamed = renamed_media_ids(&payload.manifest()?.media);
+    rename_media_references(&mut project, &renamed);
+
+    // 앱 아이콘이 내장된 빌드라면 복원하고 프로젝트에서 참조하도록 설정
+    let app_icon = payload.app_icon()?;
+    if app_icon.is_some() {
+        if let Some(obj) = project.as_object_mut() {
+            obj.insert("appIcon".to_string(), RESTORED_APP_ICON_ID.into());
+        }
+    }
+
+    if let Some(parent) = output_path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("출력 디렉토리 생성 실패: {}", e))?;
+    }
+    let file = fs::File::create(output_path)
+        .map_err(|e| format!("출력 파일 생성 실패: {}", e))?;
+
+    let result = write_tutorial_archive(
+        ZipWriter::new(file),
+        &payload,
+        &project,
+        &renamed,
+        project_name,
+        app_icon.as_deref(),
+    );
+
+    // 실패 시 불완전한 파일은 남기지 않음
+    if result.is_err() {
+        let _ = fs::remove_file(output_path);
+    }
+    result
+}
+
+// 복원한 데이터를 .tutorial(zip) 구조로 기록
+fn write_tutorial_archive(
+    mut zip: ZipWriter<fs::File>,
+    payload: &Payload,
+    project: &serde_json::Value,
+    renamed: &HashMap<String, String>,
+    project_name: String,
+    app_icon: Option<&[u8]>,
+) -> Result<ExtractSummary, String> {
+    let manifest = payload.manifest()?;
+    let (page_media, button_images) = collect_media_ids(project);
+
+    let tutorial_manifest = TutorialManifest {
+        version: "1.0.0",
+        format_version: 1,
+        created_at: std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0),
+        created_with: "Tutorial Maker v0.1.0",
+        project_name: project_name.clone(),
+    };
+    let manifest_json = serde_json::to_vec_pretty(&tutorial_manifest)
+        .map_err(|e| format!("manifest.json 직렬화 실패: {}", e))?;
+    write_entry(&mut zip, "manifest.json", &manifest_json, CompressionMethod::Deflated)?;
+
+    let project_json = serde_json::to_vec_pretty(project)
+        .map_err(|e| format!("project.json 직렬화 실패: {}", e))?;
+    write_entry(&mut zip, "project.json", &project_json, CompressionMethod::Deflated)?;
+
+    // 빈 폴더도 exporter와 동일하게 생성
+    for folder in ["media/", "buttons/", "icons/"] {
+        zip.add_directory(folder, SimpleFileOptions::default())
+            .map_err(|e| format!("{} 폴더 생성 실패: {}", folder, e))?;
+    }
+
+    let mut summary = ExtractSummary {
+        project_name,
+        media_count: 0,
+        button_count: 0,
+        has_app_icon: app_icon.is_some(),
+    };
+
+    // 빌드 시 만든 포스터 프레임은 프로젝트 미디어가 아니므로 제외 (다시 빌드하면 새로 만들어짐)
+    for entry in manifest.media.iter().filter(|m| m.poster_for.is_none()) {
+        let id = renamed.get(&entry.id).unwrap_or(&entry.id);
+        let file_name = format!("{}.{}", id, extension_for(entry));
+
+        // 같은 이미지가 페이지 미디어와 버튼에 모두 쓰이면 양쪽에 저장
+        let is_button = button_images.contains(id);
+        if page_media.contains(id) || !is_button {
+            write_media_entry(&mut zip, &format!("media/{}", file_name), payload, entry)?;
+            summary.media_count += 1;
+        }
+        if is_button {
+            write_media_entry(&mut zip, &format!("buttons/{}", file_name), payload, entry)?;
+            summary.button_count += 1;
+        }
+    }
+
+    if let Some(icon) = app_icon {
+        write_entry(&mut zip, &format!("icons/{}.png", RESTORED_APP_ICON_ID), icon, CompressionMethod::Stored)?;
+    }
+
+    zip.finish()
+        .map_err(|e| format!("튜토리얼 파일 저장 실패: {}", e))?;
+
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::license::{LicenseIssuer, LicenseOptions};
+    use crate::payload::{sha256_hex, BuildManifest, MAGIC_BYTES_V2};
+    use crate::payload_crypto::{self, PayloadEncryption, PayloadKey};
+
+    const STUB_EXE: &[u8] = b"MZ-template-executable";
+    const KEY_HEX: &str = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f";
+    const ISSUER_KEY_HEX: &str = "0909090909090909090909090909090909090909090909090909090909090909";
+
+    // 테스트마다 따로 쓰는 임시 폴더 (끝나면 삭제)
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> TempDir {
+            let dir = std::env::temp_dir().join(format!("tutorial_extract_{}_{}", name, std::process::id()));
+            let _ = fs::remove_dir_all(&dir);
+            fs::create_dir_all(&dir).unwrap();
+            TempDir(dir)
+        }
+
+        fn write(&self, name: &str, data: &[u8]) -> PathBuf {
+            let path = self.0.join(name);
+            fs::write(&path, data).unwrap();
+            path
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    // 빌드와 같은 배치의 V2 제품: [원본][미디어...][프로젝트 JSON][매니페스트][크기][매직]
+    struct Product {
+        bytes: Vec<u8>,
+        media: Vec<MediaManifestEntry>,
+    }
+
+    impl Product {
+        fn new() -> Product {
+            Product { bytes: STUB_EXE.to_vec(), media: Vec::new() }
+        }
+
+        fn add(&mut self, id: &str, mime_type: &str, stored: &[u8]) -> &mut MediaManifestEntry {
+            self.media.push(MediaManifestEntry {
+                id: id.to_string(),
+                name: format!("{}.bin", id),
+                mime_type: mime_type.to_string(),
+                offset: self.bytes.len() as u64,
+                size: stored.len() as u64,
+                sha256: Some(sha256_hex(stored)),
+                compression: None,
+                original_size: None,
+                encryption: None,
+                nonce: None,
+                shared_from: None,
+                poster_for: None,
+            });
+            self.bytes.extend_from_slice(stored);
+            self.media.last_mut().unwrap()
+        }
+
+        fn finish(self, project: &serde_json::Value, edit: impl FnOnce(&mut BuildManifest)) -> Vec<u8> {
+            let project_json = serde_json::to_vec(project).unwrap();
+            let mut bytes = self.bytes;
+            let mut manifest: BuildManifest = serde_json::from_value(serde_json::json!({
+                "project_json_offset": bytes.len(),
+                "project_json_size": project_json.len(),
+                "media": self.media,
+                "app_icon_offset": null,
+                "app_icon_size": null,
+                "project_json_sha256": sha256_hex(&project_json),
+            })).unwrap();
+            bytes.extend_from_slice(&project_json);
+            edit(&mut manifest);
+
+            let json = serde_json::to_vec(&manifest).unwrap();
+            bytes.extend_from_slice(&json);
+            bytes.extend_from_slice(&(json.len() as u64).to_le_bytes());
+            bytes.extend_from_slice(MAGIC_BYTES_V2);
+            bytes
+        }
+    }
+
+    fn project(pages: serde_json::Value) -> serde_json::Value {
+        serde_json::json!({ "id": "p1", "name": "복원 테스트", "pages": pages })
+    }
+
+    fn read_archive(path: &Path) -> HashMap<String, Vec<u8>> {
+        let mut archive = zip::ZipArchive::new(fs::File::open(path).unwrap()).unwrap();
+        (0..archive.len())
+            .map(|i| {
+                let mut entry = archive.by_index(i).unwrap();
+                let mut data = Vec::new();
+                entry.read_to_end(&mut data).unwrap();
+                (entry.name().to_string(), data)
+            })
+            .collect()
+    }
+
+    fn restored_project(files: &HashMap<String, Vec<u8>>) -> serde_json::Value {
+        serde_json::from_slice(&files["project.json"]).unwrap()
+    }
+
+    #[test]
+    fn round_trips_project_and_media() {
+        let dir = TempDir::new("round_trip");
+        let video = b"video-bytes".repeat(1000);
+        let image = b"png-bytes".to_vec();
+
+        let mut product = Product::new();
+        product.add("intro", "video/mp4", &video);
+        product.add("start-button", "image/png", &image);
+        product.add("intro-poster", "image/jpeg", b"poster").poster_for = Some("intro".to_string());
+        let pages = serde_json::json!([
+            { "id": "page-1", "mediaId": "intro", "buttons": [{ "id": "b1", "imageId": "start-button" }] },
+        ]);
+        let exe = dir.write("product.exe", &product.finish(&project(pages.clone()), |_| {}));
+
+        let output = dir.0.join("restored.tutorial");
+        let summary = extract_exe_to_tutorial(&exe, &output, ExtractKeys::default()).unwrap();
+        assert_eq!(summary.project_name, "복원 테스트");
+        assert_eq!((summary.media_count, summary.button_count), (1, 1));
+        assert!(!summary.has_app_icon);
+
+        let files = read_archive(&output);
+        assert_eq!(files["media/intro.mp4"], video);
+        assert_eq!(files["buttons/start-button.png"], image);
+        // 포스터 프레임은 프로젝트 미디어가 아니므로 복원하지 않음
+        assert!(!files.keys().any(|name| name.contains("intro-poster")));
+        assert_eq!(restored_project(&files), project(pages));
+
+        let manifest: serde_json::Value = serde_json::from_slice(&files["manifest.json"]).unwrap();
+        assert_eq!(manifest["projectName"], "복원 테스트");
+    }
+
+    #[test]
+    fn renames_unsafe_media_ids() {
+        let dir = TempDir::new("unsafe_ids");
+        let mut product = Product::new();
+        product.add("../../evil", "video/mp4", b"escape");
+        product.add("media-1", "video/mp4", b"existing");
+        // MIME 타입을 모르면 파일명 확장자를 쓰지만 경로 문자가 섞인 확장자는 쓰지 않음
+        product.add("C:\\icon", "image/unknown", b"drive").name = "evil.p\\ng".to_string();
+        let pages = serde_json::json!([
+            { "id": "page-1", "mediaId": "../../evil", "buttons": [{ "id": "b1", "imageId": "C:\\icon" }] },
+            { "id": "page-2", "mediaId": "media-1", "buttons": [] },
+        ]);
+        let exe = dir.write("product.exe", &product.finish(&project(pages), |_| {}));
+
+        let output = dir.0.join("restored.tutorial");
+        extract_exe_to_tutorial(&exe, &output, ExtractKeys::default()).unwrap();
+
+        let files = read_archive(&output);
+        let mut names: Vec<&str> = files.keys().map(String::as_str).collect();
+        names.sort();
+        assert_eq!(names, [
+            "buttons/", "buttons/media-3.png", "icons/", "manifest.json",
+            "media/", "media/media-1.mp4", "media/media-2.mp4", "project.json",
+        ]);
+        assert_eq!(files["media/media-2.mp4"], b"escape");
+        assert_eq!(files["media/media-1.mp4"], b"existing");
+
+        // 프로젝트의 참조도 바뀐 ID를 가리킴
+        let restored = restored_project(&files);
+        assert_eq!(restored["pages"][0]["mediaId"], "media-2");
+        assert_eq!(restored["pages"][0]["buttons"][0]["imageId"], "media-3");
+        assert_eq!(restored["pages"][1]["mediaId"], "media-1");
+    }
+
+    #[test]
+    fn rejects_tampered_media() {
+        let dir = TempDir::new("tampered");
+        let mut product = Product::new();
+        product.add("intro", "video/mp4", b"original").sha256 = Some(sha256_hex(b"other"));
+        let exe = dir.write("product.exe", &product.finish(&project(serde_json::json!([])), |_| {}));
+
+        let output = dir.0.join("restored.tutorial");
+        let error = extract_exe_to_tutorial(&exe, &output, ExtractKeys::default()).unwrap_err();
+        assert!(error.contains("손상"), "{}", error);
+        assert!(!output.exists());
+    }
+
+    #[test]
+    fn encrypted_product_requires_key_file() {
+        let dir = TempDir::new("encrypted");
+        let key = PayloadKey::from_hex(KEY_HEX).unwrap();
+        let nonce = payload_crypto::new_nonce();
+        let sealed = payload_crypto::encrypt(&key, &nonce, "intro", b"secret video").unwrap();
+
+        let mut product = Product::new();
+        let entry = product.add("intro", "video/mp4", &sealed);
+        entry.encryption = Some(PayloadEncryption::Aes256GcmChunked);
+        entry.nonce = Some(nonce);
+        entry.original_size = Some(12);
+        let exe = dir.write("product.exe", &product.finish(&project(serde_json::json!([])), |m| {
+            m.encryption_key_id = Some(key.key_id().to_string());
+        }));
+        let output = dir.0.join("restored.tutorial");
+
+        // 제품 옆 라이선스 파일만으로는 복원하지 않음
+        dir.write(payload_crypto::LICENSE_FILE_NAME, KEY_HEX.as_bytes());
+        let error = extract_exe_to_tutorial(&exe, &output, ExtractKeys::default()).unwrap_err();
+        assert!(error.contains("--key-file"), "{}", error);
+
+        let wrong_key = dir.write("wrong.key", "ff".repeat(32).as_bytes());
+        let keys = ExtractKeys { key_file: Some(&wrong_key), ..ExtractKeys::default() };
+        assert!(extract_exe_to_tutorial(&exe, &output, keys).is_err());
+
+        let key_file = dir.write("build.key", KEY_HEX.as_bytes());
+        let keys = ExtractKeys { key_file: Some(&key_file), ..ExtractKeys::default() };
+        extract_exe_to_tutorial(&exe, &output, keys).unwrap();
+        assert_eq!(read_archive(&output)["media/intro.mp4"], b"secret video");
+    }
+
+    #[test]
+    fn licensed_product_requires_issuer_key() {
+        let dir = TempDir::new("licensed");
+        let issuer_key = dir.write("issuer.key", ISSUER_KEY_HEX.as_bytes());
+        let issuer = LicenseIssuer::new(&LicenseOptions {
+            issuer: "교육팀".to_string(),
+            // 이미 만료된 라이선스도 발급자는 복원할 수 있음
+            expires_at: Some(1),
+            allowed_machine_ids: Vec::new(),
+            signing_key_file: issuer_key.to_string_lossy().to_string(),
+        }).unwrap();
+
+        let exe = dir.write("product.exe", &Product::new().finish(&project(serde_json::json!([])), |m| {
+            m.license = Some(issuer.issue(m.project_json_sha256.as_deref()).unwrap());
+        }));
+        let output = dir.0.join("restored.tutorial");
+
+        let error = extract_exe_to_tutorial(&exe, &output, ExtractKeys::default()).unwrap_err();
+        assert!(error.contains("--license-key"), "{}", error);
+
+        let other_key = dir.write("other.key", "08".repeat(32).as_bytes());
+        let keys = ExtractKeys { license_key_file: Some(&other_key), ..ExtractKeys::default() };
+        let error = extract_exe_to_tutorial(&exe, &output, keys).unwrap_err();
+        assert!(error.contains("발급자의 서명 키가 아닙니다"), "{}", error);
+
+        let keys = ExtractKeys { license_key_file: Some(&issuer_key), ..ExtractKeys::default() };
+        assert_eq!(extract_exe_to_tutorial(&exe, &output, keys).unwrap().project_name, "복원 테스트");
+    }
+
+    #[test]
+    fn renamed_ids_skip_existing_ids() {
+        let mut product = Product::new();
+        product.add("media-1", "video/mp4", b"a");
+        product.add("a/b", "video/mp4", b"b");
+        product.add("a/b", "video/mp4", b"c");
+        product.add("", "video/mp4", b"d");
+        let renamed = renamed_media_ids(&product.media);
+        assert_eq!(renamed.len(), 2);
+        assert_eq!(renamed["a/b"], "media-2");
+        assert_eq!(renamed[""], "media-3");
+        assert!(renamed_media_ids(&product.media[..1]).is_empty());
+    }
+}
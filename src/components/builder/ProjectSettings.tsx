@@ -22,6 +22,7 @@ const ProjectSettings: React.FC<ProjectSettingsProps> = ({
 }) => {
   const [iconPreview, setIconPreview] = useState<string | null>(null)
   const [isUploading, setIsUploading] = useState(false)
+  const [pinInput, setPinInput] = useState('')
 
   useEffect(() => {
     loadIconPreview()
@@ -52,6 +53,19 @@ const ProjectSettings: React.FC<ProjectSettingsProps> = ({
     }
   }
 
+  // 관리자 PIN은 해시로만 프로젝트에 저장
+  const handleSetExitPin = async () => {
+    try {
+      const { invoke } = await import('@tauri-apps/api/core')
+      const pinHash = await invoke<string>('hash_exit_pin', { pin: pinInput })
+      handleSettingsChange('exitPinHash', pinHash)
+      setPinInput('')
+    } catch (error) {
+      console.error('Failed to set exit PIN:', error)
+      alert(`관리자 PIN 설정에 실패했습니다.\n${error}`)
+    }
+  }
+
   const handleSettingsChange = (key: keyof ProjectSettingsType, value: any) => {
     onUpdate({
       settings: {
@@ -226,6 +240,61 @@ const ProjectSettings: React.FC<ProjectSettingsProps> = ({
                 <option value='F12'>F12</option>
               </select>
             </div>
+
+            <div>
+              <label className='mb-1 block text-sm font-medium text-gray-700'>
+                관리자 PIN (선택사항)
+              </label>
+              {project.settings.exitPinHash ? (
+                <div className='flex items-center justify-between rounded-lg border border-gray-300 px-3 py-2'>
+                  <span className='text-sm text-gray-600'>
+                    설정됨 - 종료 시 PIN을 입력해야 합니다
+                  </span>
+                  <button
+                    onClick={() =>
+                      handleSettingsChange('exitPinHash', undefined)
+                    }
+                    className='text-sm text-red-600 hover:text-red-700'
+                  >
+                    해제
+                  </button>
+                </div>
+              ) : (
+                <div className='flex gap-2'>
+                  <input
+                    type='password'
+                    inputMode='numeric'
+                    value={pinInput}
+                    onChange={(e) => setPinInput(e.target.value)}
+                    className='w-full rounded-lg border border-gray-300 px-3 py-2 focus:ring-2 focus:ring-blue-500'
+                    placeholder='4~12자리 숫자'
+                  />
+                  <button
+                    onClick={handleSetExitPin}
+                    disabled={!pinInput}
+                    className='rounded-lg bg-gray-600 px-4 py-2 text-white hover:bg-gray-700 disabled:opacity-50'
+                  >
+                    설정
+                  </button>
+                </div>
+              )}
+            </div>
+
+            <div>
+              <label className='flex cursor-pointer items-center'>
+                <input
+                  type='checkbox'
+                  checked={project.settings.blockClose ?? false}
+                  onChange={(e) =>
+                    handleSettingsChange('blockClose', e.target.checked)
+                  }
+                  className='h-4 w-4 rounded text-blue-600 focus:ring-2 focus:ring-blue-500'
+                />
+                <span className='ml-2 text-sm font-medium text-gray-700'>
+                  Alt+F4/창 닫기 차단 (종료 확인 후에만 종료)
+                </span>
+              </label>
+            </div>
           </div>
         </section>
 
@@ -0,0 +1,41 @@
+import React from 'react'
+import ConfirmDialog from '../common/ConfirmDialog'
+import ExitPinDialog from './ExitPinDialog'
+
+type ExitDialogProps = {
+  isOpen: boolean
+  pinRequired: boolean
+  onConfirm: (pin?: string) => Promise<void> // PIN이 틀리면 에러 메시지로 reject
+  onCancel: () => void
+}
+
+// 종료 확인 다이얼로그 (관리자 PIN이 설정된 제품은 PIN 입력)
+const ExitDialog: React.FC<ExitDialogProps> = ({
+  isOpen,
+  pinRequired,
+  onConfirm,
+  onCancel,
+}) => {
+  if (pinRequired) {
+    return (
+      <ExitPinDialog isOpen={isOpen} onConfirm={onConfirm} onCancel={onCancel} />
+    )
+  }
+
+  return (
+    <ConfirmDialog
+      isOpen={isOpen}
+      title='앱 종료'
+      message='앱을 종료하시겠습니까?'
+      confirmText='종료'
+      cancelText='취소'
+      onConfirm={() => {
+        onConfirm().catch((err) => console.error('종료 실패:', err))
+      }}
+      onCancel={onCancel}
+      variant='warning'
+    />
+  )
+}
+
+export default ExitDialog
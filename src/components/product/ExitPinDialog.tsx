@@ -0,0 +1,81 @@
+import React, { useState } from 'react'
+
+type ExitPinDialogProps = {
+  isOpen: boolean
+  onConfirm: (pin: string) => Promise<void> // PIN이 틀리면 에러 메시지로 reject
+  onCancel: () => void
+}
+
+// 키오스크 종료 시 관리자 PIN 입력
+const ExitPinDialog: React.FC<ExitPinDialogProps> = ({
+  isOpen,
+  onConfirm,
+  onCancel,
+}) => {
+  const [pin, setPin] = useState('')
+  const [error, setError] = useState<string | null>(null)
+  const [isChecking, setIsChecking] = useState(false)
+
+  if (!isOpen) return null
+
+  const handleCancel = () => {
+    setPin('')
+    setError(null)
+    onCancel()
+  }
+
+  const handleSubmit = async (e: React.FormEvent) => {
+    e.preventDefault()
+    setIsChecking(true)
+    try {
+      await onConfirm(pin)
+    } catch (err) {
+      setError(String(err))
+      setPin('')
+    } finally {
+      setIsChecking(false)
+    }
+  }
+
+  return (
+    <div className='fixed inset-0 z-50 flex items-center justify-center bg-black bg-opacity-50'>
+      <form
+        onSubmit={handleSubmit}
+        className='mx-4 w-full max-w-md rounded-lg bg-white p-6 shadow-xl'
+      >
+        <h3 className='mb-2 text-lg font-bold text-gray-900'>앱 종료</h3>
+        <p className='mb-4 text-gray-600'>
+          종료하려면 관리자 PIN을 입력하세요.
+        </p>
+        <input
+          type='password'
+          inputMode='numeric'
+          autoFocus
+          value={pin}
+          onChange={(e) => setPin(e.target.value)}
+          className='mb-2 w-full rounded-lg border border-gray-300 px-3 py-2 focus:ring-2 focus:ring-blue-500'
+          placeholder='관리자 PIN'
+        />
+        {error && <p className='mb-2 text-sm text-red-600'>{error}</p>}
+        <div className='mt-4 flex justify-end gap-3'>
+          <button
+            type='button'
+            onClick={handleCancel}
+            className='rounded-lg border border-gray-300 bg-white px-4 py-2 text-gray-700 hover:bg-gray-50'
+          >
+            취소
+          </button>
+          <button
+            type='submit'
+            disabled={!pin || isChecking}
+            className='rounded-lg bg-yellow-600 px-4 py-2 text-white hover:bg-yellow-700 disabled:opacity-50'
+          >
+            종료
+          </button>
+        </div>
+      </form>
+    </div>
+  )
+}
+
+export default ExitPinDialog
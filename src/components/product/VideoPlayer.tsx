@@ -6,6 +6,7 @@ import TouchAreaComponent from './TouchAreaComponent'
 type VideoPlayerProps = {
   page: Page
   mediaUrl: string
+  posterUrl?: string // 비디오가 로드되기 전 보여줄 포스터 프레임
   buttonImageUrls?: Record<string, string>
   onVideoEnd: () => void
   onButtonClick: (buttonId: string) => void
@@ -17,6 +18,7 @@ type VideoPlayerProps = {
 const VideoPlayer: React.FC<VideoPlayerProps> = ({
   page,
   mediaUrl,
+  posterUrl,
   buttonImageUrls = {},
   onVideoEnd,
   onButtonClick,
@@ -103,6 +105,7 @@ const VideoPlayer: React.FC<VideoPlayerProps> = ({
           ref={videoRef}
           className='h-full w-full object-contain'
           onEnded={handleVideoEnded}
+          poster={posterUrl}
           playsInline
         >
           <source src={mediaUrl} type='video/mp4' />
@@ -0,0 +1,86 @@
+import { useState, useEffect } from 'react'
+import type { ProjectSettings } from '../types/project'
+
+// 제품 시작 시 Rust에 등록된 종료 설정 (exit_guard::ExitSettings와 일치해야 함)
+interface ExitSettings {
+  exitKey: string | null
+  pinRequired: boolean
+  blockClose: boolean
+}
+
+// 종료 키가 눌렸거나 닫기 요청을 막았을 때 Rust가 보내는 이벤트 (EXIT_REQUEST_EVENT와 일치해야 함)
+const EXIT_REQUEST_EVENT = 'exit-requested'
+
+// 종료 키/관리자 PIN 처리
+// 제품에서는 종료 키 판단과 PIN 확인을 Rust가 하고, 여기서는 요청이 오면 확인 창만 띄움
+// 미리보기/뷰어(등록된 설정 없음)에서는 프로젝트 설정의 종료 키로 확인 없이 종료
+// settings: 프로젝트를 읽지 못한 에러 화면에서는 없음 (종료 요청만 처리)
+export function useExitGuard(
+  settings: ProjectSettings | undefined,
+  onExit?: () => void
+) {
+  const isLocal = Boolean(onExit) || !('__TAURI_INTERNALS__' in window)
+  // undefined: 아직 확인 중, null: 빌더(등록된 설정 없음)
+  const [registered, setRegistered] = useState<
+    ExitSettings | null | undefined
+  >(isLocal ? null : undefined)
+  const [isExitDialogOpen, setIsExitDialogOpen] = useState(false)
+
+  useEffect(() => {
+    if (isLocal) return
+
+    let unlisten: (() => void) | undefined
+    let disposed = false
+
+    const register = async () => {
+      const { invoke } = await import('@tauri-apps/api/core')
+      const { listen } = await import('@tauri-apps/api/event')
+
+      const exitSettings = await invoke<ExitSettings | null>(
+        'get_exit_settings'
+      )
+      if (disposed) return
+      setRegistered(exitSettings)
+      if (!exitSettings) return
+
+      // 종료 키가 눌렸거나 Alt+F4/닫기 버튼이 막히면 종료 확인 창 표시
+      const stop = await listen(EXIT_REQUEST_EVENT, () =>
+        setIsExitDialogOpen(true)
+      )
+      if (disposed) {
+        stop()
+      } else {
+        unlisten = stop
+      }
+    }
+
+    register().catch((err) => console.error('종료 설정 로드 실패:', err))
+
+    return () => {
+      disposed = true
+      unlisten?.()
+    }
+  }, [isLocal])
+
+  // 종료 (PIN이 틀리면 에러 메시지로 reject)
+  const confirmExit = async (pin?: string) => {
+    if (onExit) {
+      onExit()
+    } else if (registered) {
+      const { invoke } = await import('@tauri-apps/api/core')
+      await invoke('request_exit', { pin: pin ?? null })
+    } else {
+      window.close()
+    }
+  }
+
+  return {
+    // 화면에서 직접 확인할 종료 키 (제품에서는 Rust가 확인하므로 null)
+    exitKey: registered === null ? settings?.exitKey ?? null : null,
+    pinRequired: registered?.pinRequired ?? false,
+    isExitDialogOpen,
+    openExitDialog: () => setIsExitDialogOpen(true),
+    closeExitDialog: () => setIsExitDialogOpen(false),
+    confirmExit,
+  }
+}
@@ -19,23 +19,20 @@ function base64ToBlobUrl(base64: string, mimeType: string): string {
   return URL.createObjectURL(blob)
 }
 
-// Uint8Array를 Blob URL로 변환 (V2용)
-function bytesToBlobUrl(bytes: Uint8Array, mimeType: string): string {
-  // 새 ArrayBuffer로 복사
-  const newBuffer = new ArrayBuffer(bytes.length)
-  const newView = new Uint8Array(newBuffer)
-  newView.set(bytes)
-  const blob = new Blob([newBuffer], { type: mimeType })
-  return URL.createObjectURL(blob)
-}
+// 내장 미디어 스트리밍 URI 스킴 (Rust의 MEDIA_PROTOCOL과 일치해야 함)
+const MEDIA_PROTOCOL = 'tutorial-media'
 
 export function useProductProject(projectId?: string) {
   const [project, setProject] = useState<Project | null>(null)
   const [mediaUrls, setMediaUrls] = useState<Record<string, string>>({})
+  // 비디오 ID → 포스터 프레임 URL (빌드 시 추출된 경우만)
+  const [posterUrls, setPosterUrls] = useState<Record<string, string>>({})
   const [buttonImageUrls, setButtonImageUrls] = useState<Record<string, string>>(
     {}
   )
   const [isLoading, setIsLoading] = useState(true)
+  // 라이선스 만료/기기 불일치 등 제품을 열 수 없는 사유
+  const [loadError, setLoadError] = useState<string | null>(null)
 
   useEffect(() => {
     loadProjectData()
@@ -103,12 +100,18 @@ export function useProductProject(projectId?: string) {
   // 프로덕트 모드 데이터 로드
   const loadProductModeData = async () => {
     try {
-      const { invoke } = await import('@tauri-apps/api/core')
+      const { invoke, convertFileSrc } = await import('@tauri-apps/api/core')
 
       // V2 시도: read_project_file_v2 + read_embedded_media
       try {
         // 프로젝트 JSON 읽기 (V2 또는 V1 자동 선택)
-        const projectJson = await invoke<string>('read_project_file_v2')
+        let projectJson: string
+        try {
+          projectJson = await invoke<string>('read_project_file_v2')
+        } catch (e) {
+          setLoadError(String(e))
+          return
+        }
         const projectData: Project = JSON.parse(projectJson)
         setProject(projectData)
 
@@ -119,14 +122,17 @@ export function useProductProject(projectId?: string) {
 
           const urls: Record<string, string> = {}
           const btnUrls: Record<string, string> = {}
+          const posters: Record<string, string> = {}
 
-          // 각 미디어 로드
+          // 각 미디어는 exe에서 직접 스트리밍 (메모리에 전체 로드하지 않음)
           for (const entry of mediaManifest) {
-            const mediaBytes = await invoke<number[]>('read_embedded_media', {
-              mediaId: entry.id,
-            })
-            const uint8Array = new Uint8Array(mediaBytes)
-            const blobUrl = bytesToBlobUrl(uint8Array, entry.mimeType)
+            const mediaUrl = convertFileSrc(entry.id, MEDIA_PROTOCOL)
+
+            // 포스터 프레임은 해당 비디오의 포스터로만 사용
+            if (entry.poster_for) {
+              posters[entry.poster_for] = mediaUrl
+              continue
+            }
 
             // 미디어 타입에 따라 분류
             const isButtonImage = projectData.pages.some((page) =>
@@ -137,20 +143,21 @@ export function useProductProject(projectId?: string) {
             )
 
             if (isButtonImage) {
-              btnUrls[entry.id] = blobUrl
+              btnUrls[entry.id] = mediaUrl
             }
             if (isPageMedia) {
-              urls[entry.id] = blobUrl
+              urls[entry.id] = mediaUrl
             }
             // 분류 안 되면 둘 다에 추가
             if (!isButtonImage && !isPageMedia) {
-              urls[entry.id] = blobUrl
-              btnUrls[entry.id] = blobUrl
+              urls[entry.id] = mediaUrl
+              btnUrls[entry.id] = mediaUrl
             }
           }
 
           setMediaUrls(urls)
           setButtonImageUrls(btnUrls)
+          setPosterUrls(posters)
           return
         } catch {
           // V2 매니페스트 실패 -> V1 방식 (embeddedMedia) 시도
@@ -197,5 +204,12 @@ export function useProductProject(projectId?: string) {
     }
   }
 
-  return { project, mediaUrls, buttonImageUrls, isLoading }
+  return {
+    project,
+    mediaUrls,
+    posterUrls,
+    buttonImageUrls,
+    isLoading,
+    loadError,
+  }
 }
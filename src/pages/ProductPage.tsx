@@ -4,15 +4,17 @@ import EntryPage from '../components/product/EntryPage'
 import ControlOverlay from '../components/product/ControlOverlay'
 import LoadingScreen from '../components/product/LoadingScreen'
 import ErrorScreen from '../components/product/ErrorScreen'
-import ConfirmDialog from '../components/common/ConfirmDialog'
+import ExitDialog from '../components/product/ExitDialog'
 import { useProductProject } from '../hooks/useProductProject'
 import { usePageNavigation } from '../hooks/usePageNavigation'
-import type { Project } from '../types/project'
+import { useExitGuard } from '../hooks/useExitGuard'
+import type { Project, ProjectSettings } from '../types/project'
 
 // 프레젠테이션 전용 컴포넌트 (외부에서 데이터 주입)
 export interface ProductPageContentProps {
   project: Project
   mediaUrls: Record<string, string>
+  posterUrls?: Record<string, string> // 비디오 ID → 포스터 프레임 (빌드 시 추출)
   buttonImageUrls: Record<string, string>
   onExit?: () => void // 종료 시 콜백 (옵션)
 }
@@ -20,6 +22,7 @@ export interface ProductPageContentProps {
 export const ProductPageContent: React.FC<ProductPageContentProps> = ({
   project,
   mediaUrls,
+  posterUrls = {},
   buttonImageUrls,
   onExit,
 }) => {
@@ -33,7 +36,15 @@ export const ProductPageContent: React.FC<ProductPageContentProps> = ({
     goToPage,
   } = usePageNavigation(project)
 
-  const [exitConfirm, setExitConfirm] = useState(false)
+  const {
+    exitKey,
+    pinRequired,
+    isExitDialogOpen,
+    openExitDialog,
+    closeExitDialog,
+    confirmExit,
+  } = useExitGuard(project.settings, onExit)
+
   const [showEntryPage, setShowEntryPage] = useState(true)
   const [resumePlaybackSignal, setResumePlaybackSignal] = useState(0)
 
@@ -59,18 +70,12 @@ export const ProductPageContent: React.FC<ProductPageContentProps> = ({
     }
   }
 
-  // 종료 처리
-  const handleExit = () => {
-    if (onExit) {
-      onExit()
-    } else {
-      window.close()
-    }
-  }
-
   // 키보드 이벤트 처리
   useEffect(() => {
     const handleKeyPress = (e: KeyboardEvent) => {
+      // 종료 확인 창이 열려 있으면 PIN 입력 외에는 무시
+      if (isExitDialogOpen) return
+
       // Meta+1 (Mac) 또는 Ctrl+1 (Windows)로 전체화면 토글
       if ((e.metaKey || e.ctrlKey) && e.key === '1') {
         e.preventDefault()
@@ -78,9 +83,10 @@ export const ProductPageContent: React.FC<ProductPageContentProps> = ({
         return
       }
 
-      // 종료 키 확인
-      if (project.settings.exitKey && e.key === project.settings.exitKey) {
-        setExitConfirm(true)
+      // 종료 키 확인 (미리보기/뷰어만, 제품은 Rust가 확인해 종료 요청을 보냄)
+      if (exitKey && e.key === exitKey) {
+        e.preventDefault()
+        openExitDialog()
         return
       }
 
@@ -96,7 +102,15 @@ export const ProductPageContent: React.FC<ProductPageContentProps> = ({
 
     window.addEventListener('keydown', handleKeyPress)
     return () => window.removeEventListener('keydown', handleKeyPress)
-  }, [project, currentPageIndex, goToNextPage, goToPreviousPage, goToHome])
+  }, [
+    project,
+    currentPageIndex,
+    goToNextPage,
+    goToPreviousPage,
+    goToHome,
+    exitKey,
+    isExitDialogOpen,
+  ])
 
   const handleVideoEnd = () => {
     // 단일 재생 모드일 때만 자동으로 다음 페이지로
@@ -145,29 +159,32 @@ export const ProductPageContent: React.FC<ProductPageContentProps> = ({
     setResumePlaybackSignal((prev) => prev + 1)
   }
 
+  const exitDialog = (
+    <ExitDialog
+      isOpen={isExitDialogOpen}
+      pinRequired={pinRequired}
+      onConfirm={confirmExit}
+      onCancel={closeExitDialog}
+    />
+  )
+
   // 페이지 없음
   if (project.pages.length === 0) {
     return (
-      <ErrorScreen
-        title='페이지가 없습니다'
-        message='빌더 페이지에서 페이지를 추가해주세요'
-      />
+      <>
+        {exitDialog}
+        <ErrorScreen
+          title='페이지가 없습니다'
+          message='빌더 페이지에서 페이지를 추가해주세요'
+        />
+      </>
     )
   }
 
   return (
     <div className='relative h-screen w-screen overflow-hidden bg-black'>
-      {/* 종료 확인 다이얼로그 */}
-      <ConfirmDialog
-        isOpen={exitConfirm}
-        title='앱 종료'
-        message='앱을 종료하시겠습니까?'
-        confirmText='종료'
-        cancelText='취소'
-        onConfirm={handleExit}
-        onCancel={() => setExitConfirm(false)}
-        variant='warning'
-      />
+      {/* 종료 확인 다이얼로그 (관리자 PIN이 설정된 제품은 PIN 입력) */}
+      {exitDialog}
 
       {/* 입구 페이지 */}
       {showEntryPage && (
@@ -196,6 +213,7 @@ export const ProductPageContent: React.FC<ProductPageContentProps> = ({
               <VideoPlayer
                 page={page}
                 mediaUrl={mediaUrl}
+                posterUrl={page.mediaId ? posterUrls[page.mediaId] : undefined}
                 buttonImageUrls={buttonImageUrls}
                 onVideoEnd={isCurrentPage ? handleVideoEnd : () => {}}
                 onButtonClick={isCurrentPage ? handleButtonClick : () => {}}
@@ -231,24 +249,68 @@ export const ProductPageContent: React.FC<ProductPageContentProps> = ({
   )
 }
 
+// 프로젝트를 열지 못한 제품 화면
+// 에러 화면에서도 종료 요청(Alt+F4/닫기 버튼)을 받아 종료 확인 창을 띄움
+const ProductErrorScreen: React.FC<{
+  title: string
+  message: string
+  settings?: ProjectSettings
+}> = ({ title, message, settings }) => {
+  const {
+    pinRequired,
+    isExitDialogOpen,
+    closeExitDialog,
+    confirmExit,
+  } = useExitGuard(settings)
+
+  return (
+    <>
+      <ExitDialog
+        isOpen={isExitDialogOpen}
+        pinRequired={pinRequired}
+        onConfirm={confirmExit}
+        onCancel={closeExitDialog}
+      />
+      <ErrorScreen title={title} message={message} />
+    </>
+  )
+}
+
 // 기존 ProductPage (useProductProject 훅 사용)
 interface ProductPageProps {
   projectId?: string // 개발 모드 미리보기에서 특정 프로젝트 ID 전달
 }
 
 const ProductPage: React.FC<ProductPageProps> = ({ projectId }) => {
-  const { project, mediaUrls, buttonImageUrls, isLoading } =
-    useProductProject(projectId)
+  const {
+    project,
+    mediaUrls,
+    posterUrls,
+    buttonImageUrls,
+    isLoading,
+    loadError,
+  } = useProductProject(projectId)
 
   // 로딩 중
   if (isLoading) {
     return <LoadingScreen />
   }
 
+  // 라이선스 만료, 변조 등으로 열 수 없음
+  if (loadError) {
+    return (
+      <ProductErrorScreen
+        title='튜토리얼을 열 수 없습니다'
+        message={loadError}
+        settings={project?.settings}
+      />
+    )
+  }
+
   // 프로젝트 없음
   if (!project) {
     return (
-      <ErrorScreen
+      <ProductErrorScreen
         title='프로젝트를 찾을 수 없습니다'
         message='빌더 페이지에서 프로젝트를 먼저 만들어주세요'
       />
@@ -259,6 +321,7 @@ const ProductPage: React.FC<ProductPageProps> = ({ projectId }) => {
     <ProductPageContent
       project={project}
       mediaUrls={mediaUrls}
+      posterUrls={posterUrls}
       buttonImageUrls={buttonImageUrls}
     />
   )
@@ -15,6 +15,8 @@ export interface ProjectSettings {
   windowHeight: number
   fullscreen: boolean
   exitKey?: string // "ESC", "F11" 등
+  exitPinHash?: string // 종료 시 확인할 관리자 PIN 해시 (Rust hash_exit_pin)
+  blockClose?: boolean // Alt+F4/창 닫기로 닫지 못하게 막고 PIN 입력 요청
   showProgress: boolean // 진행 상황 표시
   showHomeButton: boolean
   showBackButton: boolean
@@ -78,20 +80,52 @@ export interface BuildProject extends Omit<Project, 'appIcon'> {
 }
 
 // 바이너리 빌드용 (큰 프로젝트) - 미디어가 exe에 바이너리로 포함됨
+// exe에 저장되는 형식 그대로 전달되므로 Rust(payload.rs)와 같은 snake_case 필드 이름 사용
+// (매니페스트 서명이 직렬화된 바이트 기준이라 Rust 쪽 이름을 바꿀 수 없음)
 export interface MediaManifestEntry {
   id: string
   name: string
-  mimeType: string
+  mime_type: string
   offset: number // exe 내 시작 위치
   size: number // 바이트 크기
+  sha256?: string // 무결성 검증용 해시 (hex)
+  compression?: 'zstd' | 'deflate' // 압축 저장된 경우 (size는 압축된 크기)
+  original_size?: number // 압축 해제(복호화) 후 크기
+  encryption?: 'aes256-gcm-chunked' // 암호화 저장된 경우
+  nonce?: string // 엔트리별 nonce (hex)
+  shared_from?: string // 내용이 같아 저장 데이터를 공유하는 엔트리 ID
+  poster_for?: string // 빌드 시 추출한 포스터 프레임이면 그 비디오 엔트리 ID
 }
 
 export interface BuildManifest {
-  projectJsonOffset: number
-  projectJsonSize: number
+  project_json_offset: number
+  project_json_size: number
   media: MediaManifestEntry[]
-  appIconOffset?: number
-  appIconSize?: number
+  app_icon_offset?: number
+  app_icon_size?: number
+  app_icon_sha256?: string
+  project_json_sha256?: string
+  verify_on_startup?: boolean // 시작 시 모든 미디어 해시 검증
+  project_json_encryption?: 'aes256-gcm-chunked'
+  project_json_nonce?: string
+  encryption_key_id?: string // 암호화 키 식별자 (키 자체가 아님)
+  license?: LicenseBlock // 기간/기기 제한 빌드의 서명된 라이선스
+  signature?: PayloadSignature // 매니페스트(내용 해시 포함)에 대한 서명
+}
+
+export interface PayloadSignature {
+  public_key: string // 서명 공개 키 (hex)
+  signature: string
+}
+
+export interface LicenseBlock {
+  issuer: string
+  issued_at: number
+  expires_at?: number
+  allowed_machine_ids: string[]
+  project_json_sha256?: string
+  public_key: string // 발급자 공개 키 (hex)
+  signature: string
 }
 
 // Rust로 전달할 미디어 정보
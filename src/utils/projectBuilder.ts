@@ -15,6 +15,85 @@ export interface BuildProgress {
   percent?: number
   step?: number
   totalSteps?: number
+  stage?: BuildStage
+}
+
+// Rust build_progress::BuildStage와 대응
+export type BuildStage =
+  | 'preparing'
+  | 'copyingMedia'
+  | 'convertingIcon'
+  | 'buildingTemplate'
+  | 'copyingTemplate'
+  | 'applyingIcon'
+  | 'strippingPayload'
+  | 'embeddingMedia'
+  | 'embeddingProject'
+  | 'finalizing'
+  | 'completed'
+  | 'cancelled'
+
+// Rust product_target::ProductTarget과 대응 (생략 시 빌더가 실행 중인 OS)
+export type ProductTarget = 'windows' | 'linux' | 'appImage' | 'macOs'
+
+// build_standalone_executable_v2 추가 옵션 (Rust BuildRequestOptions와 대응)
+export interface ProductBuildOptions {
+  target?: ProductTarget
+  verifyOnStartup?: boolean
+  // 미디어/프로젝트 데이터 암호화 (키 파일이나 템플릿에 컴파일된 키 필요)
+  encrypt?: boolean
+  encryptionKeyFile?: string
+  // 키가 없을 때 새 키를 만들어 제품 옆에 tutorial.license(평문)로 저장
+  generateEncryptionKey?: boolean
+  // 기간/기기 제한 라이선스 (발급자 서명 키로 서명)
+  license?: LicenseOptions
+  // 매니페스트와 내용 해시를 서명할 Ed25519 키 파일 (64자리 hex 시드)
+  signingKeyFile?: string
+  // 프로젝트 검사(깨진 페이지 이동, 없는 미디어 등)에서 문제가 있거나
+  // 읽을 수 없는 미디어 파일이 있으면 빌드 중단 (생략 시 경고만)
+  strict?: boolean
+}
+
+// 빌드에 넣지 못한 미디어 (Rust media_check::MissingMedia와 대응)
+export interface MissingMedia {
+  id: string
+  name: string
+  filePath: string
+  reason: string
+  pages: string[] // 이 미디어를 쓰는 페이지 ("페이지 N (제목)")
+}
+
+// 빌드 결과 (Rust BuildResult와 대응)
+export interface BuildResult {
+  outputFile: string
+  missingMedia: MissingMedia[] // 엄격 모드가 아닐 때 빼고 빌드한 미디어
+}
+
+export interface LicenseOptions {
+  issuer: string
+  expiresAt?: number // Unix 초
+  allowedMachineIds?: string[] // 제품의 machine-id 명령으로 확인한 기기 ID
+  signingKeyFile: string // Ed25519 서명 키 파일 (64자리 hex 시드)
+}
+
+// 대상별 저장 파일 확장자
+const TARGET_EXTENSIONS: Record<
+  ProductTarget,
+  { name: string; extension: string }
+> = {
+  windows: { name: 'Executable', extension: 'exe' },
+  linux: { name: 'Executable', extension: '' },
+  appImage: { name: 'AppImage', extension: 'AppImage' },
+  macOs: { name: 'Application', extension: 'app' },
+}
+
+// Rust에서 오는 build-progress 이벤트
+interface BuildProgressEvent {
+  stage: BuildStage
+  message: string
+  bytesWritten: number | null
+  bytesTotal: number | null
+  mediaId: string | null
 }
 
 // MIME 타입 추출
@@ -141,8 +220,9 @@ async function prepareMediaFiles(
 // 독립 실행 파일 빌드 (각 프로젝트마다 별도의 exe)
 export async function buildStandaloneExecutable(
   project: Project,
-  onProgress?: (progress: BuildProgress) => void
-): Promise<boolean> {
+  onProgress?: (progress: BuildProgress) => void,
+  options: ProductBuildOptions = {}
+): Promise<BuildResult | null> {
   // Tauri 환경 확인
   if (!isTauriEnvironment()) {
     console.error('Tauri internals not found:', {
@@ -163,28 +243,28 @@ export async function buildStandaloneExecutable(
 
   try {
     // 저장 위치 선택
+    const { name: filterName, extension } =
+      TARGET_EXTENSIONS[options.target ?? 'windows']
     const outputFile = await save({
-      defaultPath: `${project.name}.exe`,
-      filters: [
-        {
-          name: 'Executable',
-          extensions: ['exe'],
-        },
-      ],
+      defaultPath: extension ? `${project.name}.${extension}` : project.name,
+      filters: extension ? [{ name: filterName, extensions: [extension] }] : [],
       title: '실행 파일을 저장할 위치를 선택하세요',
     })
 
     if (!outputFile) {
-      return false
+      return null
     }
 
     // 진행 상황 리스너 등록 (Rust에서 오는 이벤트)
-    const unlisten = await listen<string>('build-progress', (event) => {
+    // 바이트 단위 진행률이 있으면 30~100% 구간에 반영
+    let lastPercent = 30
+    const unlisten = await listen<BuildProgressEvent>('build-progress', (event) => {
+      const { stage, message, bytesWritten, bytesTotal } = event.payload
+      if (bytesTotal) {
+        lastPercent = 30 + Math.round((70 * (bytesWritten ?? 0)) / bytesTotal)
+      }
       if (onProgress) {
-        onProgress({
-          message: event.payload,
-          percent: 30 + Math.min(70, 70),
-        })
+        onProgress({ message, percent: lastPercent, stage })
       }
     })
 
@@ -222,20 +302,27 @@ export async function buildStandaloneExecutable(
       const mediaInfoJson = JSON.stringify(mediaFiles)
 
       // Rust 백엔드 호출
-      const result = await invoke<string>('build_standalone_executable_v2', {
-        projectJson,
-        mediaInfoJson,
-        outputFile,
-        appIconPath: appIconPath || null,
-        tempDir: buildTempDir,
-      })
+      const result = await invoke<BuildResult>(
+        'build_standalone_executable_v2',
+        {
+          projectJson,
+          mediaInfoJson,
+          outputFile,
+          appIconPath: appIconPath || null,
+          tempDir: buildTempDir,
+          options,
+        }
+      )
 
       if (onProgress) {
         onProgress({ message: '빌드 완료!', percent: 100 })
       }
 
-      console.log('독립 실행 파일 빌드 완료:', result)
-      return true
+      if (result.missingMedia.length > 0) {
+        console.warn('빌드에 포함되지 않은 미디어:', result.missingMedia)
+      }
+      console.log('독립 실행 파일 빌드 완료:', result.outputFile)
+      return result
     } finally {
       unlisten()
     }
@@ -248,3 +335,8 @@ export async function buildStandaloneExecutable(
     throw error
   }
 }
+
+// 진행 중인 빌드 취소 (불완전한 출력 파일은 Rust에서 삭제)
+export async function cancelBuild(): Promise<void> {
+  await invoke('cancel_build')
+}